@@ -0,0 +1,87 @@
+use navigation_tree::nav_tree;
+use navigation_tree::navigation::{Entry, Message, SortKey, Stored, Tree};
+use navigation_tree::tasks::Tasks;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+const ENTRIES: usize = 100_000;
+
+fn main() {
+    let directory = PathBuf::from("/bench");
+    let entries = entries(&directory);
+
+    let open = measure(10, || {
+        let mut tree = Tree::new(directory.clone(), None, Stored::default());
+        let read = read(&directory, entries.clone());
+
+        let start = Instant::now();
+        tree.apply(read);
+        let elapsed = start.elapsed();
+
+        assert_eq!(tree.entries().len(), ENTRIES);
+        elapsed
+    });
+
+    let mut tree = Tree::new(directory.clone(), None, Stored::default());
+    tree.apply(read(&directory, entries.clone()));
+    tree.apply(Message::SortBy(SortKey::Type));
+    tree.apply(Message::ReverseSort);
+
+    let rebuilt = measure(20, || {
+        tree.apply(Message::TreeScrolled);
+
+        let start = Instant::now();
+        assert_eq!(tree.rows().len(), ENTRIES);
+        start.elapsed()
+    });
+
+    let cached = measure(200, || {
+        let start = Instant::now();
+        assert_eq!(tree.rows().len(), ENTRIES);
+        start.elapsed()
+    });
+
+    let mut state = nav_tree::State::new(directory.clone(), Tasks::default());
+    let _ = state.update(read(&directory, entries));
+
+    let view = measure(20, || {
+        let start = Instant::now();
+        let element = nav_tree::view(&mut state, nav_tree::default_row);
+        let elapsed = start.elapsed();
+
+        drop(element);
+        elapsed
+    });
+
+    println!("open a directory of {} entries: {:?}", ENTRIES, open);
+    println!("rows, rebuilt after a message:    {:?}", rebuilt);
+    println!("rows, cached between messages:    {:?}", cached);
+    println!("view of the whole tree:           {:?}", view);
+}
+
+fn entries(directory: &Path) -> Vec<Entry> {
+    (0..ENTRIES)
+        .map(|i| {
+            let name = format!("file-{:06}.txt", (i * 7919) % ENTRIES);
+
+            Entry::File {
+                path: directory.join(&name),
+                name,
+                executable: false,
+            }
+        })
+        .collect()
+}
+
+fn read(directory: &Path, entries: Vec<Entry>) -> Message {
+    Message::DirectoryRead(Ok((
+        directory.to_path_buf(),
+        Some(SystemTime::UNIX_EPOCH),
+        entries,
+    )))
+}
+
+fn measure(iterations: u32, mut run: impl FnMut() -> Duration) -> Duration {
+    (0..iterations).map(|_| run()).sum::<Duration>() / iterations
+}
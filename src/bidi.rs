@@ -0,0 +1,138 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Left,
+    Right,
+    Number,
+    Neutral,
+}
+
+impl Class {
+    fn of(c: char) -> Self {
+        match c as u32 {
+            0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF => Class::Right,
+            _ if c.is_ascii_digit() => Class::Number,
+            _ if c.is_alphabetic() => Class::Left,
+            _ => Class::Neutral,
+        }
+    }
+}
+
+pub fn has_right_to_left(text: &str) -> bool {
+    text.chars().any(|c| Class::of(c) == Class::Right)
+}
+
+pub fn display(text: &str, right_to_left: bool) -> String {
+    if !right_to_left && !has_right_to_left(text) {
+        return text.to_string();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let base = if right_to_left {
+        Class::Right
+    } else {
+        Class::Left
+    };
+
+    let mut classes: Vec<Class> = chars.iter().map(|c| Class::of(*c)).collect();
+
+    let mut last_strong = base;
+    for class in classes.iter_mut() {
+        match *class {
+            Class::Left | Class::Right => last_strong = *class,
+            Class::Number if last_strong == Class::Left => *class = Class::Left,
+            _ => {}
+        }
+    }
+
+    let strong = |class: Class| match class {
+        Class::Number => Class::Right,
+        class => class,
+    };
+
+    let mut index = 0;
+    while index < classes.len() {
+        if classes[index] != Class::Neutral {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < classes.len() && classes[index] == Class::Neutral {
+            index += 1;
+        }
+
+        let before = start.checked_sub(1).map_or(base, |i| strong(classes[i]));
+        let after = classes.get(index).map_or(base, |class| strong(*class));
+        let resolved = if before == after { before } else { base };
+
+        classes[start..index].fill(resolved);
+    }
+
+    let levels: Vec<u8> = classes
+        .iter()
+        .map(|class| match (base, class) {
+            (Class::Left, Class::Left) => 0,
+            (Class::Left, Class::Right) | (Class::Right, Class::Right) => 1,
+            _ => 2,
+        })
+        .collect();
+
+    let mut visual: Vec<(char, u8)> = chars.into_iter().zip(levels).collect();
+
+    for level in (1..=2).rev() {
+        let mut index = 0;
+
+        while index < visual.len() {
+            if visual[index].1 < level {
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+            while index < visual.len() && visual[index].1 >= level {
+                index += 1;
+            }
+
+            visual[start..index].reverse();
+        }
+    }
+
+    visual
+        .into_iter()
+        .map(|(c, level)| if level % 2 == 1 { mirror(c) } else { c })
+        .collect()
+}
+
+fn mirror(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_left_to_right_text_alone() {
+        assert_eq!(display("abc (1)", false), "abc (1)");
+        assert!(!has_right_to_left("abc"));
+        assert!(has_right_to_left("a ש"));
+    }
+
+    #[test]
+    fn reorders_right_to_left_runs() {
+        assert_eq!(display("שלום", false), "םולש");
+        assert_eq!(display("abc שלום def", false), "abc םולש def");
+        assert_eq!(display("שלום 123", true), "123 םולש");
+        assert_eq!(display("(שלום)", true), "(םולש)");
+    }
+}
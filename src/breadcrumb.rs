@@ -0,0 +1,159 @@
+use iced::button::{self, Button};
+use iced::pick_list::{self, PickList};
+use iced::{Align, Element, Row, Text};
+
+use crate::bidi;
+use crate::i18n;
+use crate::style;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub const CHARACTER_WIDTH: usize = 8;
+const SEGMENT_PADDING: usize = 16;
+const TEXT_SIZE: u16 = 14;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Crumb {
+    Collapsed,
+    Ancestor(PathBuf),
+}
+
+impl fmt::Display for Crumb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Crumb::Collapsed => f.write_str("…"),
+            Crumb::Ancestor(path) => f.write_str(&label(path)),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    buttons: Vec<button::State>,
+    hidden: pick_list::State<Crumb>,
+}
+
+pub fn view<'a>(state: &'a mut State, directory: &Path, max_width: usize) -> Element<'a, PathBuf> {
+    let mut ancestors: Vec<_> = directory.ancestors().map(Path::to_path_buf).collect();
+    ancestors.reverse();
+
+    let (head, hidden, tail) = collapse(ancestors, max_width);
+
+    state
+        .buttons
+        .resize_with(head.len() + tail.len(), button::State::new);
+
+    let mut buttons = state.buttons.iter_mut();
+    let mut segments = vec![];
+
+    for path in head.iter() {
+        push_segment(&mut segments, buttons.next(), path, directory);
+    }
+
+    if !hidden.is_empty() {
+        let options: Vec<_> = hidden.into_iter().map(Crumb::Ancestor).collect();
+        let current = directory.to_path_buf();
+
+        let dropdown = PickList::new(
+            &mut state.hidden,
+            options,
+            Some(Crumb::Collapsed),
+            move |crumb| match crumb {
+                Crumb::Ancestor(path) => path,
+                Crumb::Collapsed => current.clone(),
+            },
+        )
+        .text_size(TEXT_SIZE)
+        .padding(3);
+
+        segments.push(dropdown.into());
+        segments.push(Text::new("/").size(TEXT_SIZE).into());
+    }
+
+    for path in tail.iter() {
+        push_segment(&mut segments, buttons.next(), path, directory);
+    }
+
+    if i18n::is_right_to_left() {
+        segments.reverse();
+    }
+
+    segments
+        .into_iter()
+        .fold(Row::new().spacing(2).align_items(Align::Center), Row::push)
+        .into()
+}
+
+fn push_segment<'a>(
+    segments: &mut Vec<Element<'a, PathBuf>>,
+    button: Option<&'a mut button::State>,
+    path: &Path,
+    directory: &Path,
+) {
+    let button = match button {
+        Some(button) => button,
+        None => return,
+    };
+
+    let segment = Button::new(button, Text::new(label(path)).size(TEXT_SIZE))
+        .padding(3)
+        .on_press(path.to_path_buf());
+
+    if path == directory {
+        segments.push(segment.style(style::Selected).into());
+
+        return;
+    }
+
+    segments.push(segment.style(style::GroupHeader).into());
+
+    if path.parent().is_some() {
+        segments.push(Text::new("/").size(TEXT_SIZE).into());
+    }
+}
+
+fn collapse(
+    ancestors: Vec<PathBuf>,
+    max_width: usize,
+) -> (Vec<PathBuf>, Vec<PathBuf>, Vec<PathBuf>) {
+    let total: usize = ancestors.iter().map(|path| width(path)).sum();
+
+    if total <= max_width || ancestors.len() <= 2 {
+        return (ancestors, vec![], vec![]);
+    }
+
+    let mut remaining = ancestors;
+    let head = vec![remaining.remove(0)];
+
+    let mut budget = max_width.saturating_sub(width(&head[0]) + CHARACTER_WIDTH + SEGMENT_PADDING);
+    let mut tail = vec![];
+
+    while let Some(path) = remaining.last() {
+        let needed = width(path);
+
+        if !tail.is_empty() && needed > budget {
+            break;
+        }
+
+        budget = budget.saturating_sub(needed);
+        tail.push(remaining.pop().unwrap_or_default());
+    }
+
+    tail.reverse();
+
+    (head, remaining, tail)
+}
+
+fn width(path: &Path) -> usize {
+    label(path).chars().count() * CHARACTER_WIDTH + SEGMENT_PADDING
+}
+
+fn label(path: &Path) -> String {
+    let label = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+    bidi::display(&label, i18n::is_right_to_left())
+}
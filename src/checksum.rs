@@ -0,0 +1,294 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const READ_BUFFER: usize = 64 * 1024;
+const MANIFEST_SUFFIX: &str = ".sha256";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Ok,
+    Failed,
+    Missing,
+}
+
+pub fn is_manifest(name: &str) -> bool {
+    let name = name.to_lowercase();
+
+    name == "sha256sums" || name == "sha256sums.txt" || name.ends_with(MANIFEST_SUFFIX)
+}
+
+pub fn parse(manifest: &Path, contents: &str) -> Vec<(PathBuf, String)> {
+    let directory = manifest.parent().unwrap_or_else(|| Path::new(""));
+
+    let single_target = manifest
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .filter(|name| name.to_lowercase().ends_with(MANIFEST_SUFFIX))
+        .map(|name| name[..name.len() - MANIFEST_SUFFIX.len()].to_string());
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (hash, name) = match line.strip_prefix("SHA256 (") {
+                Some(rest) => {
+                    let (name, hash) = rest.rsplit_once(") = ")?;
+
+                    (hash.trim(), name.to_string())
+                }
+                None => {
+                    let (hash, rest) =
+                        line.split_at(line.find(char::is_whitespace).unwrap_or(line.len()));
+                    let name = rest.trim_start().trim_start_matches('*');
+
+                    if name.is_empty() {
+                        (hash, single_target.clone()?)
+                    } else {
+                        (hash, name.to_string())
+                    }
+                }
+            };
+
+            if hash.len() != 64 || !hash.chars().all(|character| character.is_ascii_hexdigit()) {
+                return None;
+            }
+
+            Some((directory.join(name), hash.to_lowercase()))
+        })
+        .collect()
+}
+
+pub fn verify(path: &Path, expected: &str, is_cancelled: impl Fn() -> bool) -> Option<Verdict> {
+    if !path.is_file() {
+        return Some(Verdict::Missing);
+    }
+
+    match sha256_file(path, is_cancelled) {
+        Ok(Some(digest)) if digest == expected => Some(Verdict::Ok),
+        Ok(Some(_)) | Err(_) => Some(Verdict::Failed),
+        Ok(None) => None,
+    }
+}
+
+fn sha256_file(path: &Path, is_cancelled: impl Fn() -> bool) -> io::Result<Option<String>> {
+    let mut file = fs::File::open(path)?;
+    let mut buffer = vec![0; READ_BUFFER];
+    let mut hasher = Sha256::new();
+
+    loop {
+        if is_cancelled() {
+            return Ok(None);
+        }
+
+        let read = file.read(&mut buffer)?;
+
+        if read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..read]);
+    }
+
+    let digest = hasher.finish();
+
+    Ok(Some(
+        digest.iter().map(|byte| format!("{:02x}", byte)).collect(),
+    ))
+}
+
+struct Sha256 {
+    state: [u32; 8],
+    pending: Vec<u8>,
+    length: u64,
+}
+
+impl Sha256 {
+    fn new() -> Self {
+        Self {
+            state: INITIAL_STATE,
+            pending: Vec::with_capacity(64),
+            length: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+
+        if !self.pending.is_empty() {
+            let take = (64 - self.pending.len()).min(data.len());
+
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+
+            if self.pending.len() < 64 {
+                return;
+            }
+
+            let block = std::mem::take(&mut self.pending);
+            compress(&mut self.state, &block);
+        }
+
+        let mut blocks = data.chunks_exact(64);
+
+        for block in &mut blocks {
+            compress(&mut self.state, block);
+        }
+
+        self.pending.extend_from_slice(blocks.remainder());
+    }
+
+    fn finish(mut self) -> [u8; 32] {
+        let bits = self.length.wrapping_mul(8);
+        let used = (self.length % 64) as usize;
+        let padding = if used < 56 { 56 - used } else { 120 - used };
+
+        let mut tail = vec![0; padding];
+        tail[0] = 0x80;
+        tail.extend_from_slice(&bits.to_be_bytes());
+
+        self.update(&tail);
+
+        let mut digest = [0; 32];
+
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+
+        digest
+    }
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut schedule = [0u32; 64];
+
+    for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+
+    for index in 16..64 {
+        let s0 = schedule[index - 15].rotate_right(7)
+            ^ schedule[index - 15].rotate_right(18)
+            ^ (schedule[index - 15] >> 3);
+        let s1 = schedule[index - 2].rotate_right(17)
+            ^ schedule[index - 2].rotate_right(19)
+            ^ (schedule[index - 2] >> 10);
+
+        schedule[index] = schedule[index - 16]
+            .wrapping_add(s0)
+            .wrapping_add(schedule[index - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    for (k, word) in K.iter().zip(schedule.iter()) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(choice)
+            .wrapping_add(*k)
+            .wrapping_add(*word);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(majority);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (value, added) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *value = value.wrapping_add(added);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(data: &[&[u8]]) -> String {
+        let mut hasher = Sha256::new();
+
+        for chunk in data {
+            hasher.update(chunk);
+        }
+
+        hasher
+            .finish()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    #[test]
+    fn matches_known_sha256_vectors() {
+        assert_eq!(
+            hex(&[b""]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(&[b"abc"]),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(&[
+                b"abcdbcdecdefdefgefghfghighijhi",
+                b"jkijkljklmklmnlmnomnopnopq"
+            ]),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+
+        let million = vec![b'a'; 1_000_000];
+        let (first, rest) = million.split_at(63);
+
+        assert_eq!(
+            hex(&[first, rest]),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    #[test]
+    fn parses_both_manifest_formats() {
+        let hash = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        let contents = format!(
+            "# comment\n{} *a.txt\nSHA256 (b c.txt) = {}\nshort  d.txt\n",
+            hash.to_uppercase(),
+            hash
+        );
+
+        assert_eq!(
+            parse(Path::new("/dir/SHA256SUMS"), &contents),
+            [
+                (PathBuf::from("/dir/a.txt"), hash.to_string()),
+                (PathBuf::from("/dir/b c.txt"), hash.to_string()),
+            ]
+        );
+        assert_eq!(
+            parse(Path::new("/dir/e.iso.sha256"), hash),
+            [(PathBuf::from("/dir/e.iso"), hash.to_string())]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use iced_graphics::{Backend, Defaults, Primitive, Renderer};
+use iced_native::{
+    event, layout, mouse, Background, Clipboard, Color, Element, Event, Hasher, Layout, Length,
+    Point, Rectangle, Size, Widget,
+};
+
+use crate::style;
+
+use std::hash::Hash;
+
+pub const WIDTH: u16 = 8;
+const HEIGHT: u16 = 24;
+
+#[derive(Debug, Default)]
+pub struct State {
+    dragged_from: Option<f32>,
+}
+
+pub struct Handle<'a, Message> {
+    state: &'a mut State,
+    on_drag: Box<dyn Fn(f32) -> Message + 'a>,
+    on_release: Message,
+}
+
+impl<'a, Message> Handle<'a, Message> {
+    pub fn new(
+        state: &'a mut State,
+        on_drag: impl Fn(f32) -> Message + 'a,
+        on_release: Message,
+    ) -> Self {
+        Self {
+            state,
+            on_drag: Box::new(on_drag),
+            on_release,
+        }
+    }
+}
+
+impl<'a, Message, B> Widget<Message, Renderer<B>> for Handle<'a, Message>
+where
+    Message: Clone,
+    B: Backend,
+{
+    fn width(&self) -> Length {
+        Length::Units(WIDTH)
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(HEIGHT)
+    }
+
+    fn layout(&self, _renderer: &Renderer<B>, limits: &layout::Limits) -> layout::Node {
+        let size = limits
+            .width(Length::Units(WIDTH))
+            .height(Length::Units(HEIGHT))
+            .resolve(Size::new(f32::from(WIDTH), f32::from(HEIGHT)));
+
+        layout::Node::new(size)
+    }
+
+    fn draw(
+        &self,
+        _renderer: &mut Renderer<B>,
+        _defaults: &Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _viewport: &Rectangle,
+    ) -> (Primitive, mouse::Interaction) {
+        let bounds = layout.bounds();
+        let is_active = self.state.dragged_from.is_some() || bounds.contains(cursor_position);
+
+        let line = Primitive::Quad {
+            bounds: Rectangle {
+                x: (bounds.center_x() - 0.5).round(),
+                width: 1.0,
+                ..bounds
+            },
+            background: Background::Color(if is_active {
+                style::SELECTION
+            } else {
+                style::INDENT_GUIDE
+            }),
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+        };
+
+        let interaction = if is_active {
+            mouse::Interaction::ResizingHorizontally
+        } else {
+            mouse::Interaction::default()
+        };
+
+        (line, interaction)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _renderer: &Renderer<B>,
+        _clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                if layout.bounds().contains(cursor_position) =>
+            {
+                self.state.dragged_from = Some(cursor_position.x);
+
+                event::Status::Captured
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let from = match self.state.dragged_from {
+                    Some(from) => from,
+                    None => return event::Status::Ignored,
+                };
+
+                if cursor_position.x != from {
+                    self.state.dragged_from = Some(cursor_position.x);
+
+                    messages.push((self.on_drag)(cursor_position.x - from));
+                }
+
+                event::Status::Captured
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if self.state.dragged_from.take().is_none() {
+                    return event::Status::Ignored;
+                }
+
+                messages.push(self.on_release.clone());
+
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+}
+
+impl<'a, Message, B> From<Handle<'a, Message>> for Element<'a, Message, Renderer<B>>
+where
+    Message: 'a + Clone,
+    B: 'a + Backend,
+{
+    fn from(handle: Handle<'a, Message>) -> Self {
+        Element::new(handle)
+    }
+}
@@ -0,0 +1,419 @@
+use iced::button::{self, Button};
+use iced::scrollable::{self, Scrollable};
+use iced::text_input::{self, TextInput};
+use iced::{Align, Column, Command, Container, Element, Length, Row, Space, Text};
+use walkdir::WalkDir;
+
+use crate::file_ops::Request;
+use crate::font;
+use crate::i18n;
+use crate::style;
+use crate::tasks::{self, Tasks};
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    RightChanged(String),
+    Compare,
+    Compared(Result<Vec<Difference>, String>),
+    Sync(usize, Side),
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Sync(Request),
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    OnlyLeft,
+    OnlyRight,
+    Different,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Kind::OnlyLeft => "Only in left",
+            Kind::OnlyRight => "Only in right",
+            Kind::Different => "Different",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Difference {
+    relative: PathBuf,
+    kind: Kind,
+    is_dir: bool,
+}
+
+pub struct State {
+    left: PathBuf,
+    right: String,
+    compared: Option<PathBuf>,
+    differences: Option<Result<Vec<Difference>, String>>,
+    running: Option<tasks::Handle>,
+    tasks: Tasks,
+    right_input: text_input::State,
+    compare_button: button::State,
+    close_button: button::State,
+    row_buttons: Vec<(button::State, button::State)>,
+    scrollable: scrollable::State,
+}
+
+impl State {
+    pub fn new(left: PathBuf, tasks: &Tasks) -> Self {
+        Self {
+            right: left.parent().unwrap_or(&left).to_string_lossy().to_string(),
+            left,
+            compared: None,
+            differences: None,
+            running: None,
+            tasks: tasks.clone(),
+            right_input: text_input::State::focused(),
+            compare_button: button::State::new(),
+            close_button: button::State::new(),
+            row_buttons: vec![],
+            scrollable: scrollable::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> (Command<Message>, Option<Event>) {
+        match message {
+            Message::RightChanged(right) => {
+                self.right = right;
+            }
+            Message::Compare => {
+                let right = PathBuf::from(&self.right);
+                let task = self
+                    .tasks
+                    .start(format!("Comparing {:?} with {:?}", self.left, right));
+
+                self.compared = Some(right.clone());
+                self.differences = None;
+                self.running = Some(task.clone());
+
+                return (
+                    Command::perform(compare(self.left.clone(), right, task), Message::Compared),
+                    None,
+                );
+            }
+            Message::Compared(result) => {
+                self.running = None;
+                self.differences = Some(result);
+            }
+            Message::Sync(index, side) => {
+                let right = match &self.compared {
+                    Some(right) => right.clone(),
+                    None => return (Command::none(), None),
+                };
+
+                if let Some(Ok(differences)) = &mut self.differences {
+                    if index < differences.len() {
+                        let difference = differences.remove(index);
+
+                        let (from, to) = match side {
+                            Side::Left => (
+                                self.left.join(&difference.relative),
+                                right.join(&difference.relative),
+                            ),
+                            Side::Right => (
+                                right.join(&difference.relative),
+                                self.left.join(&difference.relative),
+                            ),
+                        };
+
+                        let request = match difference.kind {
+                            Kind::Different => Request::Overwrite { from, to },
+                            Kind::OnlyLeft | Kind::OnlyRight => Request::Copy { from, to },
+                        };
+
+                        return (Command::none(), Some(Event::Sync(request)));
+                    }
+                }
+            }
+            Message::Close => {
+                if let Some(task) = self.running.take() {
+                    task.cancel();
+                }
+
+                return (Command::none(), Some(Event::Closed));
+            }
+        }
+
+        (Command::none(), None)
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let right = TextInput::new(
+        &mut state.right_input,
+        i18n::tr("Directory to compare with"),
+        &state.right,
+        Message::RightChanged,
+    )
+    .padding(5)
+    .on_submit(Message::Compare);
+
+    let mut compare = Button::new(&mut state.compare_button, Text::new(i18n::tr("Compare")));
+
+    if state.running.is_none() {
+        compare = compare.on_press(Message::Compare);
+    }
+
+    let close =
+        Button::new(&mut state.close_button, Text::new(i18n::tr("Close"))).on_press(Message::Close);
+
+    let mut content = Column::new()
+        .spacing(10)
+        .max_width(900)
+        .push(Text::new(i18n::tr("Compare directories")).size(30))
+        .push(Text::new(i18n::tr_with(
+            "Left: {}",
+            &[&format!("{:?}", state.left)],
+        )))
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(Text::new(i18n::tr("Right:")))
+                .push(right)
+                .push(compare)
+                .push(close),
+        );
+
+    let differences = match (&state.running, &state.differences) {
+        (Some(_), _) => {
+            return Container::new(content.push(Text::new(i18n::tr("Comparing..."))))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding(40)
+                .center_x()
+                .into();
+        }
+        (None, Some(Err(error))) => {
+            content = content.push(Text::new(error.as_str()).color(style::ERROR));
+            None
+        }
+        (None, Some(Ok(differences))) if differences.is_empty() => {
+            content = content.push(Text::new(i18n::tr("The directories are identical")));
+            None
+        }
+        (None, Some(Ok(differences))) => Some(differences),
+        (None, None) => None,
+    };
+
+    if let Some(differences) = differences {
+        state
+            .row_buttons
+            .resize_with(differences.len(), Default::default);
+
+        let rows = differences
+            .iter()
+            .zip(state.row_buttons.iter_mut())
+            .enumerate()
+            .fold(
+                Scrollable::new(&mut state.scrollable).spacing(5),
+                |scrollable, (index, (difference, (to_right, to_left)))| {
+                    let marker = if difference.is_dir { "/" } else { "" };
+
+                    let mut row = Row::new()
+                        .spacing(10)
+                        .align_items(Align::Center)
+                        .push(
+                            Text::new(difference.kind.to_string())
+                                .size(14)
+                                .width(Length::Units(110)),
+                        )
+                        .push(
+                            Text::new(format!("{}{}", difference.relative.display(), marker))
+                                .font(font::MONOSPACE),
+                        )
+                        .push(Space::with_width(Length::Fill));
+
+                    if difference.kind != Kind::OnlyRight {
+                        row = row.push(
+                            Button::new(to_right, Text::new(i18n::tr("Copy to right")).size(14))
+                                .padding(3)
+                                .on_press(Message::Sync(index, Side::Left)),
+                        );
+                    }
+
+                    if difference.kind != Kind::OnlyLeft {
+                        row = row.push(
+                            Button::new(to_left, Text::new(i18n::tr("Copy to left")).size(14))
+                                .padding(3)
+                                .on_press(Message::Sync(index, Side::Right)),
+                        );
+                    }
+
+                    scrollable.push(row)
+                },
+            );
+
+        content = content
+            .push(Text::new(i18n::tr_with(
+                "{} differences",
+                &[&differences.len()],
+            )))
+            .push(rows);
+    }
+
+    Container::new(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(40)
+        .center_x()
+        .into()
+}
+
+async fn compare(
+    left: PathBuf,
+    right: PathBuf,
+    task: tasks::Handle,
+) -> Result<Vec<Difference>, String> {
+    if !right.is_dir() {
+        return Err(format!("{:?} is not a directory", right));
+    }
+
+    if left == right {
+        return Err("Cannot compare a directory with itself".into());
+    }
+
+    let left_entries = entries(&left);
+    let right_entries = entries(&right);
+
+    let total = (left_entries.len() + right_entries.len()) as u64;
+    let mut done = 0;
+
+    let mut differences = vec![];
+    let mut only_left = HashSet::new();
+    let mut only_right = HashSet::new();
+
+    for (relative, is_dir) in left_entries.iter() {
+        if task.is_cancelled() {
+            return Err("Cancelled".into());
+        }
+
+        done += 1;
+        task.set_progress(done, total, format!("{} of {} entries", done, total));
+
+        if has_ancestor_in(relative, &only_left) {
+            continue;
+        }
+
+        let kind = match right_entries.get(relative) {
+            None => {
+                if *is_dir {
+                    only_left.insert(relative.clone());
+                }
+
+                Kind::OnlyLeft
+            }
+            Some(right_is_dir) if right_is_dir != is_dir => Kind::Different,
+            Some(_) if *is_dir => continue,
+            Some(_) => {
+                let same = same_contents(&left.join(relative), &right.join(relative))
+                    .map_err(|error| format!("Cannot compare {:?}: {}", relative, error))?;
+
+                if same {
+                    continue;
+                }
+
+                Kind::Different
+            }
+        };
+
+        differences.push(Difference {
+            relative: relative.clone(),
+            kind,
+            is_dir: *is_dir,
+        });
+    }
+
+    for (relative, is_dir) in right_entries.iter() {
+        done += 1;
+        task.set_progress(done, total, format!("{} of {} entries", done, total));
+
+        if left_entries.contains_key(relative) || has_ancestor_in(relative, &only_right) {
+            continue;
+        }
+
+        if *is_dir {
+            only_right.insert(relative.clone());
+        }
+
+        differences.push(Difference {
+            relative: relative.clone(),
+            kind: Kind::OnlyRight,
+            is_dir: *is_dir,
+        });
+    }
+
+    differences.sort_by(|a, b| a.relative.cmp(&b.relative));
+
+    Ok(differences)
+}
+
+fn entries(root: &Path) -> BTreeMap<PathBuf, bool> {
+    WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(root).ok()?.to_path_buf();
+
+            Some((relative, entry.file_type().is_dir()))
+        })
+        .collect()
+}
+
+fn has_ancestor_in(path: &Path, directories: &HashSet<PathBuf>) -> bool {
+    path.ancestors()
+        .skip(1)
+        .any(|ancestor| directories.contains(ancestor))
+}
+
+fn same_contents(left: &Path, right: &Path) -> io::Result<bool> {
+    if fs::metadata(left)?.len() != fs::metadata(right)?.len() {
+        return Ok(false);
+    }
+
+    let mut left = fs::File::open(left)?;
+    let mut right = fs::File::open(right)?;
+
+    let mut left_buffer = vec![0; CHUNK_SIZE];
+    let mut right_buffer = vec![0; CHUNK_SIZE];
+
+    loop {
+        let read = left.read(&mut left_buffer)?;
+
+        if read == 0 {
+            return Ok(true);
+        }
+
+        right.read_exact(&mut right_buffer[..read])?;
+
+        if left_buffer[..read] != right_buffer[..read] {
+            return Ok(false);
+        }
+    }
+}
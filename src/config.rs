@@ -0,0 +1,400 @@
+use iced::pick_list::{self, PickList};
+use iced::text_input::{self, TextInput};
+use iced::{Checkbox, Column, Element, Font, Length, Row, Text};
+
+use crate::font;
+use crate::i18n::{self, Language};
+use crate::keymap::KeyMap;
+use crate::launch;
+use crate::navigation::Grouping;
+use crate::storage;
+use crate::transfer::Policy;
+
+use std::fmt;
+use std::path::Path;
+
+pub const CONFIG: &str = "config";
+
+const MONOSPACE_EXTENSIONS: &[&str] = &[
+    "c", "cfg", "conf", "cpp", "cs", "css", "csv", "diff", "go", "h", "hpp", "html", "ini", "java",
+    "js", "json", "kt", "lock", "log", "lua", "patch", "py", "rb", "rs", "sh", "sql", "toml", "ts",
+    "xml", "yaml", "yml", "zig",
+];
+
+const EXPAND_DEPTHS: &[usize] = &[1, 2, 3, 5, 8];
+const TAB_WIDTHS: &[usize] = &[2, 4, 8];
+const MAX_FILE_SIZES_MB: &[u64] = &[1, 10, 50, 100, 500];
+
+const MONOSPACE_FILE_NAMES: &[&str] = &["Dockerfile", "Makefile", "CMakeLists.txt"];
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    LanguageSelected(Language),
+    ViewerFontSelected(ViewerFont),
+    GroupingSelected(Grouping),
+    ExpandDepthChanged(usize),
+    TerminalCommandChanged(String),
+    TabWidthChanged(usize),
+    ShowInvisiblesToggled(bool),
+    IndentGuidesToggled(bool),
+    ReduceMotionToggled(bool),
+    AgeColorsToggled(bool),
+    MaxFileSizeChanged(u64),
+    ConflictPolicySelected(Policy),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ViewerFont {
+    #[default]
+    ByFileType,
+    Monospace,
+    Default,
+}
+
+impl ViewerFont {
+    const ALL: [ViewerFont; 3] = [
+        ViewerFont::ByFileType,
+        ViewerFont::Monospace,
+        ViewerFont::Default,
+    ];
+
+    pub fn font_for(self, path: &Path) -> Font {
+        match self {
+            ViewerFont::ByFileType if is_monospace_file(path) => font::MONOSPACE,
+            ViewerFont::ByFileType | ViewerFont::Default => Font::Default,
+            ViewerFont::Monospace => font::MONOSPACE,
+        }
+    }
+}
+
+impl fmt::Display for ViewerFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ViewerFont::ByFileType => "Monospace for code and logs",
+            ViewerFont::Monospace => "Always monospace",
+            ViewerFont::Default => "Never monospace",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub language: Language,
+    pub viewer_font: ViewerFont,
+    pub grouping: Grouping,
+    pub expand_depth: usize,
+    pub terminal_command: String,
+    pub tab_width: usize,
+    pub show_invisibles: bool,
+    pub show_indent_guides: bool,
+    pub reduce_motion: bool,
+    pub age_colors: bool,
+    pub max_file_size_mb: u64,
+    pub conflict_policy: Policy,
+    pub keymap: KeyMap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            viewer_font: ViewerFont::default(),
+            grouping: Grouping::default(),
+            expand_depth: 3,
+            terminal_command: launch::DEFAULT_TERMINAL.to_string(),
+            tab_width: 4,
+            show_invisibles: false,
+            show_indent_guides: true,
+            reduce_motion: false,
+            age_colors: false,
+            max_file_size_mb: 10,
+            conflict_policy: Policy::default(),
+            keymap: KeyMap::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let mut config = Self::default();
+
+        for line in storage::load(CONFIG) {
+            if let Some((key, value)) = line.split_once('=') {
+                config.set(key.trim(), value.trim());
+            }
+        }
+
+        config.keymap = KeyMap::load();
+
+        config
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            format!("language={:?}", self.language),
+            format!("viewer_font={:?}", self.viewer_font),
+            format!("grouping={:?}", self.grouping),
+            format!("expand_depth={}", self.expand_depth),
+            format!("terminal_command={}", self.terminal_command),
+            format!("tab_width={}", self.tab_width),
+            format!("show_invisibles={}", self.show_invisibles),
+            format!("show_indent_guides={}", self.show_indent_guides),
+            format!("reduce_motion={}", self.reduce_motion),
+            format!("age_colors={}", self.age_colors),
+            format!("max_file_size_mb={}", self.max_file_size_mb),
+            format!("conflict_policy={:?}", self.conflict_policy),
+        ]
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "language" => set_variant(&mut self.language, &Language::ALL, value),
+            "viewer_font" => set_variant(&mut self.viewer_font, &ViewerFont::ALL, value),
+            "grouping" => set_variant(&mut self.grouping, &Grouping::ALL, value),
+            "expand_depth" => set_parsed(&mut self.expand_depth, value),
+            "terminal_command" => self.terminal_command = value.to_string(),
+            "tab_width" => set_parsed(&mut self.tab_width, value),
+            "show_invisibles" => set_parsed(&mut self.show_invisibles, value),
+            "show_indent_guides" => set_parsed(&mut self.show_indent_guides, value),
+            "reduce_motion" => set_parsed(&mut self.reduce_motion, value),
+            "age_colors" => set_parsed(&mut self.age_colors, value),
+            "max_file_size_mb" => set_parsed(&mut self.max_file_size_mb, value),
+            "conflict_policy" => set_variant(&mut self.conflict_policy, &Policy::ALL, value),
+            _ => {}
+        }
+    }
+}
+
+fn set_variant<T: Copy + fmt::Debug>(field: &mut T, variants: &[T], value: &str) {
+    if let Some(variant) = variants
+        .iter()
+        .find(|variant| format!("{:?}", variant) == value)
+    {
+        *field = *variant;
+    }
+}
+
+fn set_parsed<T: std::str::FromStr>(field: &mut T, value: &str) {
+    if let Ok(parsed) = value.parse() {
+        *field = parsed;
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    pub config: Config,
+    language: pick_list::State<Language>,
+    viewer_font: pick_list::State<ViewerFont>,
+    grouping: pick_list::State<Grouping>,
+    expand_depth: pick_list::State<usize>,
+    terminal_command: text_input::State,
+    tab_width: pick_list::State<usize>,
+    max_file_size: pick_list::State<u64>,
+    conflict_policy: pick_list::State<Policy>,
+}
+
+impl State {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::LanguageSelected(language) => {
+                self.config.language = language;
+
+                i18n::set(language);
+            }
+            Message::ViewerFontSelected(viewer_font) => {
+                self.config.viewer_font = viewer_font;
+            }
+            Message::GroupingSelected(grouping) => {
+                self.config.grouping = grouping;
+            }
+            Message::ExpandDepthChanged(expand_depth) => {
+                self.config.expand_depth = expand_depth;
+            }
+            Message::TerminalCommandChanged(terminal_command) => {
+                self.config.terminal_command = terminal_command;
+            }
+            Message::TabWidthChanged(tab_width) => {
+                self.config.tab_width = tab_width;
+            }
+            Message::ShowInvisiblesToggled(show_invisibles) => {
+                self.config.show_invisibles = show_invisibles;
+            }
+            Message::IndentGuidesToggled(show_indent_guides) => {
+                self.config.show_indent_guides = show_indent_guides;
+            }
+            Message::ReduceMotionToggled(reduce_motion) => {
+                self.config.reduce_motion = reduce_motion;
+            }
+            Message::AgeColorsToggled(age_colors) => {
+                self.config.age_colors = age_colors;
+            }
+            Message::MaxFileSizeChanged(max_file_size_mb) => {
+                self.config.max_file_size_mb = max_file_size_mb;
+            }
+            Message::ConflictPolicySelected(conflict_policy) => {
+                self.config.conflict_policy = conflict_policy;
+            }
+        }
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let language = PickList::new(
+        &mut state.language,
+        &Language::ALL[..],
+        Some(state.config.language),
+        Message::LanguageSelected,
+    );
+
+    let language = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Language")))
+        .push(language);
+
+    let viewer_font = PickList::new(
+        &mut state.viewer_font,
+        &ViewerFont::ALL[..],
+        Some(state.config.viewer_font),
+        Message::ViewerFontSelected,
+    );
+
+    let viewer_font = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Viewer font")))
+        .push(viewer_font);
+
+    let grouping = PickList::new(
+        &mut state.grouping,
+        &Grouping::ALL[..],
+        Some(state.config.grouping),
+        Message::GroupingSelected,
+    );
+
+    let grouping = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Sort directories")))
+        .push(grouping);
+
+    let expand_depth = PickList::new(
+        &mut state.expand_depth,
+        EXPAND_DEPTHS,
+        Some(state.config.expand_depth),
+        Message::ExpandDepthChanged,
+    );
+
+    let expand_depth = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Expand all depth")))
+        .push(expand_depth);
+
+    let tab_width = PickList::new(
+        &mut state.tab_width,
+        TAB_WIDTHS,
+        Some(state.config.tab_width),
+        Message::TabWidthChanged,
+    );
+
+    let tab_width = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Tab width")))
+        .push(tab_width);
+
+    let show_invisibles = Checkbox::new(
+        state.config.show_invisibles,
+        i18n::tr("Show tabs and trailing spaces"),
+        Message::ShowInvisiblesToggled,
+    );
+
+    let indent_guides = Checkbox::new(
+        state.config.show_indent_guides,
+        i18n::tr("Show indent guides"),
+        Message::IndentGuidesToggled,
+    );
+
+    let reduce_motion = Checkbox::new(
+        state.config.reduce_motion,
+        i18n::tr("Reduce motion"),
+        Message::ReduceMotionToggled,
+    );
+
+    let age_colors = Checkbox::new(
+        state.config.age_colors,
+        i18n::tr("Tint names by age"),
+        Message::AgeColorsToggled,
+    );
+
+    let max_file_size = PickList::new(
+        &mut state.max_file_size,
+        MAX_FILE_SIZES_MB,
+        Some(state.config.max_file_size_mb),
+        Message::MaxFileSizeChanged,
+    );
+
+    let max_file_size = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Warn before opening files over (MB)")))
+        .push(max_file_size);
+
+    let conflict_policy = PickList::new(
+        &mut state.conflict_policy,
+        &Policy::ALL[..],
+        Some(state.config.conflict_policy),
+        Message::ConflictPolicySelected,
+    );
+
+    let conflict_policy = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("When a copy or move target exists")))
+        .push(conflict_policy);
+
+    let terminal_command = TextInput::new(
+        &mut state.terminal_command,
+        i18n::tr("Command, {dir} is replaced by the directory"),
+        &state.config.terminal_command,
+        Message::TerminalCommandChanged,
+    )
+    .padding(5)
+    .width(Length::Units(400));
+
+    let terminal_command = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Terminal command")))
+        .push(terminal_command);
+
+    Column::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Settings")).size(30))
+        .push(language)
+        .push(viewer_font)
+        .push(tab_width)
+        .push(show_invisibles)
+        .push(max_file_size)
+        .push(grouping)
+        .push(expand_depth)
+        .push(indent_guides)
+        .push(age_colors)
+        .push(reduce_motion)
+        .push(conflict_policy)
+        .push(terminal_command)
+        .into()
+}
+
+fn is_monospace_file(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+
+    let file_name = path.file_name().map(|name| name.to_string_lossy());
+
+    extension.is_some_and(|extension| MONOSPACE_EXTENSIONS.contains(&extension.as_str()))
+        || file_name.is_some_and(|name| MONOSPACE_FILE_NAMES.contains(&name.as_ref()))
+}
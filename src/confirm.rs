@@ -0,0 +1,109 @@
+use iced::button::{self, Button};
+use iced::{Column, Command, Element, Row, Text};
+
+use crate::file_ops;
+use crate::i18n;
+use crate::style;
+
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Confirm,
+    Cancel,
+    Deleted(Result<(), String>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Deleted(PathBuf),
+    Discarded(PathBuf),
+    Failed(String),
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Purpose {
+    Delete,
+    Discard,
+}
+
+pub struct State {
+    path: PathBuf,
+    purpose: Purpose,
+    deleting: bool,
+    delete_button: button::State,
+    cancel_button: button::State,
+}
+
+impl State {
+    pub fn new(path: PathBuf) -> Self {
+        Self::with_purpose(path, Purpose::Delete)
+    }
+
+    pub fn discard(path: PathBuf) -> Self {
+        Self::with_purpose(path, Purpose::Discard)
+    }
+
+    fn with_purpose(path: PathBuf, purpose: Purpose) -> Self {
+        Self {
+            path,
+            purpose,
+            deleting: false,
+            delete_button: button::State::new(),
+            cancel_button: button::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> (Command<Message>, Option<Event>) {
+        match message {
+            Message::Confirm if self.purpose == Purpose::Discard => {
+                return (Command::none(), Some(Event::Discarded(self.path.clone())));
+            }
+            Message::Confirm if !self.deleting => {
+                self.deleting = true;
+
+                let path = self.path.clone();
+
+                let delete = async move {
+                    file_ops::remove(&path)
+                        .map_err(|error| format!("Cannot delete {:?}: {}", path, error))
+                };
+
+                return (Command::perform(delete, Message::Deleted), None);
+            }
+            Message::Confirm => {}
+            Message::Cancel => return (Command::none(), Some(Event::Cancelled)),
+            Message::Deleted(Ok(())) => {
+                return (Command::none(), Some(Event::Deleted(self.path.clone())));
+            }
+            Message::Deleted(Err(error)) => return (Command::none(), Some(Event::Failed(error))),
+        }
+
+        (Command::none(), None)
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let (question, action) = match state.purpose {
+        Purpose::Delete => ("Permanently delete {}? This cannot be undone.", "Delete"),
+        Purpose::Discard => ("Discard unsaved changes to {}?", "Discard"),
+    };
+
+    let label =
+        Text::new(i18n::tr_with(question, &[&format!("{:?}", state.path)])).color(style::ERROR);
+
+    let mut delete = Button::new(&mut state.delete_button, Text::new(i18n::tr(action)));
+    let mut cancel = Button::new(&mut state.cancel_button, Text::new(i18n::tr("Cancel")));
+
+    if !state.deleting {
+        delete = delete.on_press(Message::Confirm);
+        cancel = cancel.on_press(Message::Cancel);
+    }
+
+    Column::new()
+        .spacing(5)
+        .push(label)
+        .push(Row::new().spacing(10).push(delete).push(cancel))
+        .into()
+}
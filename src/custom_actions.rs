@@ -0,0 +1,71 @@
+use crate::plugin;
+use crate::storage;
+
+use std::path::{Path, PathBuf};
+
+pub const CUSTOM_ACTIONS: &str = "custom_actions";
+
+const PATH_PLACEHOLDER: &str = "{path}";
+const DIRECTORY_PLACEHOLDER: &str = "{dir}";
+const NAME_PLACEHOLDER: &str = "{name}";
+
+#[derive(Debug, Clone)]
+pub struct CustomAction {
+    name: String,
+    command: String,
+}
+
+impl CustomAction {
+    fn parse(line: &str) -> Option<Self> {
+        let (name, command) = line.split_once(':')?;
+        let command = command.trim().trim_matches('`').trim();
+
+        if name.trim().is_empty() || command.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name: name.trim().to_string(),
+            command: command.to_string(),
+        })
+    }
+}
+
+impl plugin::Action for CustomAction {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.command
+    }
+
+    fn command(&self, path: &Path) -> Result<(PathBuf, Vec<String>), String> {
+        let directory = path.parent().unwrap_or(path).to_string_lossy();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let path = path.to_string_lossy();
+
+        let mut parts = self.command.split_whitespace().map(|part| {
+            part.replace(PATH_PLACEHOLDER, &path)
+                .replace(DIRECTORY_PLACEHOLDER, &directory)
+                .replace(NAME_PLACEHOLDER, &name)
+        });
+
+        let program = parts
+            .next()
+            .ok_or_else(|| format!("{} has no command", self.name))?;
+
+        Ok((PathBuf::from(program), parts.collect()))
+    }
+}
+
+pub fn load() -> Vec<CustomAction> {
+    storage::load(CUSTOM_ACTIONS)
+        .iter()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| CustomAction::parse(line))
+        .collect()
+}
@@ -0,0 +1,99 @@
+use iced_native::{
+    event, layout, overlay, window, Clipboard, Element, Event, Hasher, Layout, Length, Point,
+    Rectangle, Widget,
+};
+
+use std::path::PathBuf;
+
+pub struct DropZone<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    on_drop: fn(PathBuf) -> Message,
+}
+
+impl<'a, Message, Renderer> DropZone<'a, Message, Renderer> {
+    pub fn new(
+        content: impl Into<Element<'a, Message, Renderer>>,
+        on_drop: fn(PathBuf) -> Message,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            on_drop,
+        }
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for DropZone<'a, Message, Renderer>
+where
+    Renderer: iced_native::Renderer,
+{
+    fn width(&self) -> Length {
+        self.content.width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.layout(renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        defaults: &Renderer::Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) -> Renderer::Output {
+        self.content
+            .draw(renderer, defaults, layout, cursor_position, viewport)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        self.content.hash_layout(state);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        if let Event::Window(window::Event::FileDropped(path)) = event {
+            if layout.bounds().contains(cursor_position) {
+                messages.push((self.on_drop)(path));
+
+                return event::Status::Captured;
+            }
+
+            return event::Status::Ignored;
+        }
+
+        self.content.on_event(
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            messages,
+        )
+    }
+
+    fn overlay(&mut self, layout: Layout<'_>) -> Option<overlay::Element<'_, Message, Renderer>> {
+        self.content.overlay(layout)
+    }
+}
+
+impl<'a, Message, Renderer> From<DropZone<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(drop_zone: DropZone<'a, Message, Renderer>) -> Self {
+        Element::new(drop_zone)
+    }
+}
@@ -0,0 +1,357 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const HEADER_LENGTH: u64 = 256 * 1024;
+
+const MAKE: u16 = 0x010f;
+const MODEL: u16 = 0x0110;
+const DATE_TIME: u16 = 0x0132;
+const EXIF_POINTER: u16 = 0x8769;
+const GPS_POINTER: u16 = 0x8825;
+const DATE_TAKEN: u16 = 0x9003;
+const PIXEL_WIDTH: u16 = 0xa002;
+const PIXEL_HEIGHT: u16 = 0xa003;
+const LATITUDE_REFERENCE: u16 = 0x0001;
+const LATITUDE: u16 = 0x0002;
+const LONGITUDE_REFERENCE: u16 = 0x0003;
+const LONGITUDE: u16 = 0x0004;
+
+#[derive(Debug, Clone, Default)]
+pub struct Exif {
+    pub dimensions: Option<(u32, u32)>,
+    pub camera: Option<String>,
+    pub taken: Option<String>,
+    pub location: Option<(f64, f64)>,
+}
+
+impl Exif {
+    pub fn is_empty(&self) -> bool {
+        self.dimensions.is_none()
+            && self.camera.is_none()
+            && self.taken.is_none()
+            && self.location.is_none()
+    }
+}
+
+pub fn is_photo(path: &Path) -> bool {
+    path.extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .is_some_and(|extension| matches!(extension.as_str(), "jpg" | "jpeg" | "jpe" | "jfif"))
+}
+
+pub async fn read(path: PathBuf) -> Option<Exif> {
+    let mut header = vec![];
+
+    File::open(&path)
+        .ok()?
+        .take(HEADER_LENGTH)
+        .read_to_end(&mut header)
+        .ok()?;
+
+    let exif = parse(&header)?;
+
+    (!exif.is_empty()).then_some(exif)
+}
+
+fn parse(jpeg: &[u8]) -> Option<Exif> {
+    if jpeg.get(..2)? != [0xff, 0xd8] {
+        return None;
+    }
+
+    let mut exif = Exif::default();
+    let mut frame = None;
+    let mut position = 2;
+
+    while let (Some(0xff), Some(&marker)) = (jpeg.get(position), jpeg.get(position + 1)) {
+        if marker == 0xff {
+            position += 1;
+            continue;
+        }
+
+        if marker == 0xd9 || marker == 0xda {
+            break;
+        }
+
+        let length = usize::from(u16::from_be_bytes([
+            *jpeg.get(position + 2)?,
+            *jpeg.get(position + 3)?,
+        ]));
+        let segment = jpeg.get(position + 4..position + 2 + length)?;
+
+        match marker {
+            0xe1 if segment.starts_with(b"Exif\0\0") => {
+                if let Some(tiff) = Tiff::new(&segment[6..]) {
+                    tiff.read(&mut exif);
+                }
+            }
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                let height = u16::from_be_bytes([*segment.get(1)?, *segment.get(2)?]);
+                let width = u16::from_be_bytes([*segment.get(3)?, *segment.get(4)?]);
+
+                frame = Some((u32::from(width), u32::from(height)));
+            }
+            _ => {}
+        }
+
+        position += 2 + length;
+    }
+
+    exif.dimensions = exif.dimensions.or(frame);
+
+    Some(exif)
+}
+
+struct Tiff<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+struct Entry {
+    tag: u16,
+    kind: u16,
+    count: u32,
+    position: usize,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(..2)? {
+            b"II" => true,
+            b"MM" => false,
+            _ => return None,
+        };
+
+        let tiff = Self {
+            data,
+            little_endian,
+        };
+
+        (tiff.u16(2)? == 42).then_some(tiff)
+    }
+
+    fn read(&self, exif: &mut Exif) {
+        let first = match self.u32(4) {
+            Some(offset) => offset as usize,
+            None => return,
+        };
+
+        let mut make = None;
+        let mut model = None;
+        let mut date_time = None;
+
+        for entry in self.entries(first) {
+            match entry.tag {
+                MAKE => make = self.text(&entry),
+                MODEL => model = self.text(&entry),
+                DATE_TIME => date_time = self.text(&entry),
+                EXIF_POINTER => {
+                    if let Some(offset) = self.number(&entry) {
+                        self.read_exif(offset as usize, exif);
+                    }
+                }
+                GPS_POINTER => {
+                    if let Some(offset) = self.number(&entry) {
+                        exif.location = self.read_gps(offset as usize);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        exif.camera = match (make, model) {
+            (Some(make), Some(model)) if model.starts_with(&make) => Some(model),
+            (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+            (make, model) => make.or(model),
+        };
+
+        exif.taken = exif.taken.take().or(date_time);
+    }
+
+    fn read_exif(&self, offset: usize, exif: &mut Exif) {
+        let mut width = None;
+        let mut height = None;
+
+        for entry in self.entries(offset) {
+            match entry.tag {
+                DATE_TAKEN => exif.taken = self.text(&entry),
+                PIXEL_WIDTH => width = self.number(&entry),
+                PIXEL_HEIGHT => height = self.number(&entry),
+                _ => {}
+            }
+        }
+
+        if let (Some(width), Some(height)) = (width, height) {
+            exif.dimensions = Some((width, height));
+        }
+    }
+
+    fn read_gps(&self, offset: usize) -> Option<(f64, f64)> {
+        let mut latitude = None;
+        let mut longitude = None;
+        let mut south = false;
+        let mut west = false;
+
+        for entry in self.entries(offset) {
+            match entry.tag {
+                LATITUDE_REFERENCE => south = self.text(&entry).as_deref() == Some("S"),
+                LONGITUDE_REFERENCE => west = self.text(&entry).as_deref() == Some("W"),
+                LATITUDE => latitude = self.degrees(&entry),
+                LONGITUDE => longitude = self.degrees(&entry),
+                _ => {}
+            }
+        }
+
+        let latitude = latitude?;
+        let longitude = longitude?;
+
+        Some((
+            if south { -latitude } else { latitude },
+            if west { -longitude } else { longitude },
+        ))
+    }
+
+    fn entries(&self, offset: usize) -> Vec<Entry> {
+        let count = self.u16(offset).unwrap_or(0);
+
+        (0..usize::from(count))
+            .filter_map(|index| {
+                let start = offset + 2 + index * 12;
+                let kind = self.u16(start + 2)?;
+                let count = self.u32(start + 4)?;
+
+                let size = match kind {
+                    1 | 2 | 6 | 7 => 1,
+                    3 | 8 => 2,
+                    4 | 9 | 11 => 4,
+                    5 | 10 | 12 => 8,
+                    _ => return None,
+                } * count as usize;
+
+                let position = if size <= 4 {
+                    start + 8
+                } else {
+                    self.u32(start + 8)? as usize
+                };
+
+                Some(Entry {
+                    tag: self.u16(start)?,
+                    kind,
+                    count,
+                    position,
+                })
+            })
+            .collect()
+    }
+
+    fn text(&self, entry: &Entry) -> Option<String> {
+        if entry.kind != 2 {
+            return None;
+        }
+
+        let bytes = self
+            .data
+            .get(entry.position..entry.position + entry.count as usize)?;
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim_end_matches('\0').trim();
+
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    fn number(&self, entry: &Entry) -> Option<u32> {
+        match entry.kind {
+            3 => self.u16(entry.position).map(u32::from),
+            4 => self.u32(entry.position),
+            _ => None,
+        }
+    }
+
+    fn degrees(&self, entry: &Entry) -> Option<f64> {
+        if entry.kind != 5 || entry.count != 3 {
+            return None;
+        }
+
+        let rational = |index: usize| {
+            let numerator = self.u32(entry.position + index * 8)?;
+            let denominator = self.u32(entry.position + index * 8 + 4)?;
+
+            (denominator != 0).then(|| f64::from(numerator) / f64::from(denominator))
+        };
+
+        Some(rational(0)? + rational(1)? / 60.0 + rational(2)? / 3600.0)
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes = [*self.data.get(offset)?, *self.data.get(offset + 1)?];
+
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let bytes = [
+            *self.data.get(offset)?,
+            *self.data.get(offset + 1)?,
+            *self.data.get(offset + 2)?,
+            *self.data.get(offset + 3)?,
+        ];
+
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jpeg() -> Vec<u8> {
+        let tiff: &[u8] = &[
+            b'M', b'M', 0, 42, 0, 0, 0, 8, 0, 1, 0x01, 0x0f, 0, 2, 0, 0, 0, 4, b'F', b'o', b'o', 0,
+            0, 0, 0, 0,
+        ];
+
+        let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe1];
+        jpeg.extend(((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(tiff);
+        jpeg.extend([0xff, 0xc0, 0, 11, 8, 0, 2, 0, 3, 1, 1, 0x11, 0]);
+        jpeg.extend([0xff, 0xda]);
+        jpeg
+    }
+
+    #[test]
+    fn reads_camera_and_frame_size() {
+        let exif = parse(&jpeg()).unwrap();
+
+        assert_eq!(exif.camera.as_deref(), Some("Foo"));
+        assert_eq!(exif.dimensions, Some((3, 2)));
+    }
+
+    #[test]
+    fn ignores_truncated_blocks() {
+        let jpeg = jpeg();
+
+        assert!(parse(&jpeg[..20]).is_none());
+        assert!(parse(&jpeg[..3]).is_some_and(|exif| exif.is_empty()));
+
+        let mut entries = jpeg.clone();
+        entries[21] = 200;
+
+        let exif = parse(&entries).unwrap();
+        assert_eq!(exif.camera.as_deref(), Some("Foo"));
+
+        let mut value = jpeg.clone();
+        value[29] = 40;
+
+        let exif = parse(&value).unwrap();
+        assert_eq!(exif.camera, None);
+        assert_eq!(exif.dimensions, Some((3, 2)));
+    }
+}
@@ -0,0 +1,361 @@
+use iced::button::{self, Button};
+use iced::text_input::{self, TextInput};
+use iced::{Column, Command, Element, Row, Text};
+
+use crate::format;
+use crate::i18n;
+use crate::style;
+
+use std::fmt::Write;
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PathChanged(String),
+    Save,
+    Saved(Result<(), String>),
+    Exists(PathBuf),
+    Cancel,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Exported(PathBuf, usize),
+    Failed(String),
+    Cancelled,
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub type_name: &'static str,
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    fn of(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+
+        match extension.as_str() {
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+pub struct State {
+    records: Vec<Record>,
+    path: String,
+    error: Option<String>,
+    saving: bool,
+    overwrite: bool,
+    input: text_input::State,
+    save_button: button::State,
+    cancel_button: button::State,
+}
+
+impl State {
+    pub fn new(directory: &Path, records: Vec<Record>) -> Self {
+        let mut input = text_input::State::focused();
+        input.move_cursor_to_end();
+
+        Self {
+            records,
+            path: directory.join("listing.csv").to_string_lossy().to_string(),
+            error: None,
+            saving: false,
+            overwrite: false,
+            input,
+            save_button: button::State::new(),
+            cancel_button: button::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> (Command<Message>, Option<Event>) {
+        match message {
+            Message::PathChanged(path) => {
+                self.path = path;
+                self.error = None;
+                self.overwrite = false;
+            }
+            Message::Save => {
+                let path = PathBuf::from(self.path.trim());
+
+                let format = match Format::of(&path) {
+                    Some(format) => format,
+                    None => {
+                        self.error = Some(i18n::tr("File name must end in .csv or .json").into());
+
+                        return (Command::none(), None);
+                    }
+                };
+
+                self.saving = true;
+
+                let target = path.clone();
+                let write = write(path, format, self.records.clone(), self.overwrite);
+
+                return (
+                    Command::perform(write, move |result| match result {
+                        Ok(()) => Message::Saved(Ok(())),
+                        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                            Message::Exists(target.clone())
+                        }
+                        Err(error) => {
+                            Message::Saved(Err(format!("Cannot write {:?}: {}", target, error)))
+                        }
+                    }),
+                    None,
+                );
+            }
+            Message::Saved(Ok(())) => {
+                self.saving = false;
+
+                let path = PathBuf::from(self.path.trim());
+
+                return (
+                    Command::none(),
+                    Some(Event::Exported(path, self.records.len())),
+                );
+            }
+            Message::Saved(Err(error)) => {
+                self.saving = false;
+                self.error = Some(error.clone());
+
+                return (Command::none(), Some(Event::Failed(error)));
+            }
+            Message::Exists(path) => {
+                self.saving = false;
+                self.overwrite = true;
+                self.error = Some(i18n::tr_with(
+                    "{} already exists, save again to overwrite it",
+                    &[&path.display()],
+                ));
+            }
+            Message::Cancel => {
+                return (Command::none(), Some(Event::Cancelled));
+            }
+        }
+
+        (Command::none(), None)
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let label = Text::new(i18n::tr_with(
+        "Export {} entries to (.csv or .json)",
+        &[&state.records.len()],
+    ));
+
+    let input = TextInput::new(&mut state.input, "", &state.path, Message::PathChanged)
+        .padding(5)
+        .on_submit(Message::Save);
+
+    let mut save = Button::new(&mut state.save_button, Text::new(i18n::tr("Save")));
+
+    if !state.saving {
+        save = save.on_press(Message::Save);
+    }
+
+    let cancel = Button::new(&mut state.cancel_button, Text::new(i18n::tr("Cancel")))
+        .on_press(Message::Cancel);
+
+    let mut column = Column::new().spacing(5).push(label).push(input);
+
+    if let Some(error) = state.error.as_ref() {
+        column = column.push(Text::new(error).color(style::ERROR));
+    }
+
+    column
+        .push(Row::new().spacing(10).push(save).push(cancel))
+        .into()
+}
+
+struct Line {
+    path: String,
+    name: String,
+    kind: &'static str,
+    type_name: &'static str,
+    size: Option<u64>,
+    modified: Option<String>,
+}
+
+async fn write(
+    path: PathBuf,
+    format: Format,
+    records: Vec<Record>,
+    overwrite: bool,
+) -> io::Result<()> {
+    let lines = records.into_iter().map(|record| {
+        let metadata = fs::metadata(&record.path).ok();
+
+        let size = record.size.or_else(|| {
+            metadata
+                .as_ref()
+                .filter(|metadata| metadata.is_file())
+                .map(|metadata| metadata.len())
+        });
+
+        let modified = metadata
+            .and_then(|metadata| metadata.modified().ok())
+            .map(|modified| format::timestamp(modified, 'T'));
+
+        Line {
+            name: record
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: record.path.to_string_lossy().to_string(),
+            kind: if record.is_dir { "directory" } else { "file" },
+            type_name: record.type_name,
+            size,
+            modified,
+        }
+    });
+
+    let contents = match format {
+        Format::Csv => csv(lines),
+        Format::Json => json(lines),
+    };
+
+    fs::OpenOptions::new()
+        .write(true)
+        .create(overwrite)
+        .truncate(overwrite)
+        .create_new(!overwrite)
+        .open(&path)?
+        .write_all(contents.as_bytes())
+}
+
+fn csv(lines: impl Iterator<Item = Line>) -> String {
+    let mut contents = String::from("path,name,kind,type,size,modified\n");
+
+    for line in lines {
+        let fields = [
+            csv_field(&line.path),
+            csv_field(&line.name),
+            line.kind.to_string(),
+            csv_field(line.type_name),
+            line.size.map(|size| size.to_string()).unwrap_or_default(),
+            line.modified.unwrap_or_default(),
+        ];
+
+        contents.push_str(&fields.join(","));
+        contents.push('\n');
+    }
+
+    contents
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn json(lines: impl Iterator<Item = Line>) -> String {
+    let entries: Vec<_> = lines
+        .map(|line| {
+            let size = line
+                .size
+                .map(|size| size.to_string())
+                .unwrap_or_else(|| "null".into());
+            let modified = line
+                .modified
+                .map(|modified| json_string(&modified))
+                .unwrap_or_else(|| "null".into());
+
+            format!(
+                "  {{\"path\": {}, \"name\": {}, \"kind\": {}, \"type\": {}, \"size\": {}, \"modified\": {}}}",
+                json_string(&line.path),
+                json_string(&line.name),
+                json_string(line.kind),
+                json_string(line.type_name),
+                size,
+                modified,
+            )
+        })
+        .collect();
+
+    if entries.is_empty() {
+        return "[]\n".into();
+    }
+
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_csv_fields_only_when_needed() {
+        assert_eq!(csv_field("plain.txt"), "plain.txt");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(
+            json_string("C:\\dir \"q\"\n\t"),
+            "\"C:\\\\dir \\\"q\\\"\\n\\t\""
+        );
+        assert_eq!(json_string("\u{1}ü"), "\"\\u0001ü\"");
+    }
+
+    #[test]
+    fn does_not_overwrite_without_asking() {
+        let path = std::env::temp_dir().join(format!("export-{}.csv", std::process::id()));
+        fs::write(&path, "keep").unwrap();
+
+        let result = async_std::task::block_on(write(path.clone(), Format::Csv, vec![], false));
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "keep");
+
+        async_std::task::block_on(write(path.clone(), Format::Csv, vec![], true)).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "path,name,kind,type,size,modified\n"
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
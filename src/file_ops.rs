@@ -0,0 +1,505 @@
+use rayon::prelude::*;
+
+use crate::i18n;
+use crate::trash;
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    File,
+    Directory,
+}
+
+#[derive(Debug, Clone)]
+pub enum Request {
+    Create {
+        path: PathBuf,
+        kind: Kind,
+    },
+    Rename {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Copy {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Move {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Trash(PathBuf),
+    Replace {
+        path: PathBuf,
+        before: String,
+        after: String,
+        count: usize,
+    },
+    Overwrite {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    MoveOver {
+        from: PathBuf,
+        to: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum Operation {
+    Create {
+        path: PathBuf,
+        kind: Kind,
+    },
+    Rename {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Copy {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Move {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Trash(trash::Item),
+    Replace {
+        path: PathBuf,
+        before: String,
+        after: String,
+        count: usize,
+    },
+    Overwrite {
+        from: PathBuf,
+        to: PathBuf,
+        replaced: trash::Item,
+    },
+    MoveOver {
+        from: PathBuf,
+        to: PathBuf,
+        replaced: trash::Item,
+    },
+}
+
+impl Operation {
+    pub fn request(&self) -> Request {
+        match self {
+            Operation::Create { path, kind } => Request::Create {
+                path: path.clone(),
+                kind: *kind,
+            },
+            Operation::Rename { from, to } => Request::Rename {
+                from: from.clone(),
+                to: to.clone(),
+            },
+            Operation::Copy { from, to } => Request::Copy {
+                from: from.clone(),
+                to: to.clone(),
+            },
+            Operation::Move { from, to } => Request::Move {
+                from: from.clone(),
+                to: to.clone(),
+            },
+            Operation::Trash(item) => Request::Trash(item.original.clone()),
+            Operation::Replace {
+                path,
+                before,
+                after,
+                count,
+            } => Request::Replace {
+                path: path.clone(),
+                before: before.clone(),
+                after: after.clone(),
+                count: *count,
+            },
+            Operation::Overwrite { from, to, .. } => Request::Overwrite {
+                from: from.clone(),
+                to: to.clone(),
+            },
+            Operation::MoveOver { from, to, .. } => Request::MoveOver {
+                from: from.clone(),
+                to: to.clone(),
+            },
+        }
+    }
+
+    pub fn target(&self) -> Option<&PathBuf> {
+        match self {
+            Operation::Create { path, .. } | Operation::Replace { path, .. } => Some(path),
+            Operation::Rename { to, .. }
+            | Operation::Copy { to, .. }
+            | Operation::Move { to, .. }
+            | Operation::Overwrite { to, .. }
+            | Operation::MoveOver { to, .. } => Some(to),
+            Operation::Trash(_) => None,
+        }
+    }
+
+    pub fn reverted_target(&self) -> Option<&PathBuf> {
+        match self {
+            Operation::Create { .. } => None,
+            Operation::Rename { from, .. }
+            | Operation::Copy { from, .. }
+            | Operation::Move { from, .. }
+            | Operation::MoveOver { from, .. } => Some(from),
+            Operation::Trash(item) => Some(&item.original),
+            Operation::Replace { path, .. } => Some(path),
+            Operation::Overwrite { to, .. } => Some(to),
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quoted = |path: &Path| format!("{:?}", path);
+
+        let description = match self {
+            Operation::Create { path, kind } => {
+                let text = match kind {
+                    Kind::File => "Created file {}",
+                    Kind::Directory => "Created folder {}",
+                };
+
+                i18n::tr_with(text, &[&quoted(path)])
+            }
+            Operation::Rename { from, to } => {
+                i18n::tr_with("Renamed {} to {}", &[&quoted(from), &quoted(to)])
+            }
+            Operation::Copy { from, to } => {
+                i18n::tr_with("Copied {} to {}", &[&quoted(from), &quoted(to)])
+            }
+            Operation::Move { from, to } => {
+                i18n::tr_with("Moved {} to {}", &[&quoted(from), &quoted(to)])
+            }
+            Operation::Trash(item) => {
+                i18n::tr_with("Moved {} to the trash", &[&quoted(&item.original)])
+            }
+            Operation::Replace { path, count, .. } => {
+                i18n::tr_with("Replaced {} occurrences in {}", &[count, &quoted(path)])
+            }
+            Operation::Overwrite { from, to, .. } | Operation::MoveOver { from, to, .. } => {
+                i18n::tr_with("Overwrote {} with {}", &[&quoted(to), &quoted(from)])
+            }
+        };
+
+        f.write_str(&description)
+    }
+}
+
+pub async fn apply(request: Request) -> Result<Operation, String> {
+    match request {
+        Request::Create { path, kind } => {
+            let result = match kind {
+                Kind::File => fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&path)
+                    .map(|_| ()),
+                Kind::Directory => fs::create_dir(&path),
+            };
+
+            result
+                .map(|_| Operation::Create {
+                    path: path.clone(),
+                    kind,
+                })
+                .map_err(|error| format!("Cannot create {:?}: {}", path, error))
+        }
+        Request::Rename { from, to } => rename(&from, &to).map(|_| Operation::Rename { from, to }),
+        Request::Copy { from, to } => {
+            copy(&from, &to, |_, _| Ok(())).map(|_| Operation::Copy { from, to })
+        }
+        Request::Move { from, to } => relocate(&from, &to).map(|_| Operation::Move { from, to }),
+        Request::Trash(path) => trash::trash(&path).map(Operation::Trash),
+        Request::Replace {
+            path,
+            before,
+            after,
+            count,
+        } => overwrite(&path, &before, &after).map(|_| Operation::Replace {
+            path,
+            before,
+            after,
+            count,
+        }),
+        Request::Overwrite { from, to } => {
+            let replaced = trash::trash(&to)?;
+
+            if let Err(error) = copy(&from, &to, |_, _| Ok(())) {
+                let _ = trash::restore(&replaced);
+
+                return Err(error);
+            }
+
+            Ok(Operation::Overwrite { from, to, replaced })
+        }
+        Request::MoveOver { from, to } => {
+            let replaced = trash::trash(&to)?;
+
+            if let Err(error) = relocate(&from, &to) {
+                let _ = trash::restore(&replaced);
+
+                return Err(error);
+            }
+
+            Ok(Operation::MoveOver { from, to, replaced })
+        }
+    }
+}
+
+pub async fn revert(operation: Operation) -> Result<Operation, String> {
+    let result = match &operation {
+        Operation::Create { path, kind } => {
+            let result = match kind {
+                Kind::File if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) => {
+                    return Err(format!("{:?} is no longer empty", path));
+                }
+                Kind::File => fs::remove_file(path),
+                Kind::Directory => fs::remove_dir(path),
+            };
+
+            result.map_err(|error| format!("Cannot remove {:?}: {}", path, error))
+        }
+        Operation::Copy { to, .. } => trash::trash(to).map(|_| ()),
+        Operation::Rename { from, to } => rename(to, from),
+        Operation::Move { from, to } => relocate(to, from),
+        Operation::Trash(item) => trash::restore(item),
+        Operation::Replace {
+            path,
+            before,
+            after,
+            ..
+        } => overwrite(path, after, before),
+        Operation::Overwrite { to, replaced, .. } => remove(to)
+            .map_err(|error| format!("Cannot remove {:?}: {}", to, error))
+            .and_then(|_| trash::restore(replaced)),
+        Operation::MoveOver { from, to, replaced } => {
+            relocate(to, from).and_then(|_| trash::restore(replaced))
+        }
+    };
+
+    result.map(|_| operation)
+}
+
+pub fn disk_usage(path: &Path, is_cancelled: impl Fn() -> bool + Sync) -> Option<u64> {
+    parallel_disk_usage(path, &is_cancelled)
+}
+
+fn parallel_disk_usage(path: &Path, is_cancelled: &(impl Fn() -> bool + Sync)) -> Option<u64> {
+    if is_cancelled() {
+        return None;
+    }
+
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return Some(0),
+    };
+
+    if !metadata.is_dir() {
+        return Some(if metadata.is_file() {
+            metadata.len()
+        } else {
+            0
+        });
+    }
+
+    let children: Vec<_> = match fs::read_dir(path) {
+        Ok(read_dir) => read_dir.flatten().map(|entry| entry.path()).collect(),
+        Err(_) => return Some(0),
+    };
+
+    children
+        .par_iter()
+        .map(|child| parallel_disk_usage(child, is_cancelled))
+        .sum()
+}
+
+pub fn copy(
+    from: &Path,
+    to: &Path,
+    mut progress: impl FnMut(&Path, u64) -> Result<(), String>,
+) -> Result<(), String> {
+    if to.exists() {
+        return Err(format!("{:?} already exists", to));
+    }
+
+    if is_inside(from, to) {
+        return Err(i18n::tr_with(
+            "Cannot copy {} into itself",
+            &[&format!("{:?}", from)],
+        ));
+    }
+
+    let result = walkdir::WalkDir::new(from)
+        .into_iter()
+        .try_for_each(|entry| {
+            let entry = entry.map_err(|error| error.to_string())?;
+            let relative = entry.path().strip_prefix(from).unwrap_or(entry.path());
+            let target = to.join(relative);
+            let file_type = entry.file_type();
+
+            if file_type.is_dir() {
+                fs::create_dir(&target).map_err(|error| error.to_string())
+            } else if file_type.is_symlink() {
+                copy_symlink(entry.path(), &target).map_err(|error| error.to_string())
+            } else {
+                copy_file(entry.path(), &target, &mut progress)
+            }
+        });
+
+    if let Err(error) = result {
+        let _ = remove(to);
+
+        return Err(format!("Cannot copy {:?} to {:?}: {}", from, to, error));
+    }
+
+    Ok(())
+}
+
+pub fn is_inside(from: &Path, to: &Path) -> bool {
+    let from = match fs::canonicalize(from) {
+        Ok(from) => from,
+        Err(_) => return false,
+    };
+
+    let to = fs::canonicalize(to).or_else(|_| {
+        let parent = to.parent().unwrap_or_else(|| Path::new("."));
+
+        fs::canonicalize(parent).map(|parent| parent.join(to.file_name().unwrap_or_default()))
+    });
+
+    to.is_ok_and(|to| to.starts_with(from))
+}
+
+pub fn keep_both(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    (2..)
+        .map(|copy| path.with_file_name(format!("{} ({}){}", stem, copy, extension)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+pub fn remove(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+fn copy_file(
+    from: &Path,
+    to: &Path,
+    progress: &mut impl FnMut(&Path, u64) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut source = fs::File::open(from).map_err(|error| error.to_string())?;
+    let mut target = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(to)
+        .map_err(|error| error.to_string())?;
+
+    let mut buffer = vec![0; 64 * 1024];
+
+    loop {
+        let read = source
+            .read(&mut buffer)
+            .map_err(|error| error.to_string())?;
+
+        if read == 0 {
+            break;
+        }
+
+        target
+            .write_all(&buffer[..read])
+            .map_err(|error| error.to_string())?;
+
+        progress(from, read as u64)?;
+    }
+
+    if let Ok(metadata) = source.metadata() {
+        let _ = target.set_permissions(metadata.permissions());
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(from)?, to)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    fs::copy(from, to).map(|_| ())
+}
+
+fn rename(from: &Path, to: &Path) -> Result<(), String> {
+    if to.exists() {
+        return Err(format!("{:?} already exists", to));
+    }
+
+    fs::rename(from, to).map_err(|error| format!("Cannot move {:?} to {:?}: {}", from, to, error))
+}
+
+pub fn relocate(from: &Path, to: &Path) -> Result<(), String> {
+    if to.exists() {
+        return Err(format!("{:?} already exists", to));
+    }
+
+    match fs::rename(from, to) {
+        Err(error) if error.kind() == io::ErrorKind::CrossesDevices => {
+            copy(from, to, |_, _| Ok(()))?;
+
+            remove(from)
+                .map_err(|error| format!("Copied {:?} but cannot remove it: {}", from, error))
+        }
+        result => result.map_err(|error| format!("Cannot move {:?} to {:?}: {}", from, to, error)),
+    }
+}
+
+fn overwrite(path: &Path, expected: &str, contents: &str) -> Result<(), String> {
+    let current =
+        fs::read_to_string(path).map_err(|error| format!("Cannot read {:?}: {}", path, error))?;
+
+    if current != expected {
+        return Err(format!("{:?} has changed on disk", path));
+    }
+
+    fs::write(path, contents).map_err(|error| format!("Cannot write {:?}: {}", path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copying_a_directory_into_itself_is_rejected() {
+        let root = std::env::temp_dir().join(format!("file-ops-inside-{}", std::process::id()));
+        let inner = root.join("a").join("b");
+        fs::create_dir_all(&inner).unwrap();
+
+        assert!(is_inside(&root.join("a"), &inner.join("a")));
+        assert!(copy(&root.join("a"), &inner.join("a"), |_, _| Ok(())).is_err());
+        assert!(!inner.join("a").exists());
+
+        assert!(!is_inside(&inner, &root.join("c")));
+        assert!(!is_inside(&root.join("a"), &root.join("ab")));
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
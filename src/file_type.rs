@@ -0,0 +1,199 @@
+use std::fmt;
+use std::path::Path;
+
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("7z", "application/x-7z-compressed"),
+    ("bmp", "image/bmp"),
+    ("c", "text/x-c"),
+    ("cpp", "text/x-c++"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("h", "text/x-c"),
+    ("html", "text/html"),
+    ("ico", "image/vnd.microsoft.icon"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("log", "text/plain"),
+    ("md", "text/markdown"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("py", "text/x-python"),
+    ("rs", "text/rust"),
+    ("sh", "application/x-sh"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("toml", "application/toml"),
+    ("ttf", "font/ttf"),
+    ("txt", "text/plain"),
+    ("wav", "audio/wav"),
+    ("webp", "image/webp"),
+    ("xml", "application/xml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("zip", "application/zip"),
+];
+
+const CODE_EXTENSIONS: &[&str] = &[
+    "c", "cpp", "cs", "css", "go", "h", "hpp", "html", "java", "js", "json", "kt", "lua", "py",
+    "rb", "rs", "sh", "sql", "toml", "ts", "xml", "yaml", "yml", "zig",
+];
+
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "bmp", "gif", "ico", "jpeg", "jpg", "png", "svg", "tif", "tiff", "webp",
+];
+
+const DOCUMENT_EXTENSIONS: &[&str] = &[
+    "csv", "doc", "docx", "epub", "md", "odp", "ods", "odt", "pdf", "ppt", "pptx", "rtf", "txt",
+    "xls", "xlsx",
+];
+
+const ARCHIVE_EXTENSIONS: &[&str] = &[
+    "7z", "bz2", "gz", "jar", "rar", "tar", "tgz", "xz", "zip", "zst",
+];
+
+const BINARY_EXTENSIONS: &[&str] = &[
+    "7z", "a", "bin", "bmp", "bz2", "class", "dll", "doc", "docx", "dylib", "exe", "gif", "gz",
+    "ico", "jar", "jpeg", "jpg", "mp3", "mp4", "o", "odt", "pdf", "png", "rar", "so", "tar", "tgz",
+    "tif", "tiff", "ttf", "wav", "webp", "xls", "xlsx", "xz", "zip", "zst",
+];
+
+const TYPE_NAMES: &[(&str, &str)] = &[
+    ("c", "C"),
+    ("cpp", "C++"),
+    ("cs", "C#"),
+    ("css", "Web"),
+    ("go", "Go"),
+    ("h", "C"),
+    ("hpp", "C++"),
+    ("html", "Web"),
+    ("java", "Java"),
+    ("js", "JavaScript"),
+    ("json", "Data"),
+    ("kt", "Kotlin"),
+    ("lua", "Lua"),
+    ("md", "Markdown"),
+    ("py", "Python"),
+    ("rb", "Ruby"),
+    ("rs", "Rust"),
+    ("sh", "Shell"),
+    ("sql", "SQL"),
+    ("toml", "Data"),
+    ("ts", "TypeScript"),
+    ("xml", "Data"),
+    ("yaml", "Data"),
+    ("yml", "Data"),
+    ("zig", "Zig"),
+];
+
+pub const FOLDERS: &str = "Folders";
+pub const OTHER: &str = "Other";
+
+pub fn type_name(path: &Path, is_dir: bool) -> &'static str {
+    if is_dir {
+        return FOLDERS;
+    }
+
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+
+    let known = extension.as_ref().and_then(|extension| {
+        TYPE_NAMES
+            .iter()
+            .find(|(known, _)| known == extension)
+            .map(|(_, name)| *name)
+    });
+
+    known
+        .or_else(|| match Category::of(path, false)? {
+            Category::Images => Some("Images"),
+            Category::Documents => Some("Documents"),
+            Category::Archives => Some("Archives"),
+            Category::Code | Category::Folders => None,
+        })
+        .unwrap_or(OTHER)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Folders,
+    Code,
+    Images,
+    Documents,
+    Archives,
+}
+
+impl Category {
+    pub const ALL: [Category; 5] = [
+        Category::Folders,
+        Category::Code,
+        Category::Images,
+        Category::Documents,
+        Category::Archives,
+    ];
+
+    pub fn of(path: &Path, is_dir: bool) -> Option<Category> {
+        if is_dir {
+            return Some(Category::Folders);
+        }
+
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+
+        [
+            (Category::Code, CODE_EXTENSIONS),
+            (Category::Images, IMAGE_EXTENSIONS),
+            (Category::Documents, DOCUMENT_EXTENSIONS),
+            (Category::Archives, ARCHIVE_EXTENSIONS),
+        ]
+        .iter()
+        .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+        .map(|(category, _)| *category)
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Category::Folders => "Folders",
+            Category::Code => "Code",
+            Category::Images => "Images",
+            Category::Documents => "Documents",
+            Category::Archives => "Archives",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+pub fn is_binary(path: &Path) -> bool {
+    path.extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .is_some_and(|extension| BINARY_EXTENSIONS.contains(&extension.as_str()))
+}
+
+pub fn mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+
+    extension
+        .and_then(|extension| {
+            MIME_TYPES
+                .iter()
+                .find(|(known, _)| *known == extension)
+                .map(|(_, mime)| *mime)
+        })
+        .unwrap_or("application/octet-stream")
+}
@@ -0,0 +1,6 @@
+use iced::Font;
+
+pub const MONOSPACE: Font = Font::External {
+    name: "DejaVu Sans Mono",
+    bytes: include_bytes!("../assets/fonts/DejaVuSansMono.ttf"),
+};
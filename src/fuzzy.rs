@@ -0,0 +1,60 @@
+pub fn score(query: &str, text: &str) -> Option<i64> {
+    let mut score = 0;
+    let mut previous_match: Option<usize> = None;
+    let mut text_chars = text.chars().enumerate();
+    let mut previous_char = None;
+
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        let query_char = query_char.to_ascii_lowercase();
+
+        loop {
+            let (index, text_char) = text_chars.next()?;
+            let at_word_start = previous_char.is_none_or(|c: char| !c.is_alphanumeric());
+            previous_char = Some(text_char);
+
+            if text_char.to_ascii_lowercase() != query_char {
+                continue;
+            }
+
+            score += 1;
+
+            if at_word_start {
+                score += 8;
+            }
+
+            if let Some(previous) = previous_match {
+                if previous + 1 == index {
+                    score += 5;
+                } else {
+                    score -= (index - previous) as i64 / 4;
+                }
+            }
+
+            previous_match = Some(index);
+            break;
+        }
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_word_starts_and_runs() {
+        assert_eq!(score("", "anything"), Some(0));
+        assert_eq!(score("fb", "foo_bar"), Some(17));
+        assert_eq!(score("F b", "foo_bar"), Some(17));
+        assert_eq!(score("fo", "foo"), Some(15));
+        assert_eq!(score("bf", "foo_bar"), None);
+        assert_eq!(score("x", "foo"), None);
+    }
+
+    #[test]
+    fn prefers_word_starts_over_scattered_matches() {
+        assert!(score("nt", "nav_tree") > score("nt", "intent"));
+        assert!(score("ref", "Refresh directory") > score("ref", "Show preferences"));
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Status {
+    pub branch: String,
+    pub dirty: bool,
+    pub modified: HashSet<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    pub commit: String,
+    pub author: String,
+    pub time: Option<SystemTime>,
+}
+
+pub async fn status(directory: PathBuf) -> Option<Status> {
+    let branch = git(&directory, &["symbolic-ref", "--short", "-q", "HEAD"])
+        .or_else(|| git(&directory, &["rev-parse", "--short", "HEAD"]))?
+        .trim()
+        .to_string();
+
+    let root = PathBuf::from(git(&directory, &["rev-parse", "--show-toplevel"])?.trim());
+    let porcelain = git(&directory, &["status", "--porcelain", "-z"])?;
+
+    let mut modified = HashSet::new();
+    let mut dirty = false;
+    let mut entries = porcelain.split('\0').filter(|entry| !entry.is_empty());
+
+    while let Some(entry) = entries.next() {
+        dirty = true;
+
+        let (code, path) = match (entry.get(..2), entry.get(3..)) {
+            (Some(code), Some(path)) => (code, path),
+            _ => continue,
+        };
+
+        if code.contains('R') || code.contains('C') {
+            entries.next();
+        }
+
+        if code.contains('M') {
+            modified.insert(root.join(path));
+        }
+    }
+
+    Some(Status {
+        branch,
+        dirty,
+        modified,
+    })
+}
+
+pub async fn diff(path: PathBuf) -> Result<String, String> {
+    let directory = path
+        .parent()
+        .ok_or_else(|| format!("{:?} has no parent directory", path))?;
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(["diff", "HEAD", "--"])
+        .arg(&path)
+        .output()
+        .map_err(|error| format!("Cannot run git: {}", error))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub async fn blame(path: PathBuf) -> Result<Vec<BlameLine>, String> {
+    let directory = path
+        .parent()
+        .ok_or_else(|| format!("{:?} has no parent directory", path))?;
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(["blame", "--porcelain", "--"])
+        .arg(&path)
+        .output()
+        .map_err(|error| format!("Cannot run git: {}", error))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let porcelain = String::from_utf8_lossy(&output.stdout);
+
+    let mut commits: HashMap<String, BlameLine> = HashMap::new();
+    let mut current: Option<String> = None;
+    let mut lines = vec![];
+
+    for line in porcelain.lines() {
+        if line.starts_with('\t') {
+            if let Some(commit) = current.as_ref().and_then(|commit| commits.get(commit)) {
+                lines.push(commit.clone());
+            }
+
+            continue;
+        }
+
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+
+        if key.len() == 40 && key.chars().all(|c| c.is_ascii_hexdigit()) {
+            commits.entry(key.to_string()).or_insert_with(|| BlameLine {
+                commit: key[..8].to_string(),
+                author: String::new(),
+                time: None,
+            });
+
+            current = Some(key.to_string());
+
+            continue;
+        }
+
+        let commit = match current.as_ref().and_then(|commit| commits.get_mut(commit)) {
+            Some(commit) => commit,
+            None => continue,
+        };
+
+        match key {
+            "author" => commit.author = value.to_string(),
+            "author-time" => {
+                commit.time = value
+                    .parse()
+                    .ok()
+                    .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(lines)
+}
+
+fn git(directory: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
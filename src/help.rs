@@ -0,0 +1,52 @@
+use iced::button::{self, Button};
+use iced::{Column, Container, Element, Length, Row, Text};
+
+use crate::font;
+use crate::i18n;
+use crate::keymap::{Area, KeyMap};
+
+pub fn view<'a, Message: Clone + 'a>(
+    keymap: &KeyMap,
+    close_button: &'a mut button::State,
+    on_close: Message,
+) -> Element<'a, Message> {
+    let mut content = Column::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Keyboard shortcuts")).size(30));
+
+    for area in Area::ALL.iter().copied() {
+        let mut bindings = keymap.bindings(area).peekable();
+
+        if bindings.peek().is_none() {
+            continue;
+        }
+
+        let mut section = Column::new()
+            .spacing(5)
+            .push(Text::new(area.to_string()).size(24));
+
+        for (binding, action) in bindings {
+            let row = Row::new()
+                .spacing(10)
+                .push(
+                    Text::new(binding.to_string())
+                        .font(font::MONOSPACE)
+                        .width(Length::Units(160)),
+                )
+                .push(Text::new(action.description()));
+
+            section = section.push(row);
+        }
+
+        content = content.push(section);
+    }
+
+    let close = Button::new(close_button, Text::new(i18n::tr("Close"))).on_press(on_close);
+
+    Container::new(content.push(close))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .into()
+}
@@ -0,0 +1,61 @@
+const MAX_QUERIES: usize = 25;
+
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    queries: Vec<String>,
+    position: Option<usize>,
+}
+
+impl History {
+    pub fn new(queries: Vec<String>) -> Self {
+        Self {
+            queries,
+            position: None,
+        }
+    }
+
+    pub fn queries(&self) -> &[String] {
+        &self.queries
+    }
+
+    pub fn record(&mut self, query: &str) -> Option<Vec<String>> {
+        self.position = None;
+
+        if query.is_empty() || self.queries.first().is_some_and(|first| first == query) {
+            return None;
+        }
+
+        self.queries.retain(|previous| previous != query);
+        self.queries.insert(0, query.to_string());
+        self.queries.truncate(MAX_QUERIES);
+
+        Some(self.queries.clone())
+    }
+
+    pub fn older(&mut self) -> Option<&str> {
+        let position = self.position.map_or(0, |position| position + 1);
+
+        if position >= self.queries.len() {
+            return None;
+        }
+
+        self.position = Some(position);
+
+        Some(&self.queries[position])
+    }
+
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position?;
+
+        self.position = position.checked_sub(1);
+
+        match self.position {
+            Some(position) => Some(&self.queries[position]),
+            None => Some(""),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+}
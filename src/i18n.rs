@@ -0,0 +1,942 @@
+use crate::bidi;
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Hebrew,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::Hebrew];
+
+    pub fn is_right_to_left(self) -> bool {
+        matches!(self, Language::Hebrew)
+    }
+
+    fn lookup(self, text: &str) -> Option<&'static str> {
+        match self {
+            Language::English => None,
+            Language::German => find(GERMAN.iter().copied(), text),
+            Language::Hebrew => {
+                static VISUAL: OnceLock<Vec<(&str, String)>> = OnceLock::new();
+
+                let visual = VISUAL.get_or_init(|| {
+                    HEBREW
+                        .iter()
+                        .map(|(english, hebrew)| (*english, bidi::display(hebrew, true)))
+                        .collect()
+                });
+
+                find(
+                    visual
+                        .iter()
+                        .map(|(english, hebrew)| (*english, hebrew.as_str())),
+                    text,
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Hebrew => "עברית",
+        };
+
+        f.write_str(&bidi::display(label, self.is_right_to_left()))
+    }
+}
+
+pub fn set(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn is_right_to_left() -> bool {
+    current().is_right_to_left()
+}
+
+fn current() -> Language {
+    Language::ALL
+        .iter()
+        .copied()
+        .find(|language| *language as u8 == LANGUAGE.load(Ordering::Relaxed))
+        .unwrap_or_default()
+}
+
+pub fn tr(text: &'static str) -> &'static str {
+    current().lookup(text).unwrap_or(text)
+}
+
+pub fn tr_with(text: &'static str, args: &[&dyn fmt::Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = tr(text).split("{}");
+
+    let mut result = parts.next().unwrap_or_default().to_string();
+
+    for part in parts {
+        if let Some(arg) = args.next() {
+            result.push_str(&arg.to_string());
+        }
+
+        result.push_str(part);
+    }
+
+    result
+}
+
+fn find<'a>(
+    mut translations: impl Iterator<Item = (&'a str, &'static str)>,
+    text: &str,
+) -> Option<&'static str> {
+    translations
+        .find(|(english, _)| *english == text)
+        .map(|(_, translated)| translated)
+}
+
+const GERMAN: &[(&str, &str)] = &[
+    (
+        "1 item, calculating size...",
+        "1 Element, Größe wird berechnet...",
+    ),
+    ("1 item, {}", "1 Element, {}"),
+    ("1 result", "1 Treffer"),
+    ("All tags", "Alle Markierungen"),
+    ("Always monospace", "Immer Festbreitenschrift"),
+    ("Apply", "Anwenden"),
+    ("Apply to all", "Auf alle anwenden"),
+    ("Ascending", "Aufsteigend"),
+    ("Ask", "Nachfragen"),
+    (
+        "Auto-scroll paused, scroll down to resume",
+        "Automatisches Scrollen pausiert, nach unten scrollen zum Fortsetzen",
+    ),
+    ("Blue", "Blau"),
+    ("Calculating...", "Wird berechnet..."),
+    ("Cancel", "Abbrechen"),
+    ("Cancelled", "Abgebrochen"),
+    ("Cannot access: {}", "Kein Zugriff: {}"),
+    (
+        "Cannot copy {} into itself",
+        "{} kann nicht in sich selbst kopiert werden",
+    ),
+    ("Cannot read {}: {}", "{} kann nicht gelesen werden: {}"),
+    (
+        "Click a file to view it's content",
+        "Datei anklicken, um ihren Inhalt anzuzeigen",
+    ),
+    ("Close", "Schließen"),
+    ("Close file", "Datei schließen"),
+    ("Close settings", "Einstellungen schließen"),
+    ("Close tab", "Tab schließen"),
+    ("Close the right pane", "Rechten Bereich schließen"),
+    ("Close trash", "Papierkorb schließen"),
+    ("Collapse all", "Alle einklappen"),
+    ("Collapse all directories", "Alle Verzeichnisse einklappen"),
+    (
+        "Command, {dir} is replaced by the directory",
+        "Befehl, {dir} wird durch das Verzeichnis ersetzt",
+    ),
+    ("Compare", "Vergleichen"),
+    ("Compare directories", "Verzeichnisse vergleichen"),
+    (
+        "Compare selected directory with another",
+        "Ausgewähltes Verzeichnis mit einem anderen vergleichen",
+    ),
+    ("Comparing...", "Wird verglichen..."),
+    ("Confirm delete", "Löschen bestätigen"),
+    ("Copied", "Kopiert"),
+    ("Copied {} to {}", "{} nach {} kopiert"),
+    ("Copy", "Kopieren"),
+    ("Copy selected entry", "Ausgewählten Eintrag kopieren"),
+    ("Copy to left", "Nach links kopieren"),
+    ("Copy to right", "Nach rechts kopieren"),
+    ("Copy {} into", "{} kopieren nach"),
+    ("Copying", "Kopiere"),
+    ("Created file {}", "Datei {} erstellt"),
+    ("Created folder {}", "Ordner {} erstellt"),
+    ("Current file", "Aktuelle Datei"),
+    ("Current file: {}", "Aktuelle Datei: {}"),
+    (
+        "Cycle the colored tag of selected entry",
+        "Farbmarkierung des ausgewählten Eintrags wechseln",
+    ),
+    ("DIR", "ORDNER"),
+    ("Debug", "Debug"),
+    ("Delete", "Löschen"),
+    ("Descending", "Absteigend"),
+    ("Desktop", "Schreibtisch"),
+    ("Details", "Details"),
+    ("Diff", "Diff"),
+    ("Diff against HEAD: {}", "Diff gegen HEAD: {}"),
+    ("Different", "Unterschiedlich"),
+    ("Directories first", "Verzeichnisse zuerst"),
+    ("Directories last", "Verzeichnisse zuletzt"),
+    ("Directory", "Verzeichnis"),
+    ("Directory and below", "Verzeichnis und Unterverzeichnisse"),
+    ("Directory to compare with", "Verzeichnis zum Vergleichen"),
+    ("Discard", "Verwerfen"),
+    (
+        "Discard unsaved changes to {}?",
+        "Ungespeicherte Änderungen an {} verwerfen?",
+    ),
+    ("Documents", "Dokumente"),
+    ("Does not exist", "Existiert nicht"),
+    ("Downloads", "Downloads"),
+    ("Edit", "Bearbeiten"),
+    ("Error", "Fehler"),
+    ("Expand all", "Alle ausklappen"),
+    ("Expand all depth", "Tiefe für Alle ausklappen"),
+    ("Expand all directories", "Alle Verzeichnisse ausklappen"),
+    ("Export", "Exportieren"),
+    (
+        "Export directory listing to CSV or JSON",
+        "Verzeichnisliste als CSV oder JSON exportieren",
+    ),
+    (
+        "Export {} entries to (.csv or .json)",
+        "{} Einträge exportieren nach (.csv oder .json)",
+    ),
+    ("Exported", "Exportiert"),
+    ("FAILED", "FEHLGESCHLAGEN"),
+    ("FILE", "DATEI"),
+    ("File", "Datei"),
+    (
+        "File (its folder will open)",
+        "Datei (ihr Ordner wird geöffnet)",
+    ),
+    (
+        "File name must end in .csv or .json",
+        "Der Dateiname muss auf .csv oder .json enden",
+    ),
+    ("File operations", "Dateioperationen"),
+    ("File: {}", "Datei: {}"),
+    ("File: {} (binary, {})", "Datei: {} (binär, {})"),
+    (
+        "Filter: {} (Escape to clear)",
+        "Filter: {} (Escape zum Zurücksetzen)",
+    ),
+    ("Find in current file", "In aktueller Datei suchen"),
+    ("First {} results", "Erste {} Treffer"),
+    ("Follow", "Verfolgen"),
+    ("General", "Allgemein"),
+    ("Go", "Gehe zu"),
+    ("Go back", "Zurück"),
+    ("Go to home directory", "Zum Benutzerverzeichnis wechseln"),
+    (
+        "Go to parent directory",
+        "Zum übergeordneten Verzeichnis wechseln",
+    ),
+    ("Green", "Grün"),
+    ("Grid", "Raster"),
+    ("Group by type", "Nach Typ gruppieren"),
+    ("Help", "Hilfe"),
+    ("Hide binary files", "Binärdateien ausblenden"),
+    ("Hide hidden files", "Versteckte Dateien ausblenden"),
+    ("Hide large files", "Große Dateien ausblenden"),
+    ("History", "Verlauf"),
+    ("Home", "Persönlicher Ordner"),
+    ("If the target exists", "Wenn das Ziel existiert"),
+    (
+        "Index current directory for fast search",
+        "Aktuelles Verzeichnis für schnelle Suche indizieren",
+    ),
+    ("Info", "Info"),
+    ("Insert", "Einfügen"),
+    ("Keep both", "Beide behalten"),
+    ("Keyboard shortcuts", "Tastenkürzel"),
+    ("Language", "Sprache"),
+    ("Left: {}", "Links: {}"),
+    ("List", "Liste"),
+    ("Load anyway", "Trotzdem laden"),
+    ("Loading {}...", "Lade {}..."),
+    ("Match case", "Groß-/Kleinschreibung beachten"),
+    ("Mixed", "Gemischt"),
+    ("Modified", "Geändert"),
+    (
+        "Monospace for code and logs",
+        "Festbreitenschrift für Code und Logs",
+    ),
+    ("Move", "Verschieben"),
+    (
+        "Move focus to the next pane",
+        "Fokus in den nächsten Bereich verschieben",
+    ),
+    (
+        "Move focus to the previous pane",
+        "Fokus in den vorherigen Bereich verschieben",
+    ),
+    ("Move selected entry", "Ausgewählten Eintrag verschieben"),
+    (
+        "Move selected entry to the trash",
+        "Ausgewählten Eintrag in den Papierkorb verschieben",
+    ),
+    ("Move {} into", "{} verschieben nach"),
+    ("Moved {} to the trash", "{} in den Papierkorb verschoben"),
+    ("Moved {} to {}", "{} nach {} verschoben"),
+    ("Moving", "Verschiebe"),
+    ("Name", "Name"),
+    ("Name this search", "Diese Suche benennen"),
+    ("Navigation", "Navigation"),
+    ("Never monospace", "Nie Festbreitenschrift"),
+    ("New file", "Neue Datei"),
+    ("New file name", "Name der neuen Datei"),
+    ("New folder", "Neuer Ordner"),
+    ("New folder name", "Name des neuen Ordners"),
+    (
+        "New smart folder: *.rs modified:7d under ~/src",
+        "Neuer intelligenter Ordner: *.rs modified:7d under ~/src",
+    ),
+    (
+        "No background tasks running",
+        "Keine Hintergrundaufgaben aktiv",
+    ),
+    ("No file operations yet", "Noch keine Dateioperationen"),
+    ("No matches", "Keine Treffer"),
+    ("Nothing to replace", "Nichts zu ersetzen"),
+    ("OK", "OK"),
+    ("Only in left", "Nur links"),
+    ("Only in right", "Nur rechts"),
+    ("Open command palette", "Befehlspalette öffnen"),
+    ("Open externally", "Extern öffnen"),
+    ("Open hex view", "Hex-Ansicht öffnen"),
+    ("Open selected entry", "Ausgewählten Eintrag öffnen"),
+    (
+        "Open selected file in the right pane",
+        "Ausgewählte Datei im rechten Bereich öffnen",
+    ),
+    (
+        "Open terminal in current directory",
+        "Terminal im aktuellen Verzeichnis öffnen",
+    ),
+    ("Open trash", "Papierkorb öffnen"),
+    ("Operation log", "Vorgangsprotokoll"),
+    ("Orange", "Orange"),
+    ("Other", "Sonstige"),
+    ("Overwrite", "Überschreiben"),
+    ("Overwrote {} with {}", "{} mit {} überschrieben"),
+    ("Pause", "Pausieren"),
+    (
+        "Permanently delete selected entry",
+        "Ausgewählten Eintrag endgültig löschen",
+    ),
+    (
+        "Permanently delete {}? This cannot be undone.",
+        "{} endgültig löschen? Dies kann nicht rückgängig gemacht werden.",
+    ),
+    ("Photo", "Foto"),
+    ("Pictures", "Bilder"),
+    (
+        "Pin or unpin selected entry",
+        "Ausgewählten Eintrag anheften oder lösen",
+    ),
+    ("Pinned", "Angeheftet"),
+    ("Properties", "Eigenschaften"),
+    ("Purple", "Lila"),
+    ("Recent", "Zuletzt besucht"),
+    ("Recent filters", "Letzte Filter"),
+    ("Red", "Rot"),
+    ("Redo", "Wiederholen"),
+    ("Redo file operation", "Dateioperation wiederholen"),
+    ("Reduce motion", "Bewegungen reduzieren"),
+    ("Refresh directory", "Verzeichnis aktualisieren"),
+    ("Regex", "Regex"),
+    ("Remove", "Entfernen"),
+    ("Rename", "Umbenennen"),
+    ("Rename selected entry", "Ausgewählten Eintrag umbenennen"),
+    ("Rename {} to", "{} umbenennen in"),
+    ("Renamed {} to {}", "{} in {} umbenannt"),
+    ("Replace 1 occurrence in {}?", "1 Vorkommen in {} ersetzen?"),
+    ("Replace all", "Alle ersetzen"),
+    ("Replace first", "Erstes ersetzen"),
+    ("Replace with", "Ersetzen durch"),
+    (
+        "Replace {} occurrences in {}?",
+        "{} Vorkommen in {} ersetzen?",
+    ),
+    (
+        "Replaced {} occurrences in {}",
+        "{} Vorkommen in {} ersetzt",
+    ),
+    ("Resolves to {}", "Wird aufgelöst zu {}"),
+    ("Restore", "Wiederherstellen"),
+    ("Resume", "Fortsetzen"),
+    ("Retry", "Erneut versuchen"),
+    ("Right:", "Rechts:"),
+    ("Run", "Ausführen"),
+    ("Run in background", "Im Hintergrund ausführen"),
+    ("Run selected executable", "Ausgewähltes Programm ausführen"),
+    ("Running", "Läuft"),
+    ("Save", "Speichern"),
+    ("Save search", "Suche speichern"),
+    ("Search", "Suchen"),
+    (
+        "Search files in current directory",
+        "Dateien im aktuellen Verzeichnis durchsuchen",
+    ),
+    ("Searches", "Gespeicherte Suchen"),
+    ("Searching...", "Suche läuft..."),
+    (
+        "Searching... {} results so far",
+        "Suche... bisher {} Treffer",
+    ),
+    ("Select next entry", "Nächsten Eintrag auswählen"),
+    ("Select previous entry", "Vorherigen Eintrag auswählen"),
+    ("Settings", "Einstellungen"),
+    ("Show as hex", "Als Hex anzeigen"),
+    ("Show as text", "Als Text anzeigen"),
+    ("Show indent guides", "Einrückungslinien anzeigen"),
+    (
+        "Show or hide background tasks",
+        "Hintergrundaufgaben ein- oder ausblenden",
+    ),
+    (
+        "Show or hide hidden files",
+        "Versteckte Dateien ein- oder ausblenden",
+    ),
+    (
+        "Show or hide keyboard shortcuts",
+        "Tastenkürzel ein- oder ausblenden",
+    ),
+    (
+        "Show or hide operation history",
+        "Verlauf der Operationen ein- oder ausblenden",
+    ),
+    (
+        "Show or hide settings",
+        "Einstellungen ein- oder ausblenden",
+    ),
+    (
+        "Show or hide the operation log",
+        "Vorgangsprotokoll ein- oder ausblenden",
+    ),
+    (
+        "Show properties of selected entry",
+        "Eigenschaften des ausgewählten Eintrags anzeigen",
+    ),
+    (
+        "Show tabs and trailing spaces",
+        "Tabulatoren und Leerzeichen am Zeilenende anzeigen",
+    ),
+    ("Showing the first {}", "Die ersten {} werden angezeigt"),
+    ("Size", "Größe"),
+    ("Skip", "Überspringen"),
+    (
+        "Skipped line {} of {}, the pattern is too complex",
+        "Zeile {} von {} übersprungen, das Muster ist zu komplex",
+    ),
+    (
+        "Skipped {} lines of {}, the pattern is too complex",
+        "{} Zeilen von {} übersprungen, das Muster ist zu komplex",
+    ),
+    ("Smart folders", "Intelligente Ordner"),
+    ("Sort by", "Sortieren nach"),
+    ("Sort directories", "Verzeichnisse sortieren"),
+    (
+        "Speed: {}/s, time remaining: {}",
+        "Geschwindigkeit: {}/s, verbleibende Zeit: {}",
+    ),
+    ("Statistics", "Statistik"),
+    ("Stop", "Stoppen"),
+    ("Stop editing", "Bearbeitung beenden"),
+    ("Stop following", "Verfolgen beenden"),
+    ("Tab width", "Tabulatorbreite"),
+    ("Tasks", "Aufgaben"),
+    ("Terminal", "Terminal"),
+    ("Terminal command", "Terminalbefehl"),
+    (
+        "The directories are identical",
+        "Die Verzeichnisse sind identisch",
+    ),
+    ("The trash is empty", "Der Papierkorb ist leer"),
+    (
+        "This file is {}, loading it may freeze the viewer",
+        "Diese Datei ist {} groß, das Laden kann die Anzeige einfrieren",
+    ),
+    (
+        "This location is not responding",
+        "Dieser Ort antwortet nicht",
+    ),
+    ("Tint names by age", "Namen nach Alter einfärben"),
+    (
+        "Toggle blame column in the viewer",
+        "Blame-Spalte im Betrachter umschalten",
+    ),
+    ("Trash", "Papierkorb"),
+    ("Type", "Typ"),
+    ("Type a command", "Befehl eingeben"),
+    ("Type a path to go to", "Pfad zum Wechseln eingeben"),
+    ("Undo", "Rückgängig"),
+    (
+        "Undo last file operation",
+        "Letzte Dateioperation rückgängig machen",
+    ),
+    ("Undone: {}", "Rückgängig gemacht: {}"),
+    ("Unknown", "Unbekannt"),
+    ("Unpin", "Lösen"),
+    ("Unsaved changes", "Ungespeicherte Änderungen"),
+    ("Verify", "Prüfen"),
+    ("View", "Ansicht"),
+    (
+        "View diff of selected file against HEAD",
+        "Diff der ausgewählten Datei gegen HEAD anzeigen",
+    ),
+    ("Viewer", "Betrachter"),
+    ("Viewer font", "Schrift im Betrachter"),
+    ("Volumes", "Laufwerke"),
+    (
+        "Warn before opening files over (MB)",
+        "Warnen beim Öffnen von Dateien über (MB)",
+    ),
+    ("Warning", "Warnung"),
+    (
+        "When a copy or move target exists",
+        "Wenn das Ziel beim Kopieren oder Verschieben existiert",
+    ),
+    ("Yellow", "Gelb"),
+    ("not responding", "antwortet nicht"),
+    ("to {}", "nach {}"),
+    ("{} already exists", "{} existiert bereits"),
+    (
+        "{} already exists, save again to overwrite it",
+        "{} existiert bereits, erneut speichern zum Überschreiben",
+    ),
+    ("{} differences", "{} Unterschiede"),
+    ("{} does not exist", "{} existiert nicht"),
+    ("{} free", "{} frei"),
+    ("{} free of {}", "{} von {} frei"),
+    (
+        "{} is no longer a directory",
+        "{} ist kein Verzeichnis mehr",
+    ),
+    ("{} is no longer a file", "{} ist keine Datei mehr"),
+    ("{} is not responding", "{} antwortet nicht"),
+    (
+        "{} items, calculating size...",
+        "{} Elemente, Größe wird berechnet...",
+    ),
+    ("{} items, {}", "{} Elemente, {}"),
+    (
+        "{} listed files are missing",
+        "{} aufgeführte Dateien fehlen",
+    ),
+    ("{} more files skipped", "{} weitere Dateien übersprungen"),
+    ("{} of {}", "{} von {}"),
+    ("{} results", "{} Treffer"),
+    (
+        "{} was unmounted, showing the home folder instead",
+        "{} wurde ausgehängt, stattdessen wird der persönliche Ordner angezeigt",
+    ),
+];
+
+const HEBREW: &[(&str, &str)] = &[
+    ("1 item, calculating size...", "פריט 1, מחשב גודל..."),
+    ("1 item, {}", "פריט 1, {}"),
+    ("1 result", "תוצאה אחת"),
+    ("All tags", "כל התגיות"),
+    ("Always monospace", "תמיד גופן ברוחב קבוע"),
+    ("Apply", "החל"),
+    ("Apply to all", "החל על הכול"),
+    ("Ascending", "סדר עולה"),
+    ("Ask", "לשאול"),
+    (
+        "Auto-scroll paused, scroll down to resume",
+        "הגלילה האוטומטית מושהית, גלול למטה כדי להמשיך",
+    ),
+    ("Blue", "כחול"),
+    ("Calculating...", "מחשב..."),
+    ("Cancel", "ביטול"),
+    ("Cancelled", "בוטל"),
+    ("Cannot access: {}", "אין גישה: {}"),
+    (
+        "Cannot copy {} into itself",
+        "לא ניתן להעתיק את {} לתוך עצמו",
+    ),
+    ("Cannot read {}: {}", "לא ניתן לקרוא את {}: {}"),
+    (
+        "Click a file to view it's content",
+        "לחץ על קובץ כדי להציג את תוכנו",
+    ),
+    ("Close", "סגור"),
+    ("Close file", "סגור קובץ"),
+    ("Close settings", "סגור הגדרות"),
+    ("Close tab", "סגור לשונית"),
+    ("Close the right pane", "סגור את החלונית הימנית"),
+    ("Close trash", "סגור את סל המחזור"),
+    ("Collapse all", "כווץ הכל"),
+    ("Collapse all directories", "כווץ את כל התיקיות"),
+    (
+        "Command, {dir} is replaced by the directory",
+        "פקודה, {dir} מוחלף בתיקייה",
+    ),
+    ("Compare", "השווה"),
+    ("Compare directories", "השוואת תיקיות"),
+    (
+        "Compare selected directory with another",
+        "השווה את התיקייה שנבחרה לתיקייה אחרת",
+    ),
+    ("Comparing...", "משווה..."),
+    ("Confirm delete", "אשר מחיקה"),
+    ("Copied", "הועתק"),
+    ("Copied {} to {}", "{} הועתק אל {}"),
+    ("Copy", "העתק"),
+    ("Copy selected entry", "העתק את הפריט שנבחר"),
+    ("Copy to left", "העתק לשמאל"),
+    ("Copy to right", "העתק לימין"),
+    ("Copy {} into", "העתק את {} אל"),
+    ("Copying", "מעתיק"),
+    ("Created file {}", "נוצר הקובץ {}"),
+    ("Created folder {}", "נוצרה התיקייה {}"),
+    ("Current file", "הקובץ הנוכחי"),
+    ("Current file: {}", "קובץ נוכחי: {}"),
+    (
+        "Cycle the colored tag of selected entry",
+        "החלף את תגית הצבע של הפריט שנבחר",
+    ),
+    ("DIR", "תיקייה"),
+    ("Debug", "ניפוי באגים"),
+    ("Delete", "מחק"),
+    ("Descending", "סדר יורד"),
+    ("Desktop", "שולחן עבודה"),
+    ("Details", "פרטים"),
+    ("Diff", "השוואה"),
+    ("Diff against HEAD: {}", "השוואה מול HEAD: {}"),
+    ("Different", "שונה"),
+    ("Directories first", "תיקיות תחילה"),
+    ("Directories last", "תיקיות בסוף"),
+    ("Directory", "תיקייה"),
+    ("Directory and below", "התיקייה ותתי התיקיות"),
+    ("Directory to compare with", "תיקייה להשוואה"),
+    ("Discard", "בטל שינויים"),
+    (
+        "Discard unsaved changes to {}?",
+        "לבטל את השינויים שלא נשמרו ב-{}?",
+    ),
+    ("Documents", "מסמכים"),
+    ("Does not exist", "אינו קיים"),
+    ("Downloads", "הורדות"),
+    ("Edit", "עריכה"),
+    ("Error", "שגיאה"),
+    ("Expand all", "הרחב הכל"),
+    ("Expand all depth", "עומק להרחבת הכול"),
+    ("Expand all directories", "הרחב את כל התיקיות"),
+    ("Export", "ייצוא"),
+    (
+        "Export directory listing to CSV or JSON",
+        "ייצא את רשימת התיקייה ל-CSV או JSON",
+    ),
+    (
+        "Export {} entries to (.csv or .json)",
+        "ייצא {} פריטים אל (.csv או .json)",
+    ),
+    ("Exported", "יוצא"),
+    ("FAILED", "נכשל"),
+    ("FILE", "קובץ"),
+    ("File", "קובץ"),
+    ("File (its folder will open)", "קובץ (התיקייה שלו תיפתח)"),
+    (
+        "File name must end in .csv or .json",
+        "שם הקובץ חייב להסתיים ב-.csv או ב-.json",
+    ),
+    ("File operations", "פעולות קבצים"),
+    ("File: {}", "קובץ: {}"),
+    ("File: {} (binary, {})", "קובץ: {} (בינארי, {})"),
+    ("Filter: {} (Escape to clear)", "סינון: {} (Escape לניקוי)"),
+    ("Find in current file", "חפש בקובץ הנוכחי"),
+    ("First {} results", "{} התוצאות הראשונות"),
+    ("Follow", "עקוב"),
+    ("General", "כללי"),
+    ("Go", "מעבר"),
+    ("Go back", "חזור"),
+    ("Go to home directory", "עבור לתיקיית הבית"),
+    ("Go to parent directory", "עבור לתיקיית האב"),
+    ("Green", "ירוק"),
+    ("Grid", "רשת"),
+    ("Group by type", "קבץ לפי סוג"),
+    ("Help", "עזרה"),
+    ("Hide binary files", "הסתר קבצים בינאריים"),
+    ("Hide hidden files", "הסתר קבצים נסתרים"),
+    ("Hide large files", "הסתר קבצים גדולים"),
+    ("History", "היסטוריה"),
+    ("Home", "בית"),
+    ("If the target exists", "אם היעד קיים"),
+    (
+        "Index current directory for fast search",
+        "צור אינדקס לתיקייה הנוכחית לחיפוש מהיר",
+    ),
+    ("Info", "מידע"),
+    ("Insert", "הוספה"),
+    ("Keep both", "לשמור את שניהם"),
+    ("Keyboard shortcuts", "קיצורי מקלדת"),
+    ("Language", "שפה"),
+    ("Left: {}", "שמאל: {}"),
+    ("List", "רשימה"),
+    ("Load anyway", "טען בכל זאת"),
+    ("Loading {}...", "טוען {}..."),
+    ("Match case", "התאם רישיות"),
+    ("Mixed", "מעורב"),
+    ("Modified", "שונה"),
+    (
+        "Monospace for code and logs",
+        "גופן ברוחב קבוע לקוד ויומנים",
+    ),
+    ("Move", "העבר"),
+    ("Move focus to the next pane", "העבר את המיקוד לחלונית הבאה"),
+    (
+        "Move focus to the previous pane",
+        "העבר את המיקוד לחלונית הקודמת",
+    ),
+    ("Move selected entry", "העבר את הפריט שנבחר"),
+    (
+        "Move selected entry to the trash",
+        "העבר את הפריט שנבחר לאשפה",
+    ),
+    ("Move {} into", "העבר את {} אל"),
+    ("Moved {} to the trash", "{} הועבר לאשפה"),
+    ("Moved {} to {}", "{} הועבר אל {}"),
+    ("Moving", "מעביר"),
+    ("Name", "שם"),
+    ("Name this search", "תן שם לחיפוש"),
+    ("Navigation", "ניווט"),
+    ("Never monospace", "לעולם לא גופן ברוחב קבוע"),
+    ("New file", "קובץ חדש"),
+    ("New file name", "שם הקובץ החדש"),
+    ("New folder", "תיקייה חדשה"),
+    ("New folder name", "שם התיקייה החדשה"),
+    (
+        "New smart folder: *.rs modified:7d under ~/src",
+        "תיקייה חכמה חדשה: *.rs modified:7d under ~/src",
+    ),
+    ("No background tasks running", "אין משימות רקע פעילות"),
+    ("No file operations yet", "אין עדיין פעולות קבצים"),
+    ("No matches", "אין התאמות"),
+    ("Nothing to replace", "אין מה להחליף"),
+    ("OK", "אישור"),
+    ("Only in left", "רק בשמאל"),
+    ("Only in right", "רק בימין"),
+    ("Open command palette", "פתח את לוח הפקודות"),
+    ("Open externally", "פתח באופן חיצוני"),
+    ("Open hex view", "פתח תצוגה הקסדצימלית"),
+    ("Open selected entry", "פתח את הפריט שנבחר"),
+    (
+        "Open selected file in the right pane",
+        "פתח את הקובץ שנבחר בחלונית הימנית",
+    ),
+    (
+        "Open terminal in current directory",
+        "פתח מסוף בתיקייה הנוכחית",
+    ),
+    ("Open trash", "פתח את האשפה"),
+    ("Operation log", "יומן פעולות"),
+    ("Orange", "כתום"),
+    ("Other", "אחר"),
+    ("Overwrite", "דריסה"),
+    ("Overwrote {} with {}", "{} נדרס על ידי {}"),
+    ("Pause", "השהה"),
+    (
+        "Permanently delete selected entry",
+        "מחק לצמיתות את הפריט הנבחר",
+    ),
+    (
+        "Permanently delete {}? This cannot be undone.",
+        "למחוק לצמיתות את {}? לא ניתן לבטל פעולה זו.",
+    ),
+    ("Photo", "תמונה"),
+    ("Pictures", "תמונות"),
+    (
+        "Pin or unpin selected entry",
+        "הצמד או בטל הצמדה של הפריט שנבחר",
+    ),
+    ("Pinned", "מוצמדים"),
+    ("Properties", "מאפיינים"),
+    ("Purple", "סגול"),
+    ("Recent", "אחרונים"),
+    ("Recent filters", "מסננים אחרונים"),
+    ("Red", "אדום"),
+    ("Redo", "בצע שוב"),
+    ("Redo file operation", "בצע שוב פעולת קובץ"),
+    ("Reduce motion", "הפחת תנועה"),
+    ("Refresh directory", "רענן תיקייה"),
+    ("Regex", "ביטוי רגולרי"),
+    ("Remove", "הסר"),
+    ("Rename", "שנה שם"),
+    ("Rename selected entry", "שנה את שם הפריט שנבחר"),
+    ("Rename {} to", "שנה את שם {} ל"),
+    ("Renamed {} to {}", "שם {} שונה ל-{}"),
+    ("Replace 1 occurrence in {}?", "להחליף מופע אחד ב-{}?"),
+    ("Replace all", "החלף הכל"),
+    ("Replace first", "החלף את הראשון"),
+    ("Replace with", "החלף ב"),
+    ("Replace {} occurrences in {}?", "להחליף {} מופעים ב-{}?"),
+    ("Replaced {} occurrences in {}", "הוחלפו {} מופעים ב-{}"),
+    ("Resolves to {}", "מתפרש כ-{}"),
+    ("Restore", "שחזר"),
+    ("Resume", "המשך"),
+    ("Retry", "נסה שוב"),
+    ("Right:", "ימין:"),
+    ("Run", "הפעל"),
+    ("Run in background", "הפעל ברקע"),
+    ("Run selected executable", "הפעל את קובץ ההפעלה שנבחר"),
+    ("Running", "פועל"),
+    ("Save", "שמור"),
+    ("Save search", "שמור חיפוש"),
+    ("Search", "חיפוש"),
+    (
+        "Search files in current directory",
+        "חפש קבצים בתיקייה הנוכחית",
+    ),
+    ("Searches", "חיפושים"),
+    ("Searching...", "מחפש..."),
+    ("Searching... {} results so far", "מחפש... {} תוצאות עד כה"),
+    ("Select next entry", "בחר את הפריט הבא"),
+    ("Select previous entry", "בחר את הפריט הקודם"),
+    ("Settings", "הגדרות"),
+    ("Show as hex", "הצג כהקסדצימלי"),
+    ("Show as text", "הצג כטקסט"),
+    ("Show indent guides", "הצג קווי הזחה"),
+    ("Show or hide background tasks", "הצג או הסתר משימות רקע"),
+    ("Show or hide hidden files", "הצג או הסתר קבצים נסתרים"),
+    (
+        "Show or hide keyboard shortcuts",
+        "הצג או הסתר קיצורי מקלדת",
+    ),
+    (
+        "Show or hide operation history",
+        "הצג או הסתר את היסטוריית הפעולות",
+    ),
+    ("Show or hide settings", "הצג או הסתר הגדרות"),
+    (
+        "Show or hide the operation log",
+        "הצג או הסתר את יומן הפעולות",
+    ),
+    (
+        "Show properties of selected entry",
+        "הצג את מאפייני הפריט שנבחר",
+    ),
+    (
+        "Show tabs and trailing spaces",
+        "הצג טאבים ורווחים בסוף שורה",
+    ),
+    ("Showing the first {}", "מוצגים {} הראשונים"),
+    ("Size", "גודל"),
+    ("Skip", "דילוג"),
+    (
+        "Skipped line {} of {}, the pattern is too complex",
+        "שורה {} ב-{} דולגה, התבנית מורכבת מדי",
+    ),
+    (
+        "Skipped {} lines of {}, the pattern is too complex",
+        "{} שורות ב-{} דולגו, התבנית מורכבת מדי",
+    ),
+    ("Smart folders", "תיקיות חכמות"),
+    ("Sort by", "מיין לפי"),
+    ("Sort directories", "מיין תיקיות"),
+    (
+        "Speed: {}/s, time remaining: {}",
+        "מהירות: {}/s, זמן נותר: {}",
+    ),
+    ("Statistics", "סטטיסטיקה"),
+    ("Stop", "עצור"),
+    ("Stop editing", "סיום עריכה"),
+    ("Stop following", "הפסק מעקב"),
+    ("Tab width", "רוחב טאב"),
+    ("Tasks", "משימות"),
+    ("Terminal", "מסוף"),
+    ("Terminal command", "פקודת מסוף"),
+    ("The directories are identical", "התיקיות זהות"),
+    ("The trash is empty", "סל המחזור ריק"),
+    (
+        "This file is {}, loading it may freeze the viewer",
+        "גודל הקובץ {}, טעינתו עלולה להקפיא את המציג",
+    ),
+    ("This location is not responding", "המיקום הזה אינו מגיב"),
+    ("Tint names by age", "צבע שמות לפי גיל"),
+    (
+        "Toggle blame column in the viewer",
+        "הצג או הסתר את עמודת ה-blame במציג",
+    ),
+    ("Trash", "סל מחזור"),
+    ("Type", "סוג"),
+    ("Type a command", "הקלד פקודה"),
+    ("Type a path to go to", "הקלד נתיב למעבר"),
+    ("Undo", "בטל"),
+    ("Undo last file operation", "בטל את פעולת הקובץ האחרונה"),
+    ("Undone: {}", "בוטל: {}"),
+    ("Unknown", "לא ידוע"),
+    ("Unpin", "בטל הצמדה"),
+    ("Unsaved changes", "שינויים שלא נשמרו"),
+    ("Verify", "אמת"),
+    ("View", "תצוגה"),
+    (
+        "View diff of selected file against HEAD",
+        "הצג השוואה של הקובץ שנבחר מול HEAD",
+    ),
+    ("Viewer", "מציג"),
+    ("Viewer font", "גופן המציג"),
+    ("Volumes", "כוננים"),
+    (
+        "Warn before opening files over (MB)",
+        "הזהר לפני פתיחת קבצים מעל (MB)",
+    ),
+    ("Warning", "אזהרה"),
+    (
+        "When a copy or move target exists",
+        "כאשר יעד ההעתקה או ההעברה קיים",
+    ),
+    ("Yellow", "צהוב"),
+    ("not responding", "אינו מגיב"),
+    ("to {}", "אל {}"),
+    ("{} already exists", "{} כבר קיים"),
+    (
+        "{} already exists, save again to overwrite it",
+        "{} כבר קיים, שמור שוב כדי לדרוס אותו",
+    ),
+    ("{} differences", "{} הבדלים"),
+    ("{} does not exist", "{} אינו קיים"),
+    ("{} free", "{} פנויים"),
+    ("{} free of {}", "{} פנויים מתוך {}"),
+    ("{} is no longer a directory", "{} אינו תיקייה עוד"),
+    ("{} is no longer a file", "{} אינו קובץ עוד"),
+    ("{} is not responding", "{} אינו מגיב"),
+    ("{} items, calculating size...", "{} פריטים, מחשב גודל..."),
+    ("{} items, {}", "{} פריטים, {}"),
+    ("{} listed files are missing", "{} קבצים רשומים חסרים"),
+    ("{} more files skipped", "{} קבצים נוספים דולגו"),
+    ("{} of {}", "{} מתוך {}"),
+    ("{} results", "{} תוצאות"),
+    (
+        "{} was unmounted, showing the home folder instead",
+        "{} נותק, מוצגת תיקיית הבית במקום",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeSet;
+
+    fn keys(table: &[(&'static str, &str)]) -> BTreeSet<&'static str> {
+        table.iter().map(|(english, _)| *english).collect()
+    }
+
+    #[test]
+    fn every_locale_translates_the_same_strings() {
+        let german = keys(GERMAN);
+
+        for table in [GERMAN, HEBREW] {
+            assert_eq!(keys(table).len(), table.len());
+            assert_eq!(keys(table), german);
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{BoxStream, StreamExt};
+use iced::Subscription;
+use iced_native::subscription::Recipe;
+
+use crate::fuzzy;
+use crate::storage;
+use crate::tasks;
+use crate::watcher;
+
+use std::fs;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const INDEX: &str = "index";
+const PROGRESS_INTERVAL: usize = 1000;
+const CHECKPOINT_INTERVAL: usize = 10_000;
+const THROTTLE_BATCH: usize = 100;
+const THROTTLE_PAUSE: Duration = Duration::from_millis(2);
+
+#[derive(Debug, Clone)]
+pub struct Indexed {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub is_dir: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Index {
+    root: Option<PathBuf>,
+    entries: Arc<Vec<Indexed>>,
+    pending: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Checkpoint(Index),
+    Built(Index),
+    Finished(tasks::Id),
+}
+
+impl Index {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            pending: vec![root.clone()],
+            root: Some(root),
+            entries: Arc::default(),
+        }
+    }
+
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn covers(&self, path: &Path) -> bool {
+        self.is_complete()
+            && self
+                .root
+                .as_ref()
+                .is_some_and(|root| path.starts_with(root))
+    }
+
+    pub fn apply(&mut self, change: &watcher::Change) -> bool {
+        let root = match self.root.clone() {
+            Some(root) => root,
+            None => return false,
+        };
+
+        let within = |path: &Path| {
+            path.strip_prefix(&root).is_ok_and(|relative| {
+                !relative.as_os_str().is_empty()
+                    && !relative
+                        .iter()
+                        .any(|name| name.to_string_lossy().starts_with('.'))
+            })
+        };
+
+        match change {
+            watcher::Change::Created(path) | watcher::Change::Modified(path) if within(path) => {
+                let indexed = match stat(path.clone()) {
+                    Some(indexed) => indexed,
+                    None => return false,
+                };
+
+                let entries = Arc::make_mut(&mut self.entries);
+
+                match entries.binary_search_by(|entry| entry.path.cmp(path)) {
+                    Ok(position) => entries[position] = indexed,
+                    Err(position) => entries.insert(position, indexed),
+                }
+
+                true
+            }
+            watcher::Change::Removed(path) if within(path) => {
+                let before = self.entries.len();
+
+                Arc::make_mut(&mut self.entries).retain(|entry| !entry.path.starts_with(path));
+
+                self.entries.len() != before
+            }
+            watcher::Change::Renamed { from, to } => {
+                let entries = Arc::make_mut(&mut self.entries);
+                let before = entries.len();
+
+                entries.retain(|entry| !entry.path.starts_with(from) || within(to));
+
+                if within(to) {
+                    entries.retain(|entry| !entry.path.starts_with(to));
+
+                    for entry in entries.iter_mut() {
+                        if let Ok(rest) = entry.path.strip_prefix(from) {
+                            entry.path = to.join(rest);
+                        }
+                    }
+
+                    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+                    if let Err(position) = entries.binary_search_by(|entry| entry.path.cmp(to)) {
+                        if let Some(indexed) = stat(to.clone()) {
+                            entries.insert(position, indexed);
+                        }
+                    }
+
+                    return true;
+                }
+
+                entries.len() != before
+            }
+            _ => false,
+        }
+    }
+
+    pub fn files_under(&self, directory: &Path) -> Vec<PathBuf> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.is_dir && entry.path.starts_with(directory))
+            .map(|entry| entry.path.clone())
+            .collect()
+    }
+
+    pub fn find(&self, query: &str, limit: usize) -> Vec<PathBuf> {
+        let root = match self.root.as_ref() {
+            Some(root) => root,
+            None => return vec![],
+        };
+
+        let mut matches: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let relative = entry.path.strip_prefix(root).unwrap_or(&entry.path);
+
+                fuzzy::score(query, &relative.to_string_lossy()).map(|score| (score, entry))
+            })
+            .collect();
+
+        matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, entry)| entry.path.clone())
+            .collect()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let root = match self.root.as_ref() {
+            Some(root) if storable(root) => root,
+            _ => return vec![],
+        };
+
+        std::iter::once(root.to_string_lossy().to_string())
+            .chain(
+                self.pending
+                    .iter()
+                    .filter(|directory| storable(directory))
+                    .map(|directory| format!("p\t0\t\t{}", directory.to_string_lossy())),
+            )
+            .chain(
+                self.entries
+                    .iter()
+                    .filter(|entry| storable(&entry.path))
+                    .map(encode),
+            )
+            .collect()
+    }
+}
+
+fn storable(path: &Path) -> bool {
+    !path.to_string_lossy().contains(['\n', '\r'])
+}
+
+fn sorted(mut entries: Vec<Indexed>) -> Vec<Indexed> {
+    entries.reverse();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries.dedup_by(|a, b| a.path == b.path);
+
+    entries
+}
+
+fn stat(path: PathBuf) -> Option<Indexed> {
+    let metadata = fs::symlink_metadata(&path).ok()?;
+
+    Some(Indexed {
+        size: metadata.len(),
+        modified: metadata.modified().ok(),
+        is_dir: metadata.is_dir(),
+        path,
+    })
+}
+
+fn count(files: usize) -> String {
+    match files {
+        0..=9_999 => files.to_string(),
+        10_000..=999_999 => format!("{}k", files / 1000),
+        _ => format!("{:.1}M", files as f64 / 1_000_000.0),
+    }
+}
+
+fn encode(entry: &Indexed) -> String {
+    let modified = entry
+        .modified
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(String::new(), |modified| modified.as_secs().to_string());
+
+    format!(
+        "{}\t{}\t{}\t{}",
+        if entry.is_dir { "d" } else { "f" },
+        entry.size,
+        modified,
+        entry.path.to_string_lossy()
+    )
+}
+
+fn decode(line: &str) -> Option<Indexed> {
+    let mut fields = line.splitn(4, '\t');
+
+    let is_dir = fields.next()? == "d";
+    let size = fields.next()?.parse().ok()?;
+    let modified = fields
+        .next()?
+        .parse()
+        .ok()
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds));
+    let path = PathBuf::from(fields.next()?);
+
+    Some(Indexed {
+        path,
+        size,
+        modified,
+        is_dir,
+    })
+}
+
+pub async fn load() -> Index {
+    let lines = storage::load(INDEX);
+    let mut lines = lines.iter();
+
+    let root = match lines.next() {
+        Some(root) => PathBuf::from(root),
+        None => return Index::default(),
+    };
+
+    let mut pending = vec![];
+    let mut entries = vec![];
+
+    for line in lines {
+        match line.strip_prefix("p\t0\t\t") {
+            Some(directory) => pending.push(PathBuf::from(directory)),
+            None => entries.extend(decode(line)),
+        }
+    }
+
+    Index {
+        root: Some(root),
+        entries: Arc::new(sorted(entries)),
+        pending,
+    }
+}
+
+pub async fn save(index: Index) -> Result<(), String> {
+    storage::save(INDEX, index.lines()).await
+}
+
+pub fn build(task: tasks::Handle, index: Index) -> Subscription<Event> {
+    Subscription::from_recipe(Build { task, index })
+}
+
+struct Build {
+    task: tasks::Handle,
+    index: Index,
+}
+
+impl<H, I> Recipe<H, I> for Build
+where
+    H: std::hash::Hasher,
+{
+    type Output = Event;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.task.id().hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Event> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        thread::spawn(move || {
+            let Build { task, mut index } = *self;
+
+            let mut entries =
+                Arc::try_unwrap(index.entries).unwrap_or_else(|shared| (*shared).clone());
+            let mut files = entries.iter().filter(|entry| !entry.is_dir).count();
+            let mut since_checkpoint = 0;
+
+            task.set_progress(0, 0, format!("Indexed {} files", count(files)));
+
+            while let Some(directory) = index.pending.pop() {
+                if task.is_cancelled() || sender.is_closed() {
+                    index.pending.push(directory);
+                    break;
+                }
+
+                let children = match fs::read_dir(&directory) {
+                    Ok(children) => children,
+                    Err(_) => continue,
+                };
+
+                for child in children.filter_map(Result::ok) {
+                    if child.file_name().to_string_lossy().starts_with('.') {
+                        continue;
+                    }
+
+                    let indexed = match stat(child.path()) {
+                        Some(indexed) => indexed,
+                        None => continue,
+                    };
+
+                    if indexed.is_dir {
+                        index.pending.push(indexed.path.clone());
+                    } else {
+                        files += 1;
+
+                        if files % PROGRESS_INTERVAL == 0 {
+                            task.set_progress(0, 0, format!("Indexed {} files", count(files)));
+                        }
+                    }
+
+                    entries.push(indexed);
+                    since_checkpoint += 1;
+
+                    if since_checkpoint % THROTTLE_BATCH == 0 {
+                        thread::sleep(THROTTLE_PAUSE);
+                    }
+                }
+
+                if since_checkpoint >= CHECKPOINT_INTERVAL {
+                    since_checkpoint = 0;
+
+                    let checkpoint = Index {
+                        root: index.root.clone(),
+                        entries: Arc::new(sorted(entries.clone())),
+                        pending: index.pending.clone(),
+                    };
+
+                    let _ = sender.unbounded_send(Event::Checkpoint(checkpoint));
+                }
+            }
+
+            index.entries = Arc::new(sorted(entries));
+
+            let event = if index.is_complete() {
+                Event::Built(index)
+            } else {
+                Event::Checkpoint(index)
+            };
+
+            let _ = sender.unbounded_send(event);
+            let _ = sender.unbounded_send(Event::Finished(task.id()));
+        });
+
+        receiver.boxed()
+    }
+}
@@ -0,0 +1,154 @@
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{BoxStream, StreamExt};
+use iced::Subscription;
+use iced_native::subscription::Recipe;
+
+use crate::remote;
+
+use std::hash::Hash;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const SOCKET: &str = "navigation_tree.sock";
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(unix)]
+pub type Listener = std::os::unix::net::UnixListener;
+
+#[cfg(not(unix))]
+pub struct Listener;
+
+#[cfg(unix)]
+fn socket() -> Option<std::path::PathBuf> {
+    use std::fs;
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    if let Some(runtime) = dirs_next::runtime_dir() {
+        return Some(runtime.join(SOCKET));
+    }
+
+    let uid = unsafe { libc::getuid() };
+    let directory = std::env::temp_dir().join(format!("navigation_tree-{}", uid));
+
+    let _ = fs::DirBuilder::new().mode(0o700).create(&directory);
+
+    fs::symlink_metadata(&directory)
+        .is_ok_and(|metadata| {
+            metadata.is_dir() && metadata.uid() == uid && metadata.permissions().mode() & 0o077 == 0
+        })
+        .then(|| directory.join(SOCKET))
+}
+
+#[cfg(unix)]
+pub fn forward(path: &std::path::Path) -> bool {
+    use std::io::Write;
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = match socket().and_then(|socket| UnixStream::connect(socket).ok()) {
+        Some(stream) => stream,
+        None => return false,
+    };
+
+    writeln!(stream, "{}", path.to_string_lossy()).is_ok()
+}
+
+#[cfg(not(unix))]
+pub fn forward(_path: &std::path::Path) -> bool {
+    false
+}
+
+#[cfg(unix)]
+pub fn listen() -> Option<Arc<Listener>> {
+    let socket = socket()?;
+
+    let _ = std::fs::remove_file(&socket);
+
+    Listener::bind(socket).ok().map(Arc::new)
+}
+
+#[cfg(not(unix))]
+pub fn listen() -> Option<Arc<Listener>> {
+    None
+}
+
+pub fn requests(listener: Arc<Listener>) -> Subscription<remote::Incoming> {
+    Subscription::from_recipe(Requests { listener })
+}
+
+struct Requests {
+    listener: Arc<Listener>,
+}
+
+impl<H, I> Recipe<H, I> for Requests
+where
+    H: std::hash::Hasher,
+{
+    type Output = remote::Incoming;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+    }
+
+    fn stream(
+        self: Box<Self>,
+        _input: BoxStream<'static, I>,
+    ) -> BoxStream<'static, remote::Incoming> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        thread::spawn(move || run(&self.listener, &sender));
+
+        receiver.boxed()
+    }
+}
+
+#[cfg(unix)]
+fn run(listener: &Listener, sender: &mpsc::UnboundedSender<remote::Incoming>) {
+    use std::io::{BufRead, BufReader, Write};
+
+    for mut stream in listener.incoming().filter_map(Result::ok) {
+        if sender.is_closed() {
+            return;
+        }
+
+        let sender = sender.clone();
+
+        thread::spawn(move || {
+            let reader = match stream.try_clone() {
+                Ok(reader) => BufReader::new(reader),
+                Err(_) => return,
+            };
+
+            for line in reader.lines().map_while(Result::ok) {
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let response = match remote::parse(&line) {
+                    Ok(request) => {
+                        let (reply, response) = std::sync::mpsc::channel();
+
+                        if sender
+                            .unbounded_send(remote::Incoming::new(request, reply))
+                            .is_err()
+                        {
+                            return;
+                        }
+
+                        response.recv_timeout(REPLY_TIMEOUT).unwrap_or_else(|_| {
+                            remote::encode(&remote::Response::Failed("No response".to_string()))
+                        })
+                    }
+                    Err(error) => remote::encode(&remote::Response::Failed(error)),
+                };
+
+                if writeln!(stream, "{}", response).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn run(_listener: &Listener, _sender: &mpsc::UnboundedSender<remote::Incoming>) {}
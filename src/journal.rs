@@ -0,0 +1,150 @@
+use iced::scrollable::{self, Scrollable};
+use iced::{Color, Column, Command, Element, Length, Text};
+
+use crate::file_ops::{self, Operation, Request};
+use crate::i18n;
+
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Apply(Request),
+    Applied(Result<Operation, String>),
+    Undo,
+    Undone(Result<Operation, String>),
+    Redo,
+    Redone(Result<Operation, String>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Changed(Option<PathBuf>),
+    Failed(String),
+}
+
+#[derive(Default)]
+pub struct State {
+    done: Vec<Operation>,
+    undone: Vec<Operation>,
+    pending: Option<Operation>,
+    scrollable: scrollable::State,
+}
+
+impl State {
+    pub fn update(&mut self, message: Message) -> (Command<Message>, Option<Event>) {
+        match message {
+            Message::Apply(request) => {
+                return (
+                    Command::perform(file_ops::apply(request), Message::Applied),
+                    None,
+                );
+            }
+            Message::Applied(result) => {
+                return match result {
+                    Ok(operation) => {
+                        let target = operation.target().cloned();
+
+                        self.done.push(operation);
+                        self.undone.clear();
+
+                        (Command::none(), Some(Event::Changed(target)))
+                    }
+                    Err(error) => (Command::none(), Some(Event::Failed(error))),
+                };
+            }
+            Message::Undo if self.pending.is_none() => {
+                if let Some(operation) = self.done.pop() {
+                    self.pending = Some(operation.clone());
+
+                    return (
+                        Command::perform(file_ops::revert(operation), Message::Undone),
+                        None,
+                    );
+                }
+            }
+            Message::Undone(result) => {
+                let pending = self.pending.take();
+
+                return match result {
+                    Ok(operation) => {
+                        let target = operation.reverted_target().cloned();
+
+                        self.undone.push(operation);
+
+                        (Command::none(), Some(Event::Changed(target)))
+                    }
+                    Err(error) => {
+                        self.done.extend(pending);
+
+                        (Command::none(), Some(Event::Failed(error)))
+                    }
+                };
+            }
+            Message::Redo if self.pending.is_none() => {
+                if let Some(operation) = self.undone.pop() {
+                    self.pending = Some(operation.clone());
+
+                    return (
+                        Command::perform(file_ops::apply(operation.request()), Message::Redone),
+                        None,
+                    );
+                }
+            }
+            Message::Redone(result) => {
+                let pending = self.pending.take();
+
+                return match result {
+                    Ok(operation) => {
+                        let target = operation.target().cloned();
+
+                        self.done.push(operation);
+
+                        (Command::none(), Some(Event::Changed(target)))
+                    }
+                    Err(error) => {
+                        self.undone.extend(pending);
+
+                        (Command::none(), Some(Event::Failed(error)))
+                    }
+                };
+            }
+            Message::Undo | Message::Redo => {}
+        }
+
+        (Command::none(), None)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.pending.is_none() && !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.pending.is_none() && !self.undone.is_empty()
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let mut scrollable = Scrollable::new(&mut state.scrollable).spacing(5);
+
+    if state.done.is_empty() && state.undone.is_empty() {
+        scrollable = scrollable.push(Text::new(i18n::tr("No file operations yet")));
+    }
+
+    for operation in state.undone.iter() {
+        scrollable = scrollable.push(
+            Text::new(i18n::tr_with("Undone: {}", &[&operation]))
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    for operation in state.done.iter().rev() {
+        scrollable = scrollable.push(Text::new(operation.to_string()));
+    }
+
+    Column::new()
+        .spacing(10)
+        .width(Length::Fill)
+        .push(Text::new(i18n::tr("History")).size(30))
+        .push(scrollable)
+        .into()
+}
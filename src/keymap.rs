@@ -0,0 +1,598 @@
+use iced::keyboard::{Event, KeyCode, Modifiers};
+
+use crate::i18n;
+use crate::storage;
+
+use std::fmt;
+
+pub const KEYMAP: &str = "keymap";
+
+const KEY_CODES: &[KeyCode] = &[
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Key0,
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::Escape,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Insert,
+    KeyCode::Home,
+    KeyCode::Delete,
+    KeyCode::End,
+    KeyCode::PageDown,
+    KeyCode::PageUp,
+    KeyCode::Left,
+    KeyCode::Up,
+    KeyCode::Right,
+    KeyCode::Down,
+    KeyCode::Backspace,
+    KeyCode::Enter,
+    KeyCode::Space,
+    KeyCode::Tab,
+    KeyCode::Apostrophe,
+    KeyCode::Backslash,
+    KeyCode::Comma,
+    KeyCode::Equals,
+    KeyCode::Grave,
+    KeyCode::LBracket,
+    KeyCode::Minus,
+    KeyCode::Period,
+    KeyCode::RBracket,
+    KeyCode::Semicolon,
+    KeyCode::Slash,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Area {
+    General,
+    Navigation,
+    Viewer,
+    FileOperations,
+}
+
+impl Area {
+    pub const ALL: [Area; 4] = [
+        Area::General,
+        Area::Navigation,
+        Area::Viewer,
+        Area::FileOperations,
+    ];
+}
+
+impl fmt::Display for Area {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Area::General => "General",
+            Area::Navigation => "Navigation",
+            Area::Viewer => "Viewer",
+            Area::FileOperations => "File operations",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    ToggleHelp,
+    ToggleSettings,
+    ToggleHistory,
+    ToggleLog,
+    ToggleTasks,
+    FocusNext,
+    FocusPrevious,
+    OpenPalette,
+    OpenTerminal,
+    GoHome,
+    GoToParent,
+    EditPath,
+    OpenTrash,
+    Refresh,
+    ToggleHiddenFiles,
+    ExpandAll,
+    CollapseAll,
+    SelectPrevious,
+    SelectNext,
+    OpenSelected,
+    TogglePin,
+    CycleTag,
+    SearchFiles,
+    CompareDirectories,
+    ExportListing,
+    IndexDirectory,
+    CloseFile,
+    OpenInSplit,
+    CloseSplit,
+    FindInFile,
+    ViewDiff,
+    ToggleBlame,
+    NewFolder,
+    NewFile,
+    Rename,
+    Copy,
+    Move,
+    Trash,
+    DeletePermanently,
+    ShowProperties,
+    Run,
+    Undo,
+    Redo,
+}
+
+impl Action {
+    pub const ALL: [Action; 43] = [
+        Action::ToggleHelp,
+        Action::ToggleSettings,
+        Action::ToggleHistory,
+        Action::ToggleLog,
+        Action::ToggleTasks,
+        Action::FocusNext,
+        Action::FocusPrevious,
+        Action::OpenPalette,
+        Action::OpenTerminal,
+        Action::GoHome,
+        Action::GoToParent,
+        Action::EditPath,
+        Action::OpenTrash,
+        Action::Refresh,
+        Action::ToggleHiddenFiles,
+        Action::ExpandAll,
+        Action::CollapseAll,
+        Action::SelectPrevious,
+        Action::SelectNext,
+        Action::OpenSelected,
+        Action::TogglePin,
+        Action::CycleTag,
+        Action::SearchFiles,
+        Action::CompareDirectories,
+        Action::ExportListing,
+        Action::IndexDirectory,
+        Action::CloseFile,
+        Action::OpenInSplit,
+        Action::CloseSplit,
+        Action::FindInFile,
+        Action::ViewDiff,
+        Action::ToggleBlame,
+        Action::NewFolder,
+        Action::NewFile,
+        Action::Rename,
+        Action::Copy,
+        Action::Move,
+        Action::Trash,
+        Action::DeletePermanently,
+        Action::ShowProperties,
+        Action::Run,
+        Action::Undo,
+        Action::Redo,
+    ];
+
+    pub fn area(self) -> Area {
+        match self {
+            Action::ToggleHelp
+            | Action::ToggleSettings
+            | Action::ToggleHistory
+            | Action::ToggleLog
+            | Action::ToggleTasks
+            | Action::FocusNext
+            | Action::FocusPrevious
+            | Action::OpenPalette
+            | Action::OpenTerminal => Area::General,
+            Action::GoHome
+            | Action::GoToParent
+            | Action::EditPath
+            | Action::OpenTrash
+            | Action::Refresh
+            | Action::ToggleHiddenFiles
+            | Action::ExpandAll
+            | Action::CollapseAll
+            | Action::SelectPrevious
+            | Action::SelectNext
+            | Action::OpenSelected
+            | Action::TogglePin
+            | Action::CycleTag
+            | Action::SearchFiles
+            | Action::CompareDirectories
+            | Action::ExportListing
+            | Action::IndexDirectory => Area::Navigation,
+            Action::CloseFile
+            | Action::OpenInSplit
+            | Action::CloseSplit
+            | Action::FindInFile
+            | Action::ViewDiff
+            | Action::ToggleBlame => Area::Viewer,
+            Action::NewFolder
+            | Action::NewFile
+            | Action::Rename
+            | Action::Copy
+            | Action::Move
+            | Action::Trash
+            | Action::DeletePermanently
+            | Action::ShowProperties
+            | Action::Run
+            | Action::Undo
+            | Action::Redo => Area::FileOperations,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        let description = match self {
+            Action::ToggleHelp => "Show or hide keyboard shortcuts",
+            Action::ToggleSettings => "Show or hide settings",
+            Action::ToggleHistory => "Show or hide operation history",
+            Action::ToggleLog => "Show or hide the operation log",
+            Action::ToggleTasks => "Show or hide background tasks",
+            Action::FocusNext => "Move focus to the next pane",
+            Action::FocusPrevious => "Move focus to the previous pane",
+            Action::OpenPalette => "Open command palette",
+            Action::OpenTerminal => "Open terminal in current directory",
+            Action::GoHome => "Go to home directory",
+            Action::GoToParent => "Go to parent directory",
+            Action::EditPath => "Type a path to go to",
+            Action::OpenTrash => "Open trash",
+            Action::Refresh => "Refresh directory",
+            Action::ToggleHiddenFiles => "Show or hide hidden files",
+            Action::ExpandAll => "Expand all directories",
+            Action::CollapseAll => "Collapse all directories",
+            Action::SelectPrevious => "Select previous entry",
+            Action::SelectNext => "Select next entry",
+            Action::OpenSelected => "Open selected entry",
+            Action::TogglePin => "Pin or unpin selected entry",
+            Action::CycleTag => "Cycle the colored tag of selected entry",
+            Action::SearchFiles => "Search files in current directory",
+            Action::CompareDirectories => "Compare selected directory with another",
+            Action::ExportListing => "Export directory listing to CSV or JSON",
+            Action::IndexDirectory => "Index current directory for fast search",
+            Action::CloseFile => "Close file",
+            Action::OpenInSplit => "Open selected file in the right pane",
+            Action::CloseSplit => "Close the right pane",
+            Action::FindInFile => "Find in current file",
+            Action::ViewDiff => "View diff of selected file against HEAD",
+            Action::ToggleBlame => "Toggle blame column in the viewer",
+            Action::NewFolder => "New folder",
+            Action::NewFile => "New file",
+            Action::Rename => "Rename selected entry",
+            Action::Copy => "Copy selected entry",
+            Action::Move => "Move selected entry",
+            Action::Trash => "Move selected entry to the trash",
+            Action::DeletePermanently => "Permanently delete selected entry",
+            Action::ShowProperties => "Show properties of selected entry",
+            Action::Run => "Run selected executable",
+            Action::Undo => "Undo last file operation",
+            Action::Redo => "Redo file operation",
+        };
+
+        i18n::tr(description)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Code(KeyCode),
+    Character(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    pub fn new(key_code: KeyCode) -> Self {
+        Self {
+            key: Key::Code(key_code),
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    pub fn character(character: char) -> Self {
+        Self {
+            key: Key::Character(character),
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    pub fn control(mut self) -> Self {
+        self.modifiers.control = true;
+        self
+    }
+
+    pub fn shift(mut self) -> Self {
+        self.modifiers.shift = true;
+        self
+    }
+
+    pub fn alt(mut self) -> Self {
+        self.modifiers.alt = true;
+        self
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut parts: Vec<_> = text.split('+').collect();
+
+        let key = match parts.pop()? {
+            "" if parts.last() == Some(&"") => {
+                parts.pop();
+                Key::Character('+')
+            }
+            name => match KEY_CODES
+                .iter()
+                .find(|key_code| format!("{:?}", key_code) == name)
+            {
+                Some(key_code) => Key::Code(*key_code),
+                None => {
+                    let mut characters = name.chars();
+
+                    match (characters.next(), characters.next()) {
+                        (Some(character), None) => Key::Character(character),
+                        _ => return None,
+                    }
+                }
+            },
+        };
+
+        let mut modifiers = Modifiers::default();
+
+        for part in parts {
+            match part {
+                "Ctrl" => modifiers.control = true,
+                "Alt" => modifiers.alt = true,
+                "Shift" => modifiers.shift = true,
+                "Super" => modifiers.logo = true,
+                _ => return None,
+            }
+        }
+
+        Some(Self { key, modifiers })
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.control {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.alt {
+            f.write_str("Alt+")?;
+        }
+        if self.modifiers.shift {
+            f.write_str("Shift+")?;
+        }
+        if self.modifiers.logo {
+            f.write_str("Super+")?;
+        }
+
+        match self.key {
+            Key::Code(key_code) => write!(f, "{:?}", key_code),
+            Key::Character(character) => write!(f, "{}", character),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: Vec<(Binding, Action)>,
+}
+
+impl KeyMap {
+    pub fn action(&self, event: Event) -> Option<Action> {
+        let pressed = match event {
+            Event::KeyPressed {
+                key_code,
+                modifiers,
+            } => Binding {
+                key: Key::Code(key_code),
+                modifiers,
+            },
+            Event::CharacterReceived(character) if !character.is_control() => {
+                Binding::character(character)
+            }
+            _ => return None,
+        };
+
+        self.bindings
+            .iter()
+            .find(|(binding, _)| *binding == pressed)
+            .map(|(_, action)| *action)
+    }
+
+    pub fn load() -> Self {
+        let mut keymap = Self::default();
+
+        for line in storage::load(KEYMAP) {
+            let (binding, action) = match line.split_once('\t') {
+                Some(fields) => fields,
+                None => continue,
+            };
+
+            let action = Action::ALL
+                .iter()
+                .find(|candidate| format!("{:?}", candidate) == action.trim());
+
+            if let (Some(binding), Some(action)) = (Binding::parse(binding.trim()), action) {
+                keymap.bind(binding, *action);
+            }
+        }
+
+        keymap
+    }
+
+    pub fn bind(&mut self, binding: Binding, action: Action) {
+        self.bindings.retain(|(existing, _)| *existing != binding);
+        self.bindings.push((binding, action));
+    }
+
+    pub fn binding(&self, action: Action) -> Option<Binding> {
+        self.bindings
+            .iter()
+            .find(|(_, bound)| *bound == action)
+            .map(|(binding, _)| *binding)
+    }
+
+    pub fn bindings(&self, area: Area) -> impl Iterator<Item = (Binding, Action)> + '_ {
+        self.bindings
+            .iter()
+            .copied()
+            .filter(move |(_, action)| action.area() == area)
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let mut keymap = Self { bindings: vec![] };
+
+        keymap.bind(Binding::character('?'), Action::ToggleHelp);
+        keymap.bind(
+            Binding::new(KeyCode::Comma).control(),
+            Action::ToggleSettings,
+        );
+        keymap.bind(
+            Binding::new(KeyCode::P).control().shift(),
+            Action::OpenPalette,
+        );
+        keymap.bind(Binding::new(KeyCode::Tab), Action::FocusNext);
+        keymap.bind(Binding::new(KeyCode::Tab).shift(), Action::FocusPrevious);
+        keymap.bind(Binding::new(KeyCode::F4).shift(), Action::OpenTerminal);
+        keymap.bind(Binding::new(KeyCode::Home).alt(), Action::GoHome);
+        keymap.bind(Binding::new(KeyCode::Backspace), Action::GoToParent);
+        keymap.bind(Binding::new(KeyCode::Up).alt(), Action::GoToParent);
+        keymap.bind(Binding::new(KeyCode::L).control(), Action::EditPath);
+        keymap.bind(Binding::new(KeyCode::F5), Action::Refresh);
+        keymap.bind(
+            Binding::new(KeyCode::H).control(),
+            Action::ToggleHiddenFiles,
+        );
+        keymap.bind(Binding::new(KeyCode::F2), Action::Rename);
+        keymap.bind(Binding::new(KeyCode::Delete), Action::Trash);
+        keymap.bind(
+            Binding::new(KeyCode::Delete).shift(),
+            Action::DeletePermanently,
+        );
+        keymap.bind(
+            Binding::new(KeyCode::Right).control().shift(),
+            Action::ExpandAll,
+        );
+        keymap.bind(
+            Binding::new(KeyCode::Left).control().shift(),
+            Action::CollapseAll,
+        );
+        keymap.bind(Binding::new(KeyCode::Up), Action::SelectPrevious);
+        keymap.bind(Binding::new(KeyCode::Down), Action::SelectNext);
+        keymap.bind(Binding::new(KeyCode::Enter), Action::OpenSelected);
+        keymap.bind(Binding::new(KeyCode::Enter).alt(), Action::ShowProperties);
+        keymap.bind(Binding::new(KeyCode::P).alt(), Action::TogglePin);
+        keymap.bind(Binding::new(KeyCode::T).alt(), Action::CycleTag);
+        keymap.bind(Binding::new(KeyCode::Enter).control(), Action::Run);
+        keymap.bind(Binding::new(KeyCode::Escape), Action::CloseFile);
+        keymap.bind(Binding::new(KeyCode::W).control(), Action::CloseFile);
+        keymap.bind(
+            Binding::new(KeyCode::Backslash).control(),
+            Action::OpenInSplit,
+        );
+        keymap.bind(
+            Binding::new(KeyCode::Backslash).control().shift(),
+            Action::CloseSplit,
+        );
+        keymap.bind(Binding::new(KeyCode::F).control(), Action::FindInFile);
+        keymap.bind(
+            Binding::new(KeyCode::F).control().shift(),
+            Action::SearchFiles,
+        );
+        keymap.bind(
+            Binding::new(KeyCode::C).control().shift(),
+            Action::CompareDirectories,
+        );
+        keymap.bind(
+            Binding::new(KeyCode::E).control().shift(),
+            Action::ExportListing,
+        );
+        keymap.bind(Binding::new(KeyCode::D).control(), Action::ViewDiff);
+        keymap.bind(Binding::new(KeyCode::B).control(), Action::ToggleBlame);
+        keymap.bind(Binding::new(KeyCode::Z).control(), Action::Undo);
+        keymap.bind(Binding::new(KeyCode::Z).control().shift(), Action::Redo);
+        keymap.bind(Binding::new(KeyCode::Y).control(), Action::Redo);
+
+        keymap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(
+            Binding::parse("Ctrl+Shift+F"),
+            Some(Binding::new(KeyCode::F).control().shift())
+        );
+        assert_eq!(
+            Binding::parse("Alt+Delete"),
+            Some(Binding::new(KeyCode::Delete).alt())
+        );
+        assert_eq!(
+            Binding::parse("Ctrl++"),
+            Some(Binding::character('+').control())
+        );
+        assert_eq!(Binding::parse("+"), Some(Binding::character('+')));
+        assert_eq!(Binding::parse("é"), Some(Binding::character('é')));
+    }
+
+    #[test]
+    fn rejects_malformed_chords() {
+        assert_eq!(Binding::parse(""), None);
+        assert_eq!(Binding::parse("Ctrl+"), None);
+        assert_eq!(Binding::parse("Hyper+A"), None);
+        assert_eq!(Binding::parse("Ctrl+Tab+"), None);
+        assert_eq!(Binding::parse("Ctrl+ab"), None);
+    }
+
+    #[test]
+    fn default_bindings_round_trip() {
+        for (binding, _) in &KeyMap::default().bindings {
+            assert_eq!(Binding::parse(&binding.to_string()), Some(*binding));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+
+const DIRECTORY_PLACEHOLDER: &str = "{dir}";
+
+#[cfg(target_os = "macos")]
+pub const DEFAULT_TERMINAL: &str = "open -a Terminal {dir}";
+
+#[cfg(windows)]
+pub const DEFAULT_TERMINAL: &str = "cmd /C start cmd";
+
+#[cfg(not(any(target_os = "macos", windows)))]
+pub const DEFAULT_TERMINAL: &str = "x-terminal-emulator";
+
+pub fn open_terminal(template: &str, directory: &Path) -> Result<(), String> {
+    let directory_string = directory.to_string_lossy();
+
+    let mut parts = template
+        .split_whitespace()
+        .map(|part| part.replace(DIRECTORY_PLACEHOLDER, &directory_string));
+
+    let program = parts
+        .next()
+        .ok_or_else(|| "No terminal command configured".to_string())?;
+
+    let mut child = Command::new(&program)
+        .args(parts)
+        .current_dir(directory)
+        .spawn()
+        .map_err(|error| format!("Cannot start {:?}: {}", program, error))?;
+
+    thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+pub fn open_window(directory: &Path) -> Result<(), String> {
+    let program =
+        env::current_exe().map_err(|error| format!("Cannot find executable: {}", error))?;
+
+    let mut child = Command::new(&program)
+        .current_dir(directory)
+        .spawn()
+        .map_err(|error| format!("Cannot start {:?}: {}", program, error))?;
+
+    thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+pub fn open_external(path: &Path) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+
+    let mut child = command
+        .arg(path)
+        .spawn()
+        .map_err(|error| format!("Cannot open {:?}: {}", path, error))?;
+
+    thread::spawn(move || child.wait());
+
+    Ok(())
+}
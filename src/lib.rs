@@ -0,0 +1,57 @@
+pub mod bidi;
+pub mod breadcrumb;
+pub mod checksum;
+pub mod column_resize;
+pub mod compare;
+pub mod config;
+pub mod confirm;
+pub mod custom_actions;
+pub mod drop_zone;
+pub mod exif;
+pub mod export;
+pub mod file_ops;
+pub mod file_type;
+pub mod font;
+pub mod format;
+pub mod fuzzy;
+pub mod git;
+pub mod help;
+pub mod history;
+pub mod i18n;
+pub mod index;
+pub mod instance;
+pub mod journal;
+pub mod keymap;
+pub mod launch;
+pub mod menu;
+pub mod nav_tree;
+pub mod navigation;
+#[cfg(feature = "office-preview")]
+pub mod office;
+pub mod operation_log;
+pub mod palette;
+pub mod pattern;
+pub mod plugin;
+pub mod probe;
+pub mod prompt;
+pub mod properties;
+pub mod remote;
+pub mod run;
+pub mod saved_searches;
+pub mod scan;
+pub mod search;
+pub mod smart_folder;
+pub mod storage;
+pub mod style;
+pub mod tabs;
+pub mod tags;
+pub mod tasks;
+pub mod toolbar;
+pub mod transfer;
+pub mod trash;
+pub mod tree_view;
+pub mod viewer;
+pub mod volumes;
+pub mod watcher;
+#[cfg(feature = "office-preview")]
+pub mod zip;
@@ -1,30 +1,166 @@
+use iced::button::{self, Button};
 use iced::executor;
 use iced::scrollable::{self, Scrollable};
-use iced::{Application, Clipboard, Command, Element, Length, Row, Settings, Text};
+use iced::{
+    keyboard, mouse, Align, Application, Clipboard, Column, Command, Container, Element, Length,
+    Row, Settings, Subscription, Text,
+};
+use iced_native::event::{self, Event};
 
+#[cfg(feature = "office-preview")]
+use navigation_tree::office;
+use navigation_tree::{
+    compare, config, confirm, custom_actions, export, file_ops, git, help, i18n, index, instance,
+    journal, keymap, launch, menu, nav_tree, navigation, operation_log, palette, plugin, prompt,
+    properties, remote, run, saved_searches, search, storage, style, tabs, tasks, toolbar,
+    transfer, viewer,
+};
+
+use std::cell::Cell;
+use std::collections::HashSet;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time;
+
+const VIEWER_SCROLL_STEP: f32 = 40.0;
+const INDEX_SAVE_DELAY: time::Duration = time::Duration::from_secs(2);
 
 fn main() {
     let current_dir = env::current_dir().expect("Getting current directory");
+    let argument = env::args_os().nth(1);
+    let target = argument
+        .as_ref()
+        .map_or_else(|| current_dir.clone(), |path| current_dir.join(path));
+
+    if argument.is_some() && instance::forward(&target) {
+        return;
+    }
+
+    plugins().install();
 
     let settings = Settings {
-        flags: current_dir,
+        flags: target,
         ..Default::default()
     };
 
     App::run(settings).expect("Running Iced");
 }
 
+fn plugins() -> plugin::Registry {
+    let registry = plugin::Registry::default();
+
+    #[cfg(feature = "office-preview")]
+    let registry = registry.previewer(office::Office);
+
+    custom_actions::load()
+        .into_iter()
+        .fold(registry, plugin::Registry::action)
+}
+
 #[derive(Debug, Clone)]
 enum Message {
-    NavTree(nav_tree::Message),
+    NavTree(navigation::Message),
+    Config(config::Message),
+    ToggleSettings,
+    ToggleHelp,
+    Palette(palette::Message),
+    Journal(journal::Message),
+    OperationLog(operation_log::Message),
+    PluginAction(usize),
+    Prompt(prompt::Message),
+    Confirm(confirm::Message),
+    Export(export::Message),
+    Properties(properties::Message),
+    Compare(compare::Message),
+    Transfer(tasks::Id, transfer::Message),
+    Run(tasks::Id, run::Message),
+    Search(search::Message),
+    SavedSearches(saved_searches::Message),
+    IndexLoaded(index::Index),
+    Indexed(index::Event),
+    IndexSaveDue(time::Instant),
+    IndexSaved(Result<(), String>),
+    ConfigSaved(Result<(), String>),
+    Viewer(viewer::Message),
+    SplitViewer(viewer::Message),
+    Tabs(tabs::Message),
+    DiffRead(PathBuf, Result<String, String>),
+    BlameRead(PathBuf, Result<Vec<git::BlameLine>, String>),
+    Tasks(tasks::Message),
+    Action(keymap::Action),
+    Menu(menu::Message),
+    Keyboard(keyboard::Event, event::Status),
+    Remote(remote::Incoming),
+    RemoteChecked(remote::Incoming, navigation::PathStatus),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    PathBar,
+    Tree,
+    Viewer,
+}
+
+impl Focus {
+    const ALL: [Focus; 3] = [Focus::PathBar, Focus::Tree, Focus::Viewer];
+
+    fn cycle(self, forward: bool) -> Self {
+        let index = Focus::ALL
+            .iter()
+            .position(|focus| *focus == self)
+            .unwrap_or(0);
+        let offset = if forward { 1 } else { Focus::ALL.len() - 1 };
+
+        Focus::ALL[(index + offset) % Focus::ALL.len()]
+    }
 }
 
 struct App {
     nav_tree: nav_tree::State,
-    read_file: Option<(PathBuf, String)>,
+    config: config::State,
+    show_settings: bool,
+    show_help: bool,
+    palette: Option<palette::State>,
+    journal: journal::State,
+    show_history: bool,
+    operation_log: operation_log::State,
+    show_log: bool,
+    prompt: Option<prompt::State>,
+    confirm: Option<confirm::State>,
+    export: Option<export::State>,
+    properties: Option<properties::State>,
+    compare: Option<compare::State>,
+    transfers: Vec<transfer::State>,
+    conflict_policy: Option<transfer::Resolution>,
+    run: Option<run::State>,
+    search: Option<search::State>,
+    saved_searches: saved_searches::State,
+    index: index::Index,
+    indexing: Option<(tasks::Handle, index::Index)>,
+    index_save_due: Option<time::Instant>,
+    index_saving: bool,
+    jump_to: Option<(PathBuf, usize)>,
+    tasks: tasks::State,
+    show_tasks: bool,
+    status: Option<String>,
+    viewer: Option<viewer::Document>,
+    tabs: tabs::State,
+    show_blame: bool,
+    split: Option<viewer::Document>,
+    open_in_split: Option<PathBuf>,
     scrollable: scrollable::State,
+    split_scrollable: scrollable::State,
+    viewer_extent: Cell<viewer::Extent>,
+    split_extent: Cell<viewer::Extent>,
+    menu: menu::State,
+    toolbar: toolbar::State,
+    action_bar: plugin::ActionBar,
+    focus: Focus,
+    modifiers: keyboard::Modifiers,
+    settings_button: button::State,
+    help_button: button::State,
+    instance: Option<Arc<instance::Listener>>,
 }
 
 impl Application for App {
@@ -32,284 +168,1574 @@ impl Application for App {
     type Flags = PathBuf;
     type Executor = executor::Default;
 
-    fn new(current_dir: Self::Flags) -> (Self, Command<Self::Message>) {
-        let nav_tree = nav_tree::State::Loading(current_dir.clone());
+    fn new(target: Self::Flags) -> (Self, Command<Self::Message>) {
+        let tasks = tasks::Tasks::default();
+        let mut nav_tree = nav_tree::State::new(target.clone(), tasks.clone());
+        nav_tree.set_focus(Some(nav_tree::Focus::Tree));
 
-        let command = Command::perform(nav_tree.read_directory(current_dir), Message::NavTree);
+        let operation_log = operation_log::State::new(target.clone());
 
-        (
-            Self {
-                nav_tree,
-                read_file: Default::default(),
-                scrollable: Default::default(),
-            },
-            command,
-        )
+        let mut commands = vec![
+            nav_tree::list_volumes().map(Message::NavTree),
+            Command::perform(index::load(), Message::IndexLoaded),
+        ];
+
+        let mut app = Self {
+            nav_tree,
+            config: config::State::new(config::Config::load()),
+            show_settings: false,
+            show_help: false,
+            palette: None,
+            journal: Default::default(),
+            show_history: false,
+            operation_log,
+            show_log: false,
+            prompt: None,
+            confirm: None,
+            export: None,
+            properties: None,
+            compare: None,
+            transfers: vec![],
+            conflict_policy: None,
+            run: None,
+            search: None,
+            saved_searches: saved_searches::State::load(),
+            index: index::Index::default(),
+            indexing: None,
+            index_save_due: None,
+            index_saving: false,
+            jump_to: None,
+            tasks: tasks::State::new(tasks),
+            show_tasks: false,
+            status: None,
+            viewer: None,
+            tabs: Default::default(),
+            show_blame: false,
+            split: None,
+            open_in_split: None,
+            scrollable: Default::default(),
+            split_scrollable: Default::default(),
+            viewer_extent: Default::default(),
+            split_extent: Default::default(),
+            menu: Default::default(),
+            toolbar: Default::default(),
+            action_bar: plugin::ActionBar::default(),
+            focus: Focus::Tree,
+            modifiers: keyboard::Modifiers::default(),
+            settings_button: Default::default(),
+            help_button: Default::default(),
+            instance: instance::listen(),
+        };
+
+        app.apply_config();
+
+        commands.push(app.open_path(target));
+
+        (app, Command::batch(commands))
     }
 
     fn title(&self) -> String {
-        "Navigation Tree Example".into()
+        let name = |path: &Path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string())
+        };
+
+        let directory = self.nav_tree.tree().current_dir();
+        let mut title = format!(
+            "{} ({})",
+            name(directory),
+            self.nav_tree.tree().entries().len()
+        );
+
+        if let Some(selected) = self.nav_tree.tree().selected() {
+            title = format!("{} - {}", name(selected), title);
+        }
+
+        format!("{} - Navigation Tree Example", title)
     }
 
     fn update(
         &mut self,
         message: Self::Message,
-        _clipboard: &mut Clipboard,
+        clipboard: &mut Clipboard,
     ) -> Command<Self::Message> {
         match message {
-            Message::NavTree(message) => {
-                let (command, event) = self.nav_tree.update(message);
+            Message::NavTree(navigation::Message::Watched(change)) if self.index.apply(&change) => {
+                self.schedule_index_save();
+
+                self.update_nav_tree(navigation::Message::Watched(change))
+            }
+            Message::NavTree(message) => self.update_nav_tree(message),
+            Message::Config(message) => {
+                self.config.update(message);
+                self.apply_config();
+
+                Command::perform(
+                    storage::save(config::CONFIG, self.config.config.lines()),
+                    Message::ConfigSaved,
+                )
+            }
+            Message::ConfigSaved(result) => {
+                if let Err(error) = result {
+                    self.status = Some(error);
+                }
+
+                Command::none()
+            }
+            Message::ToggleSettings => self.perform(keymap::Action::ToggleSettings),
+            Message::ToggleHelp => self.perform(keymap::Action::ToggleHelp),
+            Message::Palette(message) => {
+                let choice = self
+                    .palette
+                    .as_mut()
+                    .and_then(|palette| palette.update(message));
+
+                match choice {
+                    Some(palette::Choice::Action(action)) => {
+                        self.palette = None;
+
+                        self.perform(action)
+                    }
+                    Some(palette::Choice::File(path)) => {
+                        self.palette = None;
+
+                        self.update_nav_tree(navigation::Message::NavigateTo(path))
+                    }
+                    None => Command::none(),
+                }
+            }
+            Message::Journal(message) => self.update_journal(message),
+            Message::OperationLog(message) => self
+                .operation_log
+                .update(message)
+                .map(Message::OperationLog),
+            Message::Prompt(message) => {
+                let event = self
+                    .prompt
+                    .as_mut()
+                    .and_then(|prompt| prompt.update(message));
+
+                match event {
+                    Some(prompt::Event::Submitted(request)) => {
+                        self.prompt = None;
+
+                        match request {
+                            file_ops::Request::Copy { from, to } => {
+                                self.start_transfer(transfer::Kind::Copy, from, to)
+                            }
+                            file_ops::Request::Move { from, to } => {
+                                self.start_transfer(transfer::Kind::Move, from, to)
+                            }
+                            request => self.update_journal(journal::Message::Apply(request)),
+                        }
+                    }
+                    Some(prompt::Event::Cancelled) => {
+                        self.prompt = None;
+
+                        Command::none()
+                    }
+                    None => Command::none(),
+                }
+            }
+            Message::Confirm(message) => {
+                let (command, event) = match self.confirm.as_mut() {
+                    Some(confirm) => confirm.update(message),
+                    None => (Command::none(), None),
+                };
+
+                let command = command.map(Message::Confirm);
+
+                match event {
+                    Some(confirm::Event::Deleted(path)) => {
+                        self.confirm = None;
+                        self.operation_log.record_deletion(&Ok(path));
+
+                        Command::batch(vec![
+                            command,
+                            self.update_nav_tree(navigation::Message::RefreshDirectory),
+                        ])
+                    }
+                    Some(confirm::Event::Discarded(path)) => {
+                        self.confirm = None;
+
+                        if let Some(index) = self.tabs.position(&path) {
+                            self.tabs
+                                .close(index, &mut self.viewer, &mut self.scrollable);
+                        }
+
+                        command
+                    }
+                    Some(confirm::Event::Failed(error)) => {
+                        self.confirm = None;
+                        self.operation_log.record_deletion(&Err(error.clone()));
+                        self.status = Some(error);
+
+                        command
+                    }
+                    Some(confirm::Event::Cancelled) => {
+                        self.confirm = None;
+
+                        command
+                    }
+                    None => command,
+                }
+            }
+            Message::Export(message) => {
+                let (command, event) = match self.export.as_mut() {
+                    Some(export) => export.update(message),
+                    None => (Command::none(), None),
+                };
+
+                match event {
+                    Some(export::Event::Exported(path, count)) => {
+                        self.export = None;
+                        self.operation_log.record_export(&Ok((path, count)));
+                    }
+                    Some(export::Event::Failed(error)) => {
+                        self.operation_log.record_export(&Err(error));
+                    }
+                    Some(export::Event::Cancelled) => {
+                        self.export = None;
+                    }
+                    None => {}
+                }
+
+                command.map(Message::Export)
+            }
+            Message::Properties(properties::Message::Close) => {
+                self.properties = None;
+
+                Command::none()
+            }
+            Message::Properties(message) => match self.properties.as_mut() {
+                Some(properties) => properties.update(message).map(Message::Properties),
+                None => Command::none(),
+            },
+            Message::Compare(message) => {
+                let (command, event) = match self.compare.as_mut() {
+                    Some(compare) => compare.update(message),
+                    None => (Command::none(), None),
+                };
+
+                let command = command.map(Message::Compare);
+
+                match event {
+                    Some(compare::Event::Sync(request)) => Command::batch(vec![
+                        command,
+                        self.update_journal(journal::Message::Apply(request)),
+                    ]),
+                    Some(compare::Event::Closed) => {
+                        self.compare = None;
+
+                        command
+                    }
+                    None => command,
+                }
+            }
+            Message::Transfer(id, message) => {
+                let index = self
+                    .transfers
+                    .iter()
+                    .position(|transfer| transfer.id() == id);
+
+                if let (Some(index), transfer::Message::Resolve(resolution)) = (index, &message) {
+                    if self.transfers[index].applies_to_all() {
+                        self.conflict_policy = Some(*resolution);
+
+                        for transfer in self.transfers.iter_mut() {
+                            transfer.resolve(*resolution);
+                        }
+                    }
+                }
+
+                let result = index.and_then(|index| self.transfers[index].update(message));
+
+                match (index, result) {
+                    (Some(index), Some(result)) => {
+                        self.transfers.remove(index);
+
+                        if self.transfers.is_empty() {
+                            self.conflict_policy = None;
+                        }
+
+                        self.update_journal(journal::Message::Applied(result))
+                    }
+                    _ => Command::none(),
+                }
+            }
+            Message::Run(_, run::Message::Close) => {
+                self.run = None;
+
+                Command::none()
+            }
+            Message::Run(id, message) => {
+                if let Some(run) = self.run.as_mut().filter(|run| run.id() == id) {
+                    if let run::Message::Exited(result) = &message {
+                        self.operation_log.record_command(run.label(), result);
+                    }
+
+                    run.update(message);
+                }
+
+                Command::none()
+            }
+            Message::PluginAction(index) => {
+                let selected = self.nav_tree.tree().selected();
+
+                if let (Some(action), Some(path)) = (plugin::action(index), selected) {
+                    match action.command(path) {
+                        Ok((program, args)) => {
+                            let directory = self.nav_tree.tree().current_dir().to_path_buf();
+
+                            self.run = Some(run::State::command(
+                                action.name().to_string(),
+                                program,
+                                args,
+                                directory,
+                                self.tasks.tasks(),
+                            ));
+                        }
+                        Err(error) => self.status = Some(error),
+                    }
+                }
+
+                Command::none()
+            }
+            Message::Search(message) => {
+                let (command, event) = match self.search.as_mut() {
+                    Some(search) => search.update(message),
+                    None => (Command::none(), None),
+                };
+
+                let command = command.map(Message::Search);
+
+                match event {
+                    Some(search::Event::Open(path, line)) => {
+                        Command::batch(vec![command, self.jump_to(path, line)])
+                    }
+                    Some(search::Event::Replace(request)) => Command::batch(vec![
+                        command,
+                        self.update_journal(journal::Message::Apply(request)),
+                    ]),
+                    Some(search::Event::Save(saved)) => Command::batch(vec![
+                        command,
+                        self.saved_searches.add(saved).map(Message::SavedSearches),
+                    ]),
+                    Some(search::Event::Failed(error)) => {
+                        self.status = Some(error);
+
+                        command
+                    }
+                    Some(search::Event::Closed) => {
+                        self.search = None;
+
+                        command
+                    }
+                    None => command,
+                }
+            }
+            Message::SavedSearches(message) => {
+                let (command, event) = self.saved_searches.update(message);
+                let command = command.map(Message::SavedSearches);
+
+                match event {
+                    Some(saved_searches::Event::Run(saved)) => {
+                        let tasks = self.tasks.tasks();
+                        let index = self.index.clone();
+                        let search = self.search.get_or_insert_with(|| {
+                            search::State::new(
+                                search::Scope::Directory,
+                                saved.root.clone(),
+                                None,
+                                tasks,
+                                index,
+                            )
+                        });
+
+                        let (run, _) = search.run(saved);
+
+                        Command::batch(vec![command, run.map(Message::Search)])
+                    }
+                    Some(saved_searches::Event::Failed(error)) => {
+                        self.status = Some(error);
+
+                        command
+                    }
+                    None => command,
+                }
+            }
+            Message::IndexLoaded(index) => {
+                if !index.is_complete() {
+                    self.start_indexing(index.clone());
+                }
+
+                self.index = index;
+
+                Command::none()
+            }
+            Message::Indexed(index::Event::Checkpoint(index))
+            | Message::Indexed(index::Event::Built(index)) => {
+                self.index = index;
+                self.schedule_index_save();
+
+                Command::none()
+            }
+            Message::IndexSaveDue(now) => {
+                if self.index_saving || self.index_save_due.is_none_or(|due| due > now) {
+                    return Command::none();
+                }
+
+                self.index_save_due = None;
+                self.index_saving = true;
+
+                Command::perform(index::save(self.index.clone()), Message::IndexSaved)
+            }
+            Message::Indexed(index::Event::Finished(id)) => {
+                if self.indexing.as_ref().map(|(task, _)| task.id()) == Some(id) {
+                    self.indexing = None;
+                }
+
+                Command::none()
+            }
+            Message::IndexSaved(result) => {
+                self.index_saving = false;
+
+                if let Err(error) = result {
+                    self.status = Some(error);
+                }
+
+                Command::none()
+            }
+            Message::Viewer(message) => match self.viewer.as_mut() {
+                Some(document) => {
+                    if let (viewer::Message::CopyContents, Some(text)) = (&message, document.text())
+                    {
+                        clipboard.write(text.to_string());
+                    }
+
+                    if let viewer::Message::BytesSaved(result) = &message {
+                        self.operation_log.record_save(&document.path, result);
+                    }
+
+                    let command = document.update(message).map(Message::Viewer);
+
+                    if document.is_auto_scrolling() {
+                        self.viewer_extent
+                            .get()
+                            .scroll_to_bottom(&mut self.scrollable, document);
+                    }
+
+                    command
+                }
+                None => Command::none(),
+            },
+            Message::SplitViewer(message) => match self.split.as_mut() {
+                Some(document) => {
+                    if let (viewer::Message::CopyContents, Some(text)) = (&message, document.text())
+                    {
+                        clipboard.write(text.to_string());
+                    }
+
+                    if let viewer::Message::BytesSaved(result) = &message {
+                        self.operation_log.record_save(&document.path, result);
+                    }
+
+                    let command = document.update(message).map(Message::SplitViewer);
+
+                    if document.is_auto_scrolling() {
+                        self.split_extent
+                            .get()
+                            .scroll_to_bottom(&mut self.split_scrollable, document);
+                    }
+
+                    command
+                }
+                None => Command::none(),
+            },
+            Message::Remote(incoming) => match incoming.request.clone() {
+                remote::Request::Navigate(path) | remote::Request::Open(path) => {
+                    Command::perform(nav_tree::check_path(path), move |status| {
+                        Message::RemoteChecked(incoming.clone(), status)
+                    })
+                }
+                remote::Request::Selection => {
+                    let selected = self.nav_tree.tree().selected().map(Path::to_path_buf);
+
+                    incoming.respond(remote::Response::Selection(selected));
+
+                    Command::none()
+                }
+            },
+            Message::RemoteChecked(incoming, status) => {
+                let (command, response) = match incoming.request.clone() {
+                    remote::Request::Navigate(path) | remote::Request::Open(path)
+                        if status == navigation::PathStatus::Missing =>
+                    {
+                        let error = format!("{:?} does not exist", path);
+
+                        (Command::none(), remote::Response::Failed(error))
+                    }
+                    remote::Request::Navigate(path) => {
+                        (self.reveal_path(path), remote::Response::Done)
+                    }
+                    remote::Request::Open(path) => (self.open_path(path), remote::Response::Done),
+                    remote::Request::Selection => (Command::none(), remote::Response::Done),
+                };
+
+                incoming.respond(response);
+
+                command
+            }
+            Message::Tabs(tabs::Message::Select(index)) => {
+                self.tabs
+                    .select(index, &mut self.viewer, &mut self.scrollable);
+
+                Command::none()
+            }
+            Message::Tabs(tabs::Message::Close(index)) => {
+                self.close_tab(index);
+
+                Command::none()
+            }
+            Message::DiffRead(path, Ok(diff)) => {
+                self.open_document(viewer::Document::diff(path, diff));
+
+                Command::none()
+            }
+            Message::DiffRead(_, Err(error)) => {
+                self.status = Some(error);
+
+                Command::none()
+            }
+            Message::BlameRead(path, result) => {
+                match (self.viewer.as_mut(), result) {
+                    (Some(document), Ok(blame)) if document.path == path && self.show_blame => {
+                        document.blame = Some(blame);
+                    }
+                    (_, Err(error)) if self.show_blame => {
+                        self.status = Some(error);
+                    }
+                    _ => {}
+                }
+
+                Command::none()
+            }
+            Message::Tasks(message) => {
+                self.tasks.update(message);
+
+                Command::none()
+            }
+            Message::Action(action) => self.perform(action),
+            Message::Menu(message) => match self.menu.update(message) {
+                Some(action) => self.perform(action),
+                None => Command::none(),
+            },
+            Message::Keyboard(event, status) => {
+                if let keyboard::Event::ModifiersChanged(modifiers) = event {
+                    self.modifiers = modifiers;
+                }
+
+                let key_code = match event {
+                    keyboard::Event::KeyPressed { key_code, .. } => Some(key_code),
+                    _ => None,
+                };
+
+                if let Some(palette) = self.palette.as_mut() {
+                    match key_code {
+                        Some(keyboard::KeyCode::Up) => palette.select_previous(),
+                        Some(keyboard::KeyCode::Down) => palette.select_next(),
+                        Some(keyboard::KeyCode::Escape) => self.palette = None,
+                        _ => {}
+                    }
+
+                    return Command::none();
+                }
+
+                if self.show_help && key_code == Some(keyboard::KeyCode::Escape) {
+                    self.show_help = false;
+
+                    return Command::none();
+                }
+
+                if self.properties.is_some() && key_code == Some(keyboard::KeyCode::Escape) {
+                    self.properties = None;
+
+                    return Command::none();
+                }
+
+                if let Some(compare) = self.compare.as_mut() {
+                    if key_code == Some(keyboard::KeyCode::Escape) {
+                        let _ = compare.update(compare::Message::Close);
+
+                        self.compare = None;
+                    }
+
+                    return Command::none();
+                }
+
+                if self.prompt.is_some() {
+                    if key_code == Some(keyboard::KeyCode::Escape) {
+                        self.prompt = None;
+                    }
+
+                    return Command::none();
+                }
+
+                if self.export.is_some() {
+                    if key_code == Some(keyboard::KeyCode::Escape) {
+                        self.export = None;
+                    }
+
+                    return Command::none();
+                }
+
+                if self.search.as_ref().is_some_and(search::State::is_focused)
+                    && key_code == Some(keyboard::KeyCode::Escape)
+                {
+                    self.search = None;
+
+                    return Command::none();
+                }
+
+                if self
+                    .search
+                    .as_ref()
+                    .is_some_and(search::State::is_query_focused)
+                {
+                    let message = match key_code {
+                        Some(keyboard::KeyCode::Up) => Some(search::Message::RecallOlder),
+                        Some(keyboard::KeyCode::Down) => Some(search::Message::RecallNewer),
+                        _ => None,
+                    };
+
+                    if let Some(message) = message {
+                        return self.update(Message::Search(message), clipboard);
+                    }
+                }
+
+                if self.nav_tree.tree().path_input().is_some() {
+                    let message = match key_code {
+                        Some(keyboard::KeyCode::Escape) => {
+                            Some(navigation::Message::CancelPathInput)
+                        }
+                        Some(
+                            keyboard::KeyCode::Left
+                            | keyboard::KeyCode::Right
+                            | keyboard::KeyCode::Home
+                            | keyboard::KeyCode::End,
+                        ) if self.nav_tree.tree().is_path_input_selected() => {
+                            Some(navigation::Message::DeselectPathInput)
+                        }
+                        Some(keyboard::KeyCode::Down) => Some(navigation::Message::NextCompletion),
+                        Some(keyboard::KeyCode::Up) => {
+                            Some(navigation::Message::PreviousCompletion)
+                        }
+                        Some(keyboard::KeyCode::Tab) => Some(navigation::Message::AcceptCompletion),
+                        _ => None,
+                    };
+
+                    if let Some(message) = message {
+                        return self.update_nav_tree(message);
+                    }
+                } else if self.focus == Focus::PathBar
+                    && key_code == Some(keyboard::KeyCode::Escape)
+                {
+                    self.set_focus(Focus::Tree);
+
+                    return Command::none();
+                }
 
-                if let Some(event) = event {
-                    match event {
-                        nav_tree::Event::FileRead(path, content) => {
-                            self.read_file = Some((path, content));
+                if status == event::Status::Captured {
+                    return Command::none();
+                }
+
+                let typed = match event {
+                    keyboard::Event::CharacterReceived(character) if !character.is_control() => {
+                        Some(character)
+                    }
+                    _ => None,
+                };
+
+                if self
+                    .viewer
+                    .as_ref()
+                    .is_some_and(viewer::Document::is_editing)
+                {
+                    let edit = match (key_code, typed) {
+                        (Some(keyboard::KeyCode::Left), _) => Some(viewer::Edit::Left),
+                        (Some(keyboard::KeyCode::Right), _) => Some(viewer::Edit::Right),
+                        (Some(keyboard::KeyCode::Up), _) => Some(viewer::Edit::Up),
+                        (Some(keyboard::KeyCode::Down), _) => Some(viewer::Edit::Down),
+                        (Some(keyboard::KeyCode::Home), _) => Some(viewer::Edit::Home),
+                        (Some(keyboard::KeyCode::End), _) => Some(viewer::Edit::End),
+                        (Some(keyboard::KeyCode::Backspace), _) => Some(viewer::Edit::Backspace),
+                        (Some(keyboard::KeyCode::Insert), _) => Some(viewer::Edit::ToggleOverwrite),
+                        (Some(keyboard::KeyCode::Escape), _) => Some(viewer::Edit::Stop),
+                        (_, Some(character)) => character
+                            .to_digit(16)
+                            .map(|digit| viewer::Edit::Digit(digit as u8)),
+                        _ => None,
+                    };
+
+                    if let Some(edit) = edit {
+                        return self
+                            .update(Message::Viewer(viewer::Message::Edit(edit)), clipboard);
+                    }
+
+                    if typed.is_some() {
+                        return Command::none();
+                    }
+                }
+
+                if self.nav_tree.tree().has_text_filter() {
+                    match (key_code, typed) {
+                        (Some(keyboard::KeyCode::Escape), _) => {
+                            return self.nav_tree.clear_text_filter().map(Message::NavTree);
+                        }
+                        (Some(keyboard::KeyCode::Backspace), _) => {
+                            self.nav_tree.delete_filter_character();
+
+                            return Command::none();
+                        }
+                        (_, Some(character)) => {
+                            self.nav_tree.type_ahead(character);
+
+                            return Command::none();
                         }
+                        _ => {}
                     }
                 }
 
-                command.map(Message::NavTree)
+                match self.config.config.keymap.action(event) {
+                    Some(action) => self.perform(action),
+                    None => {
+                        if let Some(character) = typed.filter(|_| !self.show_help) {
+                            self.nav_tree.type_ahead(character);
+                        }
+
+                        Command::none()
+                    }
+                }
             }
         }
     }
 
-    fn subscription(&self) -> iced::Subscription<Self::Message> {
-        self.nav_tree.refresh_directory().map(Message::NavTree)
+    fn subscription(&self) -> Subscription<Self::Message> {
+        let keyboard = iced_native::subscription::events_with(|event, status| match event {
+            Event::Keyboard(event) => Some(Message::Keyboard(event, status)),
+            Event::Mouse(mouse::Event::ButtonPressed(button)) => history_button(button)
+                .filter(|_| status == event::Status::Ignored)
+                .map(Message::NavTree),
+            _ => None,
+        });
+
+        let mut subscriptions = vec![
+            self.nav_tree.refresh_directory().map(Message::NavTree),
+            self.nav_tree.scans().map(Message::NavTree),
+            self.nav_tree.animation_frames().map(Message::NavTree),
+            self.nav_tree.git_status_refresh().map(Message::NavTree),
+            self.nav_tree.watch_volumes().map(Message::NavTree),
+            keyboard,
+        ];
+
+        if let Some(listener) = self.instance.as_ref() {
+            subscriptions.push(instance::requests(listener.clone()).map(Message::Remote));
+        }
+
+        for transfer in self.transfers.iter() {
+            let id = transfer.id();
+
+            subscriptions.push(
+                transfer
+                    .subscription()
+                    .with(id)
+                    .map(|(id, message)| Message::Transfer(id, message)),
+            );
+        }
+
+        if let Some(document) = self.viewer.as_ref() {
+            subscriptions.push(document.subscription().map(Message::Viewer));
+        }
+
+        if let Some(document) = self.split.as_ref() {
+            subscriptions.push(
+                document
+                    .subscription()
+                    .with(())
+                    .map(|((), message)| Message::SplitViewer(message)),
+            );
+        }
+
+        if let Some(search) = self.search.as_ref() {
+            subscriptions.push(search.subscription().map(Message::Search));
+        }
+
+        if let Some((task, index)) = self.indexing.as_ref() {
+            subscriptions.push(index::build(task.clone(), index.clone()).map(Message::Indexed));
+        }
+
+        if self.index_save_due.is_some() {
+            subscriptions.push(
+                iced_futures::time::every(time::Duration::from_millis(250))
+                    .map(Message::IndexSaveDue),
+            );
+        }
+
+        if let Some(run) = self.run.as_ref() {
+            let id = run.id();
+
+            subscriptions.push(
+                run.subscription()
+                    .with(id)
+                    .map(|(id, message)| Message::Run(id, message)),
+            );
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     fn view(&mut self) -> Element<'_, Self::Message> {
-        let nav_tree = nav_tree::view(&mut self.nav_tree).map(Message::NavTree);
+        if let Some(palette) = self.palette.as_mut() {
+            return palette::view(palette, &self.config.config.keymap).map(Message::Palette);
+        }
 
-        let read_file = if let Some((path, content)) = self.read_file.as_ref() {
-            format!("File: {:?}\n\n{}", path, content)
-        } else {
-            "Click a file to view it's content".into()
-        };
+        if self.show_help {
+            return help::view(
+                &self.config.config.keymap,
+                &mut self.help_button,
+                Message::ToggleHelp,
+            );
+        }
 
-        let scollable =
-            Scrollable::new(&mut self.scrollable).push(Text::new(read_file).width(Length::Fill));
+        if let Some(transfer) = self
+            .transfers
+            .iter_mut()
+            .find(|transfer| transfer.is_visible())
+        {
+            let id = transfer.id();
 
-        Row::new().push(nav_tree).push(scollable).into()
-    }
-}
+            return transfer::view(transfer).map(move |message| Message::Transfer(id, message));
+        }
 
-mod nav_tree {
-    use iced::button::{self, Button};
-    use iced::futures::FutureExt;
-    use iced::scrollable::{self, Scrollable};
-    use iced::{Column, Command, Container, Element, Length, Text};
-
-    use std::fs;
-    use std::future::Future;
-    use std::path::PathBuf;
-    use std::time;
-
-    #[derive(Debug, Clone)]
-    pub enum Message {
-        ChangeDirectory(PathBuf),
-        DirectoryRead(Option<(PathBuf, Vec<Entry>)>),
-        ReadFile(PathBuf),
-        FileRead(Option<(PathBuf, String)>),
-        RefreshDirectory,
-    }
+        if let Some(properties) = self.properties.as_mut() {
+            return properties::view(properties).map(Message::Properties);
+        }
 
-    #[derive(Debug, Clone)]
-    pub enum Event {
-        FileRead(PathBuf, String),
-    }
+        if let Some(compare) = self.compare.as_mut() {
+            return compare::view(compare).map(Message::Compare);
+        }
+
+        let selected = self.nav_tree.tree().selected().map(Path::to_path_buf);
+        let has_selection = selected.is_some();
+        let has_executable = self.nav_tree.tree().selected_executable().is_some();
+        let has_modified = self.nav_tree.tree().selected_modified().is_some();
+        let can_undo = self.journal.can_undo();
+        let can_redo = self.journal.can_redo();
 
-    pub fn view(state: &mut State) -> Element<Message> {
-        let content: Element<_> = match state {
-            State::Loading(directory) => {
-                let text = Text::new(format!("Loading {:?}...", directory));
+        let is_enabled = |action| match action {
+            keymap::Action::Rename
+            | keymap::Action::Copy
+            | keymap::Action::Move
+            | keymap::Action::Trash
+            | keymap::Action::DeletePermanently
+            | keymap::Action::ShowProperties => has_selection,
+            keymap::Action::Run => has_executable,
+            keymap::Action::ViewDiff => has_modified,
+            keymap::Action::Undo => can_undo,
+            keymap::Action::Redo => can_redo,
+            _ => true,
+        };
+
+        let menu = menu::view(&mut self.menu, &self.config.config.keymap, is_enabled);
+
+        let open_paths: HashSet<&Path> = self.tabs.paths().collect();
+
+        let nav_tree = nav_tree::view(&mut self.nav_tree, |row| {
+            let label = nav_tree::default_row(row);
 
-                Container::new(text).center_x().center_y().into()
+            if open_paths.contains(row.entry.path()) {
+                Row::new()
+                    .spacing(5)
+                    .align_items(Align::Center)
+                    .push(label)
+                    .push(Text::new("•").color(style::GUTTER))
+                    .into()
+            } else {
+                label
             }
-            State::Loaded {
-                directory,
-                entries,
-                entry_buttons: buttons,
-                up_button,
-                scrollable,
-            } => {
-                let mut scrollable = Scrollable::new(scrollable);
+        })
+        .map(Message::NavTree);
+
+        let nav_tree: Element<_> = if self.saved_searches.is_empty() {
+            nav_tree
+        } else {
+            Column::new()
+                .spacing(10)
+                .push(nav_tree)
+                .push(saved_searches::view(&mut self.saved_searches).map(Message::SavedSearches))
+                .into()
+        };
+
+        let settings_label = if self.show_settings {
+            "Close settings"
+        } else {
+            "Settings"
+        };
 
-                if let Some(parent) = directory.parent() {
-                    let content = Text::new("..");
+        let settings_button = Button::new(
+            &mut self.settings_button,
+            Text::new(i18n::tr(settings_label)),
+        )
+        .on_press(Message::ToggleSettings);
 
-                    let button = Button::new(up_button, content)
-                        .on_press(Message::ChangeDirectory(parent.to_path_buf()));
+        let content: Element<_> = if self.show_settings {
+            config::view(&mut self.config).map(Message::Config)
+        } else if self.show_history {
+            journal::view(&mut self.journal).map(Message::Journal)
+        } else if self.show_log {
+            operation_log::view(&mut self.operation_log).map(Message::OperationLog)
+        } else if self.show_tasks {
+            tasks::view(&mut self.tasks).map(Message::Tasks)
+        } else {
+            let config = &self.config.config;
 
-                    scrollable = scrollable.push(button);
-                };
+            let read_file = if let Some(document) = self.viewer.as_mut() {
+                let options = viewer_options(config, &document.path);
 
-                for (entry, button) in entries.iter_mut().zip(buttons.iter_mut()) {
-                    let name = entry.name();
-                    let message = entry.message();
+                viewer::view(document, options).map(Message::Viewer)
+            } else {
+                Text::new(i18n::tr("Click a file to view it's content"))
+                    .width(Length::Fill)
+                    .into()
+            };
 
-                    let content = Text::new(name);
+            let read_file = viewer::measured(
+                &self.viewer_extent,
+                Scrollable::new(&mut self.scrollable)
+                    .width(Length::Fill)
+                    .push::<Element<_>>(read_file),
+            );
 
-                    let button = Button::new(button, content).on_press(message);
+            let panes: Element<_> = match self.split.as_mut() {
+                Some(document) => {
+                    let options = viewer_options(config, &document.path);
 
-                    scrollable = scrollable.push(button);
+                    let split = viewer::measured(
+                        &self.split_extent,
+                        Scrollable::new(&mut self.split_scrollable)
+                            .width(Length::Fill)
+                            .push(viewer::view(document, options).map(Message::SplitViewer)),
+                    );
+
+                    Row::new().spacing(20).push(read_file).push(split).into()
                 }
+                None => read_file,
+            };
 
-                let header = Text::new(format!("Entries for {:?}", directory));
+            let panes = Container::new(panes)
+                .padding(2)
+                .style(style::FocusRing(self.focus == Focus::Viewer));
 
+            if self.tabs.is_empty() {
+                panes.into()
+            } else {
                 Column::new()
                     .spacing(10)
-                    .push(header)
-                    .push(scrollable)
+                    .push(tabs::view(&mut self.tabs).map(Message::Tabs))
+                    .push(panes)
                     .into()
             }
         };
 
-        Container::new(content).width(Length::Units(300)).into()
-    }
+        let help_button = style::labelled(
+            Button::new(&mut self.help_button, Text::new("?")).on_press(Message::ToggleHelp),
+            keymap::Action::ToggleHelp.description(),
+        );
+
+        let file_toolbar = toolbar::view(&mut self.toolbar, is_enabled).map(Message::Action);
+
+        let mut toolbar: Vec<Element<_>> = vec![file_toolbar];
+
+        if !self.action_bar.is_empty() {
+            toolbar.push(
+                plugin::view(&mut self.action_bar, selected.as_deref()).map(Message::PluginAction),
+            );
+        }
+
+        toolbar.push(settings_button.into());
+        toolbar.push(help_button);
+
+        let mut column = Column::new().spacing(10).push(mirrored(toolbar, 10));
+
+        if let Some(status) = self.status.as_ref() {
+            column = column.push(Text::new(status).color(style::ERROR));
+        }
+
+        if let Some(prompt) = self.prompt.as_mut() {
+            column = column.push(prompt::view(prompt).map(Message::Prompt));
+        }
+
+        if let Some(confirm) = self.confirm.as_mut() {
+            column = column.push(confirm::view(confirm).map(Message::Confirm));
+        }
+
+        if let Some(export) = self.export.as_mut() {
+            column = column.push(export::view(export).map(Message::Export));
+        }
+
+        column = if self.search.is_some() || self.run.is_some() {
+            column.push(Container::new(content).height(Length::Fill))
+        } else {
+            column.push(content)
+        };
+
+        if let Some(search) = self.search.as_mut() {
+            column = column.push(search::view(search).map(Message::Search));
+        }
 
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub enum Entry {
-        File { path: PathBuf, name: String },
-        Directory { path: PathBuf, name: String },
+        if let Some(run) = self.run.as_mut() {
+            let id = run.id();
+
+            column = column.push(run::view(run).map(move |message| Message::Run(id, message)));
+        }
+
+        Column::new()
+            .spacing(5)
+            .push(menu.map(Message::Menu))
+            .push(mirrored(vec![nav_tree, column.into()], 0))
+            .into()
     }
+}
 
-    impl Entry {
-        fn name(&self) -> String {
-            match self {
-                Entry::File { name, .. } => format!("F - {}", name),
-                Entry::Directory { name, .. } => format!("D - {}", name),
+impl App {
+    fn close_tab(&mut self, index: usize) {
+        match self.tabs.path(index) {
+            Some(path) if self.tabs.is_dirty(index, &self.viewer) => {
+                self.confirm = Some(confirm::State::discard(path.to_path_buf()));
             }
+            _ => self
+                .tabs
+                .close(index, &mut self.viewer, &mut self.scrollable),
         }
+    }
+
+    fn schedule_index_save(&mut self) {
+        self.index_save_due
+            .get_or_insert_with(|| time::Instant::now() + INDEX_SAVE_DELAY);
+    }
+
+    fn start_indexing(&mut self, index: index::Index) {
+        if let Some((task, _)) = self.indexing.take() {
+            task.cancel();
+        }
+
+        let root = index.root().unwrap_or_else(|| Path::new(""));
+        let task = self.tasks.tasks().start(format!("Indexing {:?}", root));
+
+        self.indexing = Some((task, index));
+    }
+
+    fn apply_config(&mut self) {
+        let config = &self.config.config;
+
+        i18n::set(config.language);
+
+        self.nav_tree.set_grouping(config.grouping);
+        self.nav_tree.set_expand_depth(config.expand_depth);
+        self.nav_tree
+            .set_max_file_size(config.max_file_size_mb * 1024 * 1024);
+        self.nav_tree.set_indent_guides(config.show_indent_guides);
+        self.nav_tree.set_reduce_motion(config.reduce_motion);
+        self.nav_tree.set_age_colors(config.age_colors);
+    }
+
+    fn set_focus(&mut self, focus: Focus) {
+        self.focus = focus;
+
+        self.nav_tree.set_focus(match focus {
+            Focus::PathBar => Some(nav_tree::Focus::PathBar),
+            Focus::Tree => Some(nav_tree::Focus::Tree),
+            Focus::Viewer => None,
+        });
+    }
+
+    fn update_nav_tree(&mut self, message: navigation::Message) -> Command<Message> {
+        let was_editing = self.nav_tree.tree().path_input().is_some();
+        let (command, event) = self.nav_tree.update(message);
+
+        if was_editing
+            && self.nav_tree.tree().path_input().is_none()
+            && self.focus == Focus::PathBar
+        {
+            self.set_focus(Focus::Tree);
+        }
+
+        if let Some(event) = event {
+            match event {
+                navigation::Event::FileRead(path, content)
+                    if self.open_in_split.as_ref() == Some(&path) =>
+                {
+                    self.open_in_split = None;
+                    self.split_scrollable = scrollable::State::new();
+                    self.split = Some(viewer::Document::file(path, content));
+                }
+                navigation::Event::FileTooLarge(path, size)
+                    if self.open_in_split.as_ref() == Some(&path) =>
+                {
+                    self.open_in_split = None;
+                    self.split_scrollable = scrollable::State::new();
+                    self.split = Some(viewer::Document::too_large(path, size));
+                }
+                navigation::Event::FileRead(path, content) => {
+                    let mut document = viewer::Document::file(path.clone(), content);
+
+                    let jump = self.jump_to.take().filter(|(jump, _)| *jump == path);
+
+                    document.highlight = jump.as_ref().map(|(_, line)| *line);
+
+                    self.open_document(document);
+
+                    if let Some(document) = self.viewer.as_ref().filter(|_| jump.is_some()) {
+                        self.viewer_extent
+                            .get()
+                            .scroll_to_highlight(&mut self.scrollable, document);
+                    }
+
+                    if self.show_blame {
+                        return Command::batch(vec![
+                            command.map(Message::NavTree),
+                            read_blame(path),
+                        ]);
+                    }
+                }
+                navigation::Event::FileTooLarge(path, size) => {
+                    self.open_document(viewer::Document::too_large(path, size));
+                }
+                navigation::Event::OpenInNewWindow(path) => {
+                    self.status = launch::open_window(&path).err();
+                }
+                navigation::Event::Dropped { from, into } => {
+                    let to = match from.file_name() {
+                        Some(name) => into.join(name),
+                        None => return command.map(Message::NavTree),
+                    };
 
-        fn message(&self) -> Message {
-            match self {
-                Entry::File { path, .. } => Message::ReadFile(path.clone()),
-                Entry::Directory { path, .. } => Message::ChangeDirectory(path.clone()),
+                    let kind = if self.modifiers.shift {
+                        transfer::Kind::Move
+                    } else {
+                        transfer::Kind::Copy
+                    };
+
+                    return Command::batch(vec![
+                        command.map(Message::NavTree),
+                        self.start_transfer(kind, from, to),
+                    ]);
+                }
+                navigation::Event::Restored(path, result) => {
+                    self.operation_log.record_restore(&path, &result);
+                    self.status = result.err();
+                }
+                navigation::Event::DeletedFromTrash(path, result) => {
+                    self.operation_log
+                        .record_deletion(&result.clone().map(|()| path));
+                    self.status = result.err();
+                }
+                navigation::Event::Failed(error) => {
+                    self.status = Some(error);
+                }
             }
         }
+
+        command.map(Message::NavTree)
     }
 
-    impl Ord for Entry {
-        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-            use std::cmp::Ordering;
-            use Entry::*;
+    fn open_path(&mut self, path: PathBuf) -> Command<Message> {
+        self.nav_tree.open_path(path).map(Message::NavTree)
+    }
+
+    fn reveal_path(&mut self, path: PathBuf) -> Command<Message> {
+        self.nav_tree.reveal_path(path).map(Message::NavTree)
+    }
 
-            match (self, other) {
-                (Directory { .. }, File { .. }) => Ordering::Less,
-                (File { .. }, Directory { .. }) => Ordering::Greater,
-                (File { name: a, .. }, File { name: b, .. })
-                | (Directory { name: a, .. }, Directory { name: b, .. }) => a.cmp(b),
+    fn open_document(&mut self, document: viewer::Document) {
+        self.tabs
+            .open(document, &mut self.viewer, &mut self.scrollable);
+    }
+
+    fn jump_to(&mut self, path: PathBuf, line: usize) -> Command<Message> {
+        self.nav_tree.reveal(path.clone());
+
+        match self.viewer.as_mut() {
+            Some(document) if document.path == path => {
+                document.highlight = Some(line);
+                self.viewer_extent
+                    .get()
+                    .scroll_to_highlight(&mut self.scrollable, document);
+
+                Command::none()
+            }
+            _ => {
+                self.jump_to = Some((path.clone(), line));
+
+                self.nav_tree.open_file(path).map(Message::NavTree)
             }
         }
     }
 
-    impl PartialOrd for Entry {
-        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-            Some(self.cmp(other))
+    fn update_journal(&mut self, message: journal::Message) -> Command<Message> {
+        match &message {
+            journal::Message::Applied(result) => self
+                .operation_log
+                .record(operation_log::Kind::Applied, result),
+            journal::Message::Undone(result) => self
+                .operation_log
+                .record(operation_log::Kind::Undone, result),
+            journal::Message::Redone(result) => self
+                .operation_log
+                .record(operation_log::Kind::Redone, result),
+            _ => {}
         }
+
+        let (command, event) = self.journal.update(message);
+
+        let refresh = match event {
+            Some(journal::Event::Changed(target)) => {
+                self.status = None;
+
+                let reload = target
+                    .clone()
+                    .filter(|target| {
+                        self.viewer
+                            .as_ref()
+                            .is_some_and(|document| document.path == *target)
+                    })
+                    .map(|target| self.update_nav_tree(navigation::Message::ReadFile(target)));
+
+                if let Some(target) = target {
+                    self.nav_tree.reveal(target);
+                }
+
+                let refresh = self.update_nav_tree(navigation::Message::RefreshDirectory);
+
+                Command::batch(reload.into_iter().chain(Some(refresh)))
+            }
+            Some(journal::Event::Failed(error)) => {
+                self.status = Some(error);
+
+                Command::none()
+            }
+            None => Command::none(),
+        };
+
+        Command::batch(vec![command.map(Message::Journal), refresh])
     }
 
-    pub enum State {
-        Loading(PathBuf),
-        Loaded {
-            directory: PathBuf,
-            entries: Vec<Entry>,
-            entry_buttons: Vec<button::State>,
-            up_button: button::State,
-            scrollable: scrollable::State,
-        },
+    fn start_transfer(
+        &mut self,
+        kind: transfer::Kind,
+        from: PathBuf,
+        to: PathBuf,
+    ) -> Command<Message> {
+        let policy = self
+            .conflict_policy
+            .or(self.config.config.conflict_policy.resolution());
+
+        let transfer = transfer::State::new(kind, from, to, policy, self.tasks.tasks());
+
+        self.transfers.push(transfer);
+
+        Command::none()
     }
 
-    impl State {
-        pub fn update(&mut self, message: Message) -> (Command<Message>, Option<Event>) {
-            match message {
-                Message::ChangeDirectory(path) => {
-                    if path.is_dir() {
-                        return (
-                            Command::perform(self.read_directory(path), |message| message),
-                            None,
-                        );
-                    }
+    fn perform(&mut self, action: keymap::Action) -> Command<Message> {
+        match action {
+            keymap::Action::ToggleHelp => {
+                self.show_help = !self.show_help;
+            }
+            keymap::Action::ToggleSettings => {
+                self.show_settings = !self.show_settings;
+            }
+            keymap::Action::OpenPalette => {
+                self.show_help = false;
+                self.palette = Some(palette::State::new(self.index.clone()));
+            }
+            keymap::Action::OpenTerminal => {
+                let directory = self.nav_tree.tree().current_dir();
+
+                self.status =
+                    launch::open_terminal(&self.config.config.terminal_command, directory).err();
+            }
+            keymap::Action::Run => {
+                if let Some(program) = self.nav_tree.tree().selected_executable() {
+                    let directory = self.nav_tree.tree().current_dir().to_path_buf();
+
+                    self.run = Some(run::State::new(
+                        program.to_path_buf(),
+                        directory,
+                        self.tasks.tasks(),
+                    ));
                 }
-                Message::DirectoryRead(result) => {
-                    if let Some((directory, entries)) = result {
-                        let buttons = vec![button::State::new(); entries.len()];
+            }
+            keymap::Action::GoHome => {
+                if let Some(home) = dirs_next::home_dir() {
+                    return self.nav_tree.navigate_to(home).map(Message::NavTree);
+                }
+            }
+            keymap::Action::OpenTrash => {
+                return self.update_nav_tree(navigation::Message::OpenTrash);
+            }
+            keymap::Action::GoToParent => {
+                return self.update_nav_tree(navigation::Message::GoToParent);
+            }
+            keymap::Action::EditPath => {
+                self.set_focus(Focus::PathBar);
 
-                        *self = Self::Loaded {
-                            directory,
-                            entries,
-                            entry_buttons: buttons,
-                            up_button: button::State::new(),
-                            scrollable: scrollable::State::new(),
-                        };
-                    }
+                return self.update_nav_tree(navigation::Message::EditPath);
+            }
+            keymap::Action::TogglePin => {
+                if let Some(selected) = self.nav_tree.tree().selected() {
+                    let selected = selected.to_path_buf();
+
+                    return self.update_nav_tree(navigation::Message::TogglePin(selected));
                 }
-                Message::ReadFile(path) => {
-                    if path.is_file() {
-                        return (
-                            Command::perform(self.read_file(path), |message| message),
-                            None,
-                        );
-                    }
+            }
+            keymap::Action::CycleTag => {
+                if let Some(selected) = self.nav_tree.tree().selected() {
+                    let selected = selected.to_path_buf();
+
+                    return self.update_nav_tree(navigation::Message::CycleTag(selected));
+                }
+            }
+            keymap::Action::ExpandAll => {
+                return self.update_nav_tree(navigation::Message::ExpandAll);
+            }
+            keymap::Action::CollapseAll => {
+                return self.update_nav_tree(navigation::Message::CollapseAll);
+            }
+            keymap::Action::Refresh => {
+                return self.update_nav_tree(navigation::Message::RefreshDirectory);
+            }
+            keymap::Action::FocusNext | keymap::Action::FocusPrevious => {
+                self.set_focus(self.focus.cycle(action == keymap::Action::FocusNext));
+            }
+            keymap::Action::SelectPrevious => match self.focus {
+                Focus::PathBar => return self.update_nav_tree(navigation::Message::GoToParent),
+                Focus::Tree => return self.update_nav_tree(navigation::Message::SelectPrevious),
+                Focus::Viewer => self
+                    .viewer_extent
+                    .get()
+                    .scroll_by(&mut self.scrollable, VIEWER_SCROLL_STEP),
+            },
+            keymap::Action::SelectNext => match self.focus {
+                Focus::PathBar => {}
+                Focus::Tree => return self.update_nav_tree(navigation::Message::SelectNext),
+                Focus::Viewer => self
+                    .viewer_extent
+                    .get()
+                    .scroll_by(&mut self.scrollable, -VIEWER_SCROLL_STEP),
+            },
+            keymap::Action::OpenSelected => {
+                return self.update_nav_tree(navigation::Message::OpenSelected);
+            }
+            keymap::Action::ViewDiff => {
+                if let Some(path) = self.nav_tree.tree().selected_modified() {
+                    let path = path.to_path_buf();
+
+                    return Command::perform(git::diff(path.clone()), move |result| {
+                        Message::DiffRead(path.clone(), result)
+                    });
                 }
-                Message::FileRead(result) => {
-                    if let Some((path, content)) = result {
-                        return (Command::none(), Some(Event::FileRead(path, content)));
+            }
+            keymap::Action::ToggleBlame => {
+                self.show_blame = !self.show_blame;
+
+                if let Some(document) = self.viewer.as_mut() {
+                    document.blame = None;
+
+                    if self.show_blame {
+                        return read_blame(document.path.clone());
                     }
                 }
-                Message::RefreshDirectory => {
-                    if let Self::Loaded { directory, .. } = &self {
-                        return (
-                            Command::perform(self.read_directory(directory.clone()), |message| {
-                                message
-                            }),
-                            None,
-                        );
+            }
+            keymap::Action::FindInFile | keymap::Action::SearchFiles => {
+                let scope = match (action, self.viewer.as_ref()) {
+                    (keymap::Action::FindInFile, Some(_)) => search::Scope::CurrentFile,
+                    _ => search::Scope::Directory,
+                };
+                let directory = self.nav_tree.tree().current_dir().to_path_buf();
+                let file = self.viewer.as_ref().map(|document| document.path.clone());
+
+                match self.search.as_mut() {
+                    Some(search) => search.focus(scope, directory, file),
+                    None => {
+                        self.search = Some(search::State::new(
+                            scope,
+                            directory,
+                            file,
+                            self.tasks.tasks(),
+                            self.index.clone(),
+                        ));
                     }
                 }
             }
+            keymap::Action::OpenInSplit => {
+                if let Some(path) = self.nav_tree.tree().selected_file() {
+                    let path = path.to_path_buf();
 
-            (Command::none(), None)
-        }
+                    self.open_in_split = Some(path.clone());
 
-        pub fn read_directory(&self, path: PathBuf) -> impl Future<Output = Message> {
-            read_directory(path).map(Message::DirectoryRead)
-        }
+                    return self.nav_tree.open_file(path).map(Message::NavTree);
+                }
+            }
+            keymap::Action::CloseSplit => {
+                self.split = None;
+            }
+            keymap::Action::CloseFile => {
+                if let Some(index) = self.tabs.active() {
+                    self.close_tab(index);
+                }
+            }
+            keymap::Action::ToggleHistory => {
+                self.show_history = !self.show_history;
+            }
+            keymap::Action::ToggleLog => {
+                self.show_log = !self.show_log;
+            }
+            keymap::Action::ToggleTasks => {
+                self.show_tasks = !self.show_tasks;
+            }
+            keymap::Action::NewFolder | keymap::Action::NewFile => {
+                let kind = if action == keymap::Action::NewFolder {
+                    file_ops::Kind::Directory
+                } else {
+                    file_ops::Kind::File
+                };
 
-        pub fn read_file(&self, path: PathBuf) -> impl Future<Output = Message> {
-            read_file(path).map(Message::FileRead)
-        }
+                self.prompt = Some(prompt::State::new(prompt::Kind::Create {
+                    directory: self.nav_tree.tree().current_dir().to_path_buf(),
+                    kind,
+                }));
+            }
+            keymap::Action::Rename => {
+                if let Some(selected) = self.nav_tree.tree().selected() {
+                    self.prompt = Some(prompt::State::new(prompt::Kind::Rename(
+                        selected.to_path_buf(),
+                    )));
+                }
+            }
+            keymap::Action::Copy => {
+                if let Some(selected) = self.nav_tree.tree().selected() {
+                    self.prompt = Some(prompt::State::new(prompt::Kind::Copy(
+                        selected.to_path_buf(),
+                    )));
+                }
+            }
+            keymap::Action::Move => {
+                if let Some(selected) = self.nav_tree.tree().selected() {
+                    self.prompt = Some(prompt::State::new(prompt::Kind::Move(
+                        selected.to_path_buf(),
+                    )));
+                }
+            }
+            keymap::Action::Trash => {
+                if let Some(selected) = self.nav_tree.tree().selected() {
+                    let request = file_ops::Request::Trash(selected.to_path_buf());
 
-        pub fn refresh_directory(&self) -> iced::Subscription<Message> {
-            iced_futures::time::every(time::Duration::from_secs(1))
-                .map(|_| Message::RefreshDirectory)
-        }
-    }
+                    return self.update_journal(journal::Message::Apply(request));
+                }
+            }
+            keymap::Action::DeletePermanently => {
+                if let Some(selected) = self.nav_tree.tree().selected() {
+                    self.confirm = Some(confirm::State::new(selected.to_path_buf()));
+                }
+            }
+            keymap::Action::ToggleHiddenFiles => {
+                let hide_hidden = !self.nav_tree.tree().hides_hidden_files();
 
-    async fn read_directory(path: PathBuf) -> Option<(PathBuf, Vec<Entry>)> {
-        let read_dir = fs::read_dir(&path).ok()?;
+                return self.update_nav_tree(navigation::Message::HideHiddenFiles(hide_hidden));
+            }
+            keymap::Action::ShowProperties => {
+                if let Some(selected) = self.nav_tree.tree().selected() {
+                    let (properties, command) =
+                        properties::State::new(selected.to_path_buf(), self.tasks.tasks());
 
-        let mut entries = vec![];
+                    self.properties = Some(properties);
 
-        for entry in read_dir.flatten() {
-            let name = entry.file_name().to_string_lossy().to_string();
-            let path = entry.path();
+                    return command.map(Message::Properties);
+                }
+            }
+            keymap::Action::CompareDirectories => {
+                let left = self
+                    .nav_tree
+                    .tree()
+                    .selected_directory()
+                    .unwrap_or_else(|| self.nav_tree.tree().current_dir())
+                    .to_path_buf();
 
-            if path.is_file() {
-                entries.push(Entry::File { path, name })
-            } else if path.is_dir() {
-                entries.push(Entry::Directory { path, name })
+                self.compare = Some(compare::State::new(left, self.tasks.tasks()));
+            }
+            keymap::Action::ExportListing => {
+                self.export = Some(export::State::new(
+                    self.nav_tree.tree().current_dir(),
+                    self.nav_tree.tree().export_records(),
+                ));
+            }
+            keymap::Action::IndexDirectory => {
+                let directory = self.nav_tree.tree().current_dir();
+
+                let index = if !self.index.is_complete() && self.index.root() == Some(directory) {
+                    self.index.clone()
+                } else {
+                    index::Index::new(directory.to_path_buf())
+                };
+
+                self.start_indexing(index);
+            }
+            keymap::Action::Undo => {
+                return self.update_journal(journal::Message::Undo);
+            }
+            keymap::Action::Redo => {
+                return self.update_journal(journal::Message::Redo);
             }
         }
 
-        entries.sort();
+        Command::none()
+    }
+}
+
+fn read_blame(path: PathBuf) -> Command<Message> {
+    Command::perform(git::blame(path.clone()), move |result| {
+        Message::BlameRead(path.clone(), result)
+    })
+}
 
-        Some((path, entries))
+fn mirrored(mut elements: Vec<Element<'_, Message>>, spacing: u16) -> Row<'_, Message> {
+    if i18n::is_right_to_left() {
+        elements.reverse();
     }
 
-    async fn read_file(path: PathBuf) -> Option<(PathBuf, String)> {
-        let contents = fs::read_to_string(&path).ok()?;
+    elements
+        .into_iter()
+        .fold(Row::new().spacing(spacing), Row::push)
+}
+
+fn viewer_options(config: &config::Config, path: &Path) -> viewer::Options {
+    viewer::Options {
+        font: config.viewer_font.font_for(path),
+        tab_width: config.tab_width,
+        show_invisibles: config.show_invisibles,
+    }
+}
 
-        Some((path, contents))
+fn history_button(button: mouse::Button) -> Option<navigation::Message> {
+    // Windows reports the side buttons as 1 and 2, X11 as 8 and 9 and Wayland as
+    // BTN_SIDE and BTN_EXTRA truncated to a byte.
+    match button {
+        mouse::Button::Other(1) if cfg!(windows) => Some(navigation::Message::GoBack),
+        mouse::Button::Other(2) if cfg!(windows) => Some(navigation::Message::GoForward),
+        mouse::Button::Other(8 | 19) => Some(navigation::Message::GoBack),
+        mouse::Button::Other(9 | 20) => Some(navigation::Message::GoForward),
+        _ => None,
     }
 }
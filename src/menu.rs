@@ -0,0 +1,185 @@
+use iced::button::{self, Button};
+use iced::{Align, Column, Element, Length, Row, Text};
+
+use crate::i18n;
+use crate::keymap::{Action, KeyMap};
+use crate::style;
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Menu {
+    File,
+    Edit,
+    View,
+    Go,
+    Help,
+}
+
+impl Menu {
+    pub const ALL: [Menu; 5] = [Menu::File, Menu::Edit, Menu::View, Menu::Go, Menu::Help];
+
+    fn actions(self) -> &'static [Action] {
+        match self {
+            Menu::File => &[
+                Action::NewFolder,
+                Action::NewFile,
+                Action::Rename,
+                Action::Copy,
+                Action::Move,
+                Action::Trash,
+                Action::DeletePermanently,
+                Action::ShowProperties,
+                Action::Run,
+                Action::OpenTerminal,
+                Action::ExportListing,
+                Action::CloseFile,
+            ],
+            Menu::Edit => &[
+                Action::Undo,
+                Action::Redo,
+                Action::FindInFile,
+                Action::SearchFiles,
+                Action::TogglePin,
+                Action::CycleTag,
+                Action::ToggleSettings,
+            ],
+            Menu::View => &[
+                Action::Refresh,
+                Action::ToggleHiddenFiles,
+                Action::ExpandAll,
+                Action::CollapseAll,
+                Action::OpenInSplit,
+                Action::CloseSplit,
+                Action::ViewDiff,
+                Action::ToggleBlame,
+                Action::ToggleHistory,
+                Action::ToggleLog,
+                Action::ToggleTasks,
+                Action::CompareDirectories,
+                Action::IndexDirectory,
+                Action::OpenPalette,
+            ],
+            Menu::Go => &[
+                Action::GoHome,
+                Action::GoToParent,
+                Action::EditPath,
+                Action::OpenTrash,
+                Action::SelectPrevious,
+                Action::SelectNext,
+                Action::OpenSelected,
+                Action::FocusNext,
+                Action::FocusPrevious,
+            ],
+            Menu::Help => &[Action::ToggleHelp],
+        }
+    }
+}
+
+impl fmt::Display for Menu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Menu::File => "File",
+            Menu::Edit => "Edit",
+            Menu::View => "View",
+            Menu::Go => "Go",
+            Menu::Help => "Help",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Message {
+    Toggle(Menu),
+    Perform(Action),
+}
+
+#[derive(Default)]
+pub struct State {
+    open: Option<Menu>,
+    menu_buttons: [button::State; Menu::ALL.len()],
+    item_buttons: Vec<button::State>,
+}
+
+impl State {
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::Toggle(menu) => {
+                self.open = if self.open == Some(menu) {
+                    None
+                } else {
+                    Some(menu)
+                };
+
+                None
+            }
+            Message::Perform(action) => {
+                self.open = None;
+
+                Some(action)
+            }
+        }
+    }
+}
+
+pub fn view<'a>(
+    state: &'a mut State,
+    keymap: &KeyMap,
+    is_enabled: impl Fn(Action) -> bool,
+) -> Element<'a, Message> {
+    let open = state.open;
+
+    let menus = Menu::ALL.iter().zip(state.menu_buttons.iter_mut()).fold(
+        Row::new().spacing(2),
+        |row, (menu, button)| {
+            let button = Button::new(button, Text::new(menu.to_string()))
+                .padding(5)
+                .on_press(Message::Toggle(*menu));
+
+            row.push(if open == Some(*menu) {
+                button.style(style::Selected)
+            } else {
+                button.style(style::GroupHeader)
+            })
+        },
+    );
+
+    let menu = match open {
+        Some(menu) => menu,
+        None => return menus.into(),
+    };
+
+    let actions = menu.actions();
+    state
+        .item_buttons
+        .resize_with(actions.len(), button::State::new);
+
+    let items = actions.iter().zip(state.item_buttons.iter_mut()).fold(
+        Column::new().spacing(2),
+        |column, (action, button)| {
+            let binding = keymap
+                .binding(*action)
+                .map_or(String::new(), |binding| binding.to_string());
+
+            let label = Row::new()
+                .spacing(20)
+                .align_items(Align::Center)
+                .push(Text::new(action.description()).width(Length::Units(320)))
+                .push(Text::new(binding).size(14).color(style::GUTTER));
+
+            let mut button = Button::new(button, label)
+                .padding(4)
+                .style(style::GroupHeader);
+
+            if is_enabled(*action) {
+                button = button.on_press(Message::Perform(*action));
+            }
+
+            column.push(button)
+        },
+    );
+
+    Column::new().spacing(5).push(menus).push(items).into()
+}
@@ -0,0 +1,1883 @@
+use iced::button::{self, Button};
+use iced::futures::FutureExt;
+use iced::pick_list::{self, PickList};
+use iced::scrollable::{self, Scrollable};
+use iced::slider::{self, Slider};
+use iced::text_input::{self, TextInput};
+use iced::{
+    Align, Checkbox, Column, Command, Container, Element, HorizontalAlignment, Length, ProgressBar,
+    Rectangle, Row, Space, Text,
+};
+
+use crate::bidi;
+use crate::breadcrumb;
+use crate::checksum::Verdict;
+use crate::column_resize;
+use crate::drop_zone::DropZone;
+use crate::file_type::Category;
+use crate::format;
+use crate::git;
+use crate::i18n;
+use crate::navigation::{
+    self, Destination, DetailColumn, Effect, Entry, Event, Grouping, Listing, Message, PathStatus,
+    Scan, ScanKind, SortKey, Stored, Tree, TreeRow, ViewMode,
+};
+use crate::probe::{with_timeout, Probe};
+use crate::scan;
+use crate::smart_folder::{self, Contents};
+use crate::storage;
+use crate::style;
+use crate::tags::{self, Tag};
+use crate::tasks::{self, Tasks};
+use crate::trash;
+use crate::tree_view::{self, TreeView};
+use crate::volumes;
+use crate::watcher;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::time;
+
+const INDENT_WIDTH: u16 = 16;
+const TOGGLE_WIDTH: u16 = 24;
+const TREE_WIDTH: u16 = 300;
+const SCROLLBAR_WIDTH: u16 = 20;
+const CHARACTER_WIDTH: u16 = 10;
+const BUTTON_PADDING: u16 = 10;
+const BREADCRUMB_WIDTH: usize = 280;
+const GRID_CELL_WIDTH: u16 = 92;
+const GRID_COLUMNS: usize = ((TREE_WIDTH - SCROLLBAR_WIDTH) / GRID_CELL_WIDTH) as usize;
+const VOLUME_POLL_INTERVAL: time::Duration = time::Duration::from_secs(3);
+const SHORTCUTS: &[(&str, KnownFolder)] = &[
+    ("Home", dirs_next::home_dir),
+    ("Desktop", dirs_next::desktop_dir),
+    ("Documents", dirs_next::document_dir),
+    ("Downloads", dirs_next::download_dir),
+    ("Pictures", dirs_next::picture_dir),
+];
+const DAY: time::Duration = time::Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Age {
+    Today,
+    ThisWeek,
+    Older,
+}
+
+impl Age {
+    fn of(modified: time::SystemTime, now: time::SystemTime) -> Self {
+        let elapsed = now.duration_since(modified).unwrap_or_default();
+
+        if elapsed < DAY {
+            Age::Today
+        } else if elapsed < DAY * 7 {
+            Age::ThisWeek
+        } else {
+            Age::Older
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Recent {
+    Placeholder,
+    Directory(PathBuf),
+}
+
+impl fmt::Display for Recent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recent::Placeholder => f.write_str(i18n::tr("Recent")),
+            Recent::Directory(path) => write!(f, "{}", path.to_string_lossy()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RecentFilter {
+    Placeholder,
+    Query(String),
+}
+
+impl fmt::Display for RecentFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecentFilter::Placeholder => f.write_str(i18n::tr("Recent filters")),
+            RecentFilter::Query(query) => f.write_str(query),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagFilter {
+    All,
+    Tag(Tag),
+}
+
+impl fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagFilter::All => f.write_str(i18n::tr("All tags")),
+            TagFilter::Tag(tag) => write!(f, "● {}", tag),
+        }
+    }
+}
+
+type KnownFolder = fn() -> Option<PathBuf>;
+
+pub struct RowContext<'a> {
+    pub entry: &'a Entry,
+    pub label: String,
+    pub is_selected: bool,
+    pub age: Option<Age>,
+    pub verdict: Option<Verdict>,
+    pub tag: Option<Tag>,
+}
+
+pub fn default_row(row: &RowContext<'_>) -> Element<'static, Message> {
+    let mut label = Text::new(row.label.clone());
+
+    if !row.is_selected {
+        if row.entry.is_executable() {
+            label = label.color(style::EXECUTABLE);
+        } else if row.age == Some(Age::Today) {
+            label = label.color(style::RECENT);
+        } else if row.age == Some(Age::Older) {
+            label = label.color(style::GUTTER);
+        }
+    }
+
+    let mark = match row.verdict {
+        Some(Verdict::Ok) => Some(Text::new(i18n::tr("OK")).color(style::DIFF_ADDED)),
+        Some(Verdict::Failed) => Some(Text::new(i18n::tr("FAILED")).color(style::ERROR)),
+        Some(Verdict::Missing) | None => None,
+    };
+
+    if mark.is_none() && row.tag.is_none() {
+        return label.into();
+    }
+
+    let mut content = Row::new().spacing(5).align_items(Align::Center);
+
+    if let Some(tag) = row.tag {
+        content = content.push(Text::new("●").size(12).color(tag.color()));
+    }
+
+    content = content.push(label);
+
+    if let Some(mark) = mark {
+        content = content.push(mark.size(12));
+    }
+
+    content.into()
+}
+
+pub fn view(
+    state: &mut State,
+    render_row: impl Fn(&RowContext<'_>) -> Element<'static, Message>,
+) -> Element<'_, Message> {
+    state.scroll_to_reveal();
+
+    let tree = &state.tree;
+
+    let rows = tree.rows();
+    let groups = tree.groups();
+    let view_mode = tree.view_mode();
+    let show_details = view_mode == ViewMode::Details;
+
+    if state.widest_row.0 != tree.row_generation() {
+        let widest = rows
+            .iter()
+            .map(|row| row_width(row.entry, row.depth))
+            .max()
+            .unwrap_or_default();
+
+        state.widest_row = (tree.row_generation(), widest);
+    }
+
+    let horizontal_overflow = if view_mode == ViewMode::Grid {
+        0
+    } else {
+        state
+            .widest_row
+            .1
+            .saturating_sub(TREE_WIDTH - SCROLLBAR_WIDTH)
+    };
+
+    let horizontal_offset = tree.horizontal_offset().min(horizontal_overflow);
+    let guides = if state.show_indent_guides {
+        guides(rows.iter().map(|row| row.depth))
+    } else {
+        vec![vec![]; rows.len()]
+    };
+    let selected = tree.selected();
+    let group_by_type = tree.group_by_type();
+    let flash = tree.flash();
+    let animation = tree.animation();
+    let pinned = tree.pinned();
+    let pin_buttons = &mut state.pin_buttons;
+    let volumes = tree.volumes();
+    let volume_buttons = &mut state.volume_buttons;
+    let smart_folders = tree.smart_folders();
+    let smart_folder_rows = &mut state.smart_folder_rows;
+    let smart_folder_input = &mut state.smart_folder_input;
+    let focus = state.focus;
+    let now = time::SystemTime::now();
+    let age_colors = state.age_colors;
+
+    let content: Element<_> = match tree.listing() {
+        Listing::Loading(directory) => {
+            let text = Text::new(i18n::tr_with(
+                "Loading {}...",
+                &[&format!("{:?}", directory)],
+            ));
+
+            Container::new(text).center_x().center_y().into()
+        }
+        Listing::NotResponding { directory, .. } => {
+            let retry = Button::new(&mut state.retry_button, Text::new(i18n::tr("Retry")))
+                .on_press(Message::ChangeDirectory(directory.clone()));
+            let back = Button::new(&mut state.back_button, Text::new(i18n::tr("Go back")))
+                .on_press(Message::LeaveUnresponsive);
+
+            Column::new()
+                .spacing(10)
+                .push(Text::new(i18n::tr("This location is not responding")).color(style::ERROR))
+                .push(Text::new(directory.to_string_lossy()).size(14))
+                .push(Row::new().spacing(10).push(retry).push(back))
+                .into()
+        }
+        Listing::Loaded { directory, entries } => {
+            let mut scrollable = Scrollable::new(&mut state.scrollable);
+
+            if !pinned.is_empty() {
+                scrollable =
+                    scrollable.push(Text::new(i18n::tr("Pinned")).size(14).color(style::GUTTER));
+
+                pin_buttons.resize_with(pinned.len(), Default::default);
+
+                for (path, (open, unpin)) in pinned.iter().zip(pin_buttons.iter_mut()) {
+                    let name = path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+                    let mut open = Button::new(
+                        open,
+                        Text::new(bidi::display(
+                            &format!("* {}", name),
+                            i18n::is_right_to_left(),
+                        )),
+                    )
+                    .on_press(Message::OpenPinned(path.clone()));
+
+                    if selected == Some(path.as_path()) {
+                        open = open.style(style::Selected);
+                    }
+
+                    let unpin = Button::new(unpin, Text::new("×").size(14))
+                        .padding(3)
+                        .style(style::GroupHeader)
+                        .on_press(Message::TogglePin(path.clone()));
+
+                    scrollable = scrollable.push(
+                        Row::new()
+                            .align_items(Align::Center)
+                            .push(open)
+                            .push(style::labelled(unpin, i18n::tr("Unpin"))),
+                    );
+                }
+            }
+
+            scrollable = scrollable.push(
+                Text::new(i18n::tr("Smart folders"))
+                    .size(14)
+                    .color(style::GUTTER),
+            );
+
+            smart_folder_rows.resize_with(smart_folders.len(), SmartFolderRow::default);
+
+            for ((query, contents), row) in smart_folders.iter().zip(smart_folder_rows.iter_mut()) {
+                let marker = if *contents == Contents::Collapsed {
+                    "+"
+                } else {
+                    "-"
+                };
+
+                let toggle = Button::new(
+                    &mut row.toggle_button,
+                    Text::new(format!("{} {}", marker, query.text)).size(14),
+                )
+                .padding(3)
+                .style(style::GroupHeader)
+                .on_press(Message::ToggleSmartFolder(query.text.clone()));
+
+                let remove = Button::new(&mut row.remove_button, Text::new("×").size(14))
+                    .padding(3)
+                    .style(style::GroupHeader)
+                    .on_press(Message::RemoveSmartFolder(query.text.clone()));
+
+                scrollable = scrollable.push(
+                    Row::new()
+                        .align_items(Align::Center)
+                        .push(style::labelled(toggle, &query.root.to_string_lossy()))
+                        .push(style::labelled(remove, i18n::tr("Remove"))),
+                );
+
+                let indent = Space::with_width(Length::Units(INDENT_WIDTH));
+
+                match contents {
+                    Contents::Collapsed => {}
+                    Contents::Loading => {
+                        scrollable = scrollable.push(
+                            Row::new().push(indent).push(
+                                Text::new(i18n::tr("Searching..."))
+                                    .size(14)
+                                    .color(style::GUTTER),
+                            ),
+                        );
+                    }
+                    Contents::Loaded(paths) if paths.is_empty() => {
+                        scrollable = scrollable.push(
+                            Row::new().push(indent).push(
+                                Text::new(i18n::tr("No matches"))
+                                    .size(14)
+                                    .color(style::GUTTER),
+                            ),
+                        );
+                    }
+                    Contents::Loaded(paths) => {
+                        row.result_buttons
+                            .resize_with(paths.len(), button::State::default);
+
+                        for (path, button) in paths.iter().zip(row.result_buttons.iter_mut()) {
+                            let label = path.strip_prefix(&query.root).unwrap_or(path);
+
+                            let open =
+                                Button::new(button, Text::new(label.to_string_lossy()).size(14))
+                                    .padding(2)
+                                    .on_press(Message::NavigateTo(path.clone()));
+
+                            scrollable = scrollable.push(
+                                Row::new()
+                                    .push(Space::with_width(Length::Units(INDENT_WIDTH)))
+                                    .push(open),
+                            );
+                        }
+                    }
+                }
+            }
+
+            scrollable = scrollable.push(
+                TextInput::new(
+                    smart_folder_input,
+                    i18n::tr("New smart folder: *.rs modified:7d under ~/src"),
+                    tree.smart_folder_input(),
+                    Message::SmartFolderInputChanged,
+                )
+                .size(14)
+                .padding(3)
+                .on_submit(Message::AddSmartFolder),
+            );
+
+            if !volumes.is_empty() {
+                scrollable =
+                    scrollable.push(Text::new(i18n::tr("Volumes")).size(14).color(style::GUTTER));
+
+                volume_buttons.resize_with(volumes.len(), Default::default);
+
+                for (volume, button) in volumes.iter().zip(volume_buttons.iter_mut()) {
+                    let mut open = Button::new(button, Text::new(volume.root.to_string_lossy()))
+                        .on_press(Message::ChangeDirectory(volume.root.clone()));
+
+                    if *directory == volume.root {
+                        open = open.style(style::Selected);
+                    }
+
+                    let mut row = Row::new().spacing(5).align_items(Align::Center).push(open);
+
+                    if let Some(space) = volume.space {
+                        row = row
+                            .push(
+                                ProgressBar::new(0.0..=1.0, space.used())
+                                    .width(Length::Units(60))
+                                    .height(Length::Units(6)),
+                            )
+                            .push(
+                                Text::new(i18n::tr_with("{} free", &[&format::size(space.free)]))
+                                    .size(12)
+                                    .color(style::GUTTER),
+                            );
+                    }
+
+                    scrollable = scrollable.push(row);
+                }
+            }
+
+            if let Some(parent) = directory.parent() {
+                let content = Text::new("..");
+
+                let button = Button::new(&mut state.up_button, content)
+                    .on_press(Message::ChangeDirectory(parent.to_path_buf()));
+
+                scrollable =
+                    scrollable.push(style::labelled(button, i18n::tr("Go to parent directory")));
+            };
+
+            let summary = summary(tree, entries.iter().filter(|entry| tree.matches(entry)));
+
+            let total = rows.len() + if group_by_type { groups.len() } else { 0 };
+            let window = if view_mode == ViewMode::Grid {
+                0..0
+            } else {
+                state.tree_view.window(total)
+            };
+
+            let mut items = vec![];
+            let mut index = 0;
+            let mut reveal = None;
+            let mut remaining = rows.iter().zip(guides.iter()).peekable();
+
+            for (group, count) in groups.iter() {
+                if group_by_type && window.contains(&index) {
+                    let marker = if tree.is_group_collapsed(group) {
+                        "+"
+                    } else {
+                        "-"
+                    };
+
+                    let header = Text::new(format!("{} {} ({})", marker, group, count)).size(16);
+
+                    items.push(tree_view::Item::new(
+                        Container::new(header).padding(5),
+                        Message::ToggleGroup(group),
+                    ));
+                }
+
+                if group_by_type {
+                    index += 1;
+                }
+
+                while let Some((row, continues)) =
+                    remaining.next_if(|(row, _)| !group_by_type || row.group == *group)
+                {
+                    let (entry, depth) = (row.entry, row.depth);
+                    let position = index;
+
+                    index += 1;
+
+                    if let Some((directory, _)) = animation {
+                        if entry.path() == directory {
+                            reveal = Some((position + 1, 0));
+                        } else if entry.path().starts_with(directory) {
+                            if let Some((_, count)) = reveal.as_mut() {
+                                *count += 1;
+                            }
+                        }
+                    }
+
+                    if !window.contains(&position) {
+                        continue;
+                    }
+
+                    let indent = depth as u16 * INDENT_WIDTH;
+                    let overflow = horizontal_offset.saturating_sub(indent);
+                    let offset = indent.saturating_sub(horizontal_offset);
+
+                    let toggle: Element<_> = match entry {
+                        _ if overflow >= TOGGLE_WIDTH => Space::with_width(Length::Shrink).into(),
+                        Entry::Directory { path, .. } => {
+                            let marker = if tree.is_expanded(path) { "-" } else { "+" };
+
+                            Text::new(marker)
+                                .size(16)
+                                .width(Length::Units(TOGGLE_WIDTH))
+                                .horizontal_alignment(HorizontalAlignment::Center)
+                                .into()
+                        }
+                        Entry::File { .. } => Space::with_width(Length::Units(TOGGLE_WIDTH)).into(),
+                    };
+
+                    let hidden_characters = overflow.saturating_sub(TOGGLE_WIDTH) / CHARACTER_WIDTH;
+                    let mut name: String = entry
+                        .name()
+                        .chars()
+                        .skip(hidden_characters as usize)
+                        .collect();
+
+                    if show_details {
+                        let room = tree
+                            .column_width(DetailColumn::Name)
+                            .saturating_sub(offset + TOGGLE_WIDTH + BUTTON_PADDING)
+                            / CHARACTER_WIDTH;
+
+                        name = truncate(&name, room as usize);
+                    }
+
+                    let is_selected = selected == Some(entry.path());
+
+                    let age = tree
+                        .modified(entry.path())
+                        .filter(|_| age_colors)
+                        .map(|modified| Age::of(modified, now));
+
+                    let label = render_row(&RowContext {
+                        entry,
+                        label: name,
+                        is_selected,
+                        age,
+                        verdict: tree.verdict(entry.path()),
+                        tag: tree.tag(entry.path()),
+                    });
+
+                    let mut row = Row::new()
+                        .align_items(Align::Center)
+                        .push(Space::with_width(Length::Units(offset)))
+                        .push(toggle)
+                        .push(Container::new(label).padding(5));
+
+                    if show_details {
+                        row = details_row(tree, entry, row);
+                    }
+
+                    let mut item = tree_view::Item::new(row, entry.message())
+                        .on_select(Message::Select(entry.path().to_path_buf()))
+                        .selected(is_selected);
+
+                    if let Some((_, intensity)) = flash.filter(|(path, _)| *path == entry.path()) {
+                        item = item.flash(intensity);
+                    }
+
+                    if let Entry::Directory { path, .. } = entry {
+                        item = item.on_middle_click(Message::OpenInNewWindow(path.clone()));
+
+                        if overflow < TOGGLE_WIDTH {
+                            item = item.toggle(
+                                offset,
+                                TOGGLE_WIDTH,
+                                Message::ToggleExpanded(path.clone()),
+                            );
+                        }
+                    }
+
+                    for (level, continues) in continues.iter().enumerate() {
+                        let x = level as u16 * INDENT_WIDTH + TOGGLE_WIDTH / 2;
+
+                        if x < horizontal_offset {
+                            continue;
+                        }
+
+                        let guide = if level + 1 < depth {
+                            if !continues {
+                                continue;
+                            }
+
+                            tree_view::Guide::Through
+                        } else {
+                            let arm = match entry {
+                                Entry::Directory { .. } => INDENT_WIDTH - TOGGLE_WIDTH / 4,
+                                Entry::File { .. } => {
+                                    INDENT_WIDTH - TOGGLE_WIDTH / 2 + TOGGLE_WIDTH
+                                }
+                            };
+
+                            tree_view::Guide::Branch {
+                                arm,
+                                last: !continues,
+                            }
+                        };
+
+                        item = item.guide(x - horizontal_offset, guide);
+                    }
+
+                    items.push(item);
+                }
+            }
+
+            if show_details {
+                scrollable = scrollable.push(details_header(tree, &mut state.column_headers));
+            }
+
+            if view_mode == ViewMode::Grid {
+                scrollable = scrollable.push(grid(&rows, selected, &mut state.grid_buttons));
+            } else {
+                let mut tree_view = TreeView::new(&mut state.tree_view, items)
+                    .window(window.start, total)
+                    .on_scroll(Message::TreeScrolled);
+
+                if let (Some((start, count)), Some((_, fraction))) = (reveal, animation) {
+                    tree_view = tree_view.reveal(start, count, fraction);
+                }
+
+                scrollable = scrollable.push(tree_view);
+            }
+
+            let git_label = tree.git_status().map(|status| {
+                let marker = if status.dirty { "*" } else { "" };
+
+                format!("{}{}", status.branch, marker)
+            });
+
+            let reserved = git_label.as_ref().map_or(0, |label| {
+                (label.chars().count() + 2) * breadcrumb::CHARACTER_WIDTH
+            });
+
+            let breadcrumb: Element<_> = match tree.path_input() {
+                Some(value) => {
+                    TextInput::new(&mut state.path_input, "", value, Message::PathInputChanged)
+                        .size(14)
+                        .padding(3)
+                        .width(Length::Units(
+                            BREADCRUMB_WIDTH.saturating_sub(reserved) as u16
+                        ))
+                        .on_submit(Message::SubmitPath)
+                        .style(style::ValidatedInput {
+                            border: tree.path_status().map(path_status_color),
+                            is_selected: tree.is_path_input_selected(),
+                        })
+                        .into()
+                }
+                None => breadcrumb::view(
+                    &mut state.breadcrumb,
+                    directory,
+                    BREADCRUMB_WIDTH.saturating_sub(reserved),
+                )
+                .map(Message::ChangeDirectory),
+            };
+
+            let breadcrumb = Container::new(breadcrumb)
+                .padding(2)
+                .style(style::FocusRing(focus == Some(Focus::PathBar)));
+
+            let mut header = Row::new()
+                .spacing(5)
+                .align_items(Align::Center)
+                .push(DropZone::new(breadcrumb, Message::NavigateTo));
+
+            if let Some(label) = git_label {
+                header = header.push(Text::new(label).size(14).color(style::GIT_BRANCH));
+            }
+
+            let recent: Vec<_> = tree
+                .recent()
+                .iter()
+                .filter(|path| *path != directory)
+                .cloned()
+                .map(Recent::Directory)
+                .collect();
+
+            if !recent.is_empty() {
+                let current = directory.clone();
+
+                let dropdown = PickList::new(
+                    &mut state.recent_list,
+                    recent,
+                    Some(Recent::Placeholder),
+                    move |recent| match recent {
+                        Recent::Directory(path) => Message::ChangeDirectory(path),
+                        Recent::Placeholder => Message::ChangeDirectory(current.clone()),
+                    },
+                )
+                .text_size(14)
+                .padding(3);
+
+                header = header.push(dropdown);
+            }
+
+            let trash_button = Button::new(
+                &mut state.trash_button,
+                Text::new(i18n::tr("Trash")).size(14),
+            )
+            .padding(3)
+            .on_press(Message::OpenTrash);
+            let expand_all_button = Button::new(
+                &mut state.expand_all_button,
+                Text::new(i18n::tr("Expand all")).size(14),
+            )
+            .padding(3)
+            .on_press(Message::ExpandAll);
+            let collapse_all_button = Button::new(
+                &mut state.collapse_all_button,
+                Text::new(i18n::tr("Collapse all")).size(14),
+            )
+            .padding(3)
+            .on_press(Message::CollapseAll);
+
+            let mut actions = Row::new()
+                .spacing(5)
+                .push(trash_button)
+                .push(expand_all_button)
+                .push(collapse_all_button);
+
+            if !tree.manifests().is_empty() {
+                actions = actions.push(
+                    Button::new(
+                        &mut state.verify_button,
+                        Text::new(i18n::tr("Verify")).size(14),
+                    )
+                    .padding(3)
+                    .on_press(Message::VerifyChecksums),
+                );
+            }
+
+            let group_toggle = Checkbox::new(
+                group_by_type,
+                i18n::tr("Group by type"),
+                Message::GroupByType,
+            )
+            .size(16)
+            .text_size(16);
+
+            let view_modes = ViewMode::ALL
+                .iter()
+                .zip(state.view_mode_buttons.iter_mut())
+                .fold(Row::new().spacing(2), |row, (mode, button)| {
+                    let mut button = Button::new(button, Text::new(mode.to_string()).size(14))
+                        .padding(3)
+                        .on_press(Message::SetViewMode(*mode));
+
+                    if *mode == view_mode {
+                        button = button.style(style::Selected);
+                    }
+
+                    row.push(button)
+                });
+
+            let sort = tree.sort();
+
+            let sort_list = PickList::new(
+                &mut state.sort_list,
+                &SortKey::ALL[..],
+                Some(sort.key),
+                Message::SortBy,
+            )
+            .text_size(14)
+            .padding(3);
+
+            let sort_order = Button::new(
+                &mut state.sort_order_button,
+                Text::new(if sort.descending { "↓" } else { "↑" }).size(14),
+            )
+            .padding(3)
+            .on_press(Message::ReverseSort);
+
+            let group_toggle = Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(group_toggle)
+                .push(view_modes)
+                .push(Text::new(i18n::tr("Sort by")).size(14))
+                .push(sort_list)
+                .push(style::labelled(
+                    sort_order,
+                    i18n::tr(if sort.descending {
+                        "Descending"
+                    } else {
+                        "Ascending"
+                    }),
+                ));
+
+            let hide_large = Checkbox::new(
+                tree.hides_large_files(),
+                i18n::tr("Hide large files"),
+                Message::HideLargeFiles,
+            )
+            .size(16)
+            .text_size(16);
+
+            let hide_binary = Checkbox::new(
+                tree.hides_binary_files(),
+                i18n::tr("Hide binary files"),
+                Message::HideBinaryFiles,
+            )
+            .size(16)
+            .text_size(16);
+
+            let hide_hidden = Checkbox::new(
+                tree.hides_hidden_files(),
+                i18n::tr("Hide hidden files"),
+                Message::HideHiddenFiles,
+            )
+            .size(16)
+            .text_size(16);
+
+            let mut chips = Category::ALL
+                .iter()
+                .zip(state.filter_buttons.iter_mut())
+                .fold(Row::new().spacing(5), |row, (category, button)| {
+                    let mut chip = Button::new(button, Text::new(category.to_string()).size(14))
+                        .padding(3)
+                        .on_press(Message::ToggleFilter(*category));
+
+                    if tree.is_filtered_by(*category) {
+                        chip = chip.style(style::Selected);
+                    }
+
+                    row.push(chip)
+                });
+
+            let recent_filters: Vec<_> = tree
+                .filter_history()
+                .iter()
+                .cloned()
+                .map(RecentFilter::Query)
+                .collect();
+
+            if tree.has_tags() || tree.tag_filter().is_some() {
+                let options: Vec<_> = std::iter::once(TagFilter::All)
+                    .chain(Tag::ALL.iter().copied().map(TagFilter::Tag))
+                    .collect();
+
+                let selected = tree.tag_filter().map_or(TagFilter::All, TagFilter::Tag);
+
+                chips = chips.push(
+                    PickList::new(
+                        &mut state.tag_filter_list,
+                        options,
+                        Some(selected),
+                        |filter| match filter {
+                            TagFilter::All => Message::FilterByTag(None),
+                            TagFilter::Tag(tag) => Message::FilterByTag(Some(tag)),
+                        },
+                    )
+                    .text_size(14)
+                    .padding(3),
+                );
+            }
+
+            if !recent_filters.is_empty() {
+                let current = tree.filter_text().to_string();
+
+                chips = chips.push(
+                    PickList::new(
+                        &mut state.recent_filter_list,
+                        recent_filters,
+                        Some(RecentFilter::Placeholder),
+                        move |recent| match recent {
+                            RecentFilter::Query(query) => Message::RecallFilter(query),
+                            RecentFilter::Placeholder => Message::RecallFilter(current.clone()),
+                        },
+                    )
+                    .text_size(14)
+                    .padding(3),
+                );
+            }
+
+            let shortcuts = state.shortcuts.iter_mut().fold(
+                Row::new().spacing(5),
+                |row, (label, path, button)| {
+                    let mut shortcut = Button::new(button, Text::new(i18n::tr(label)).size(14))
+                        .padding(3)
+                        .style(style::GroupHeader)
+                        .on_press(Message::ChangeDirectory(path.clone()));
+
+                    if path == directory {
+                        shortcut = shortcut.style(style::Selected);
+                    }
+
+                    row.push(shortcut)
+                },
+            );
+
+            let resolved = tree
+                .path_input()
+                .zip(tree.resolved_path_input())
+                .map(|(value, resolved)| (resolved.to_string_lossy().to_string(), value))
+                .filter(|(resolved, value)| resolved != value)
+                .map(|(resolved, _)| {
+                    Text::new(i18n::tr_with("Resolves to {}", &[&resolved]))
+                        .size(12)
+                        .color(style::GUTTER)
+                });
+
+            let status = tree.path_input().and(tree.path_status()).map(|status| {
+                Text::new(status.to_string())
+                    .size(12)
+                    .color(path_status_color(status))
+            });
+
+            let mut column = Column::new().spacing(10).push(header);
+
+            if status.is_some() || resolved.is_some() {
+                let feedback = status
+                    .into_iter()
+                    .chain(resolved)
+                    .fold(Row::new().spacing(10), Row::push);
+
+                column = column.push(feedback);
+            }
+
+            if tree.path_input().is_some() && !tree.completions().is_empty() {
+                let highlighted = tree.completion();
+
+                state
+                    .completion_buttons
+                    .resize_with(tree.completions().len(), Default::default);
+
+                let completions = tree
+                    .completions()
+                    .iter()
+                    .zip(state.completion_buttons.iter_mut())
+                    .fold(Column::new(), |completions, (path, button)| {
+                        let name = path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                            .unwrap_or_default();
+
+                        let mut completion = Button::new(button, Text::new(name).size(14))
+                            .padding(3)
+                            .width(Length::Fill)
+                            .style(style::GroupHeader)
+                            .on_press(Message::PickCompletion(path.clone()));
+
+                        if highlighted == Some(path.as_path()) {
+                            completion = completion.style(style::Selected);
+                        }
+
+                        completions.push(completion)
+                    });
+
+                column = column.push(
+                    Container::new(completions)
+                        .width(Length::Units(BREADCRUMB_WIDTH as u16))
+                        .padding(2)
+                        .style(style::FocusRing(true)),
+                );
+            }
+
+            column = column.push(shortcuts).push(Text::new(summary).size(14));
+
+            let missing = tree.missing_checksums();
+
+            if missing > 0 {
+                column = column.push(
+                    Text::new(i18n::tr_with(
+                        "{} listed files are missing",
+                        &[&missing.to_string()],
+                    ))
+                    .size(14)
+                    .color(style::ERROR),
+                );
+            }
+
+            if let Some(space) = tree.space() {
+                column = column.push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Align::Center)
+                        .push(
+                            ProgressBar::new(0.0..=1.0, space.used())
+                                .width(Length::Units(60))
+                                .height(Length::Units(6)),
+                        )
+                        .push(
+                            Text::new(i18n::tr_with(
+                                "{} free of {}",
+                                &[&format::size(space.free), &format::size(space.total)],
+                            ))
+                            .size(12)
+                            .color(style::GUTTER),
+                        ),
+                );
+            }
+
+            let marker = if tree.shows_statistics() { "-" } else { "+" };
+
+            column = column.push(
+                Button::new(
+                    &mut state.statistics_button,
+                    Text::new(format!("{} {}", marker, i18n::tr("Statistics"))).size(14),
+                )
+                .padding(2)
+                .style(style::GroupHeader)
+                .on_press(Message::ToggleStatistics),
+            );
+
+            if tree.shows_statistics() {
+                column = column.push(statistics(tree));
+            }
+
+            if tree.has_text_filter() {
+                column = column.push(
+                    Text::new(i18n::tr_with(
+                        "Filter: {} (Escape to clear)",
+                        &[&tree.filter_text()],
+                    ))
+                    .size(14),
+                );
+            }
+
+            let container = Container::new(scrollable)
+                .padding(2)
+                .style(style::FocusRing(focus == Some(Focus::Tree)));
+
+            column = column
+                .push(actions)
+                .push(chips)
+                .push(group_toggle)
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(hide_large)
+                        .push(hide_binary)
+                        .push(hide_hidden),
+                )
+                .push(DropZone::new(container, Message::Dropped));
+
+            if horizontal_overflow > 0 {
+                let slider = Slider::new(
+                    &mut state.horizontal_slider,
+                    0.0..=horizontal_overflow as f32,
+                    horizontal_offset as f32,
+                    |offset| Message::ScrolledHorizontally(offset as u16),
+                );
+
+                column = column.push(slider);
+            }
+
+            column.into()
+        }
+        Listing::Trash {
+            return_to,
+            items,
+            confirm_delete,
+        } => {
+            let back_button = Button::new(&mut state.trash_back_button, Text::new(".."))
+                .on_press(Message::ChangeDirectory(return_to.clone()));
+
+            let mut scrollable = Scrollable::new(&mut state.trash_scrollable)
+                .spacing(5)
+                .push(style::labelled(back_button, i18n::tr("Close trash")));
+
+            if items.is_empty() {
+                scrollable = scrollable.push(Text::new(i18n::tr("The trash is empty")));
+            }
+
+            state.trash_rows.resize_with(items.len(), TrashRow::default);
+
+            for (item, row) in items.iter().zip(state.trash_rows.iter_mut()) {
+                let name = item
+                    .original
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                let location = item
+                    .original
+                    .parent()
+                    .map(|parent| parent.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                let restore = Button::new(&mut row.restore_button, Text::new(i18n::tr("Restore")))
+                    .on_press(Message::RestoreFromTrash(item.clone()));
+
+                let delete_label = if confirm_delete.as_ref() == Some(&item.trashed) {
+                    "Confirm delete"
+                } else {
+                    "Delete"
+                };
+
+                let delete = Button::new(&mut row.delete_button, Text::new(i18n::tr(delete_label)))
+                    .on_press(Message::DeleteFromTrash(item.clone()));
+
+                let row = Column::new()
+                    .push(Text::new(bidi::display(&name, i18n::is_right_to_left())))
+                    .push(Text::new(location).size(14))
+                    .push(
+                        Row::new()
+                            .spacing(5)
+                            .push(restore)
+                            .push(Space::with_width(Length::Fill))
+                            .push(delete),
+                    );
+
+                scrollable = scrollable.push(row);
+            }
+
+            Column::new()
+                .spacing(10)
+                .push(Text::new(i18n::tr("Trash")))
+                .push(
+                    Container::new(scrollable)
+                        .padding(2)
+                        .style(style::FocusRing(focus == Some(Focus::Tree))),
+                )
+                .into()
+        }
+    };
+
+    Container::new(content)
+        .width(Length::Units(TREE_WIDTH))
+        .into()
+}
+
+fn details_header<'a>(
+    tree: &Tree,
+    headers: &'a mut [ColumnHeader; DetailColumn::ALL.len()],
+) -> Element<'a, Message> {
+    let sort = tree.sort();
+
+    DetailColumn::ALL
+        .iter()
+        .zip(headers.iter_mut())
+        .fold(
+            Row::new().align_items(Align::Center),
+            |row, (column, header)| {
+                let column = *column;
+
+                let label = if sort.key == column.sort_key() {
+                    format!("{} {}", column, if sort.descending { "↓" } else { "↑" })
+                } else {
+                    column.to_string()
+                };
+
+                let sort_button = Button::new(&mut header.sort_button, Text::new(label).size(14))
+                    .width(Length::Units(tree.column_width(column)))
+                    .padding(3)
+                    .style(style::GroupHeader)
+                    .on_press(Message::SortBy(column.sort_key()));
+
+                let handle = column_resize::Handle::new(
+                    &mut header.handle,
+                    move |delta| Message::ResizeColumn(column, delta),
+                    Message::FinishColumnResize,
+                );
+
+                row.push(sort_button).push(handle)
+            },
+        )
+        .into()
+}
+
+fn details_row<'a>(tree: &Tree, entry: &Entry, name: Row<'a, Message>) -> Row<'a, Message> {
+    let cell = |column: DetailColumn, text: String| {
+        let room = tree.column_width(column).saturating_sub(BUTTON_PADDING) / CHARACTER_WIDTH;
+
+        Text::new(truncate(&text, room as usize))
+            .size(14)
+            .color(style::GUTTER)
+            .width(Length::Units(
+                tree.column_width(column) + column_resize::WIDTH,
+            ))
+    };
+
+    let size = match entry {
+        Entry::File { .. } => tree.size(entry.path()).map(format::size),
+        Entry::Directory { .. } => None,
+    };
+
+    let modified = tree
+        .modified(entry.path())
+        .map(|modified| format::timestamp(modified, ' '));
+
+    Row::new()
+        .align_items(Align::Center)
+        .push(Container::new(name).width(Length::Units(
+            tree.column_width(DetailColumn::Name) + column_resize::WIDTH,
+        )))
+        .push(cell(DetailColumn::Size, size.unwrap_or_default()))
+        .push(cell(DetailColumn::Modified, modified.unwrap_or_default()))
+}
+
+fn grid<'a>(
+    rows: &[TreeRow<'_>],
+    selected: Option<&Path>,
+    buttons: &'a mut Vec<button::State>,
+) -> Element<'a, Message> {
+    let entries: Vec<_> = rows
+        .iter()
+        .filter(|row| row.depth == 0)
+        .map(|row| row.entry)
+        .collect();
+
+    buttons.resize_with(entries.len(), button::State::new);
+
+    let name_room = (GRID_CELL_WIDTH - BUTTON_PADDING) / CHARACTER_WIDTH;
+    let mut buttons = buttons.iter_mut();
+
+    entries
+        .chunks(GRID_COLUMNS)
+        .fold(Column::new().spacing(4), |grid, chunk| {
+            let row = chunk.iter().zip(buttons.by_ref()).fold(
+                Row::new().spacing(4),
+                |row, (entry, button)| {
+                    let icon = match entry {
+                        Entry::Directory { .. } => i18n::tr("DIR").to_string(),
+                        Entry::File { path, .. } => path
+                            .extension()
+                            .map(|extension| extension.to_string_lossy().to_uppercase())
+                            .unwrap_or_else(|| i18n::tr("FILE").to_string()),
+                    };
+
+                    let content = Column::new()
+                        .spacing(4)
+                        .align_items(Align::Center)
+                        .push(Text::new(truncate(&icon, 4)).size(20).color(style::GUTTER))
+                        .push(Text::new(truncate(&entry.name(), name_room as usize)).size(14));
+
+                    let cell = Button::new(button, content)
+                        .width(Length::Units(GRID_CELL_WIDTH))
+                        .padding(5)
+                        .on_press(entry.message());
+
+                    row.push(if selected == Some(entry.path()) {
+                        cell.style(style::Selected)
+                    } else {
+                        cell.style(style::GroupHeader)
+                    })
+                },
+            );
+
+            grid.push(row)
+        })
+        .into()
+}
+
+fn truncate(text: &str, characters: usize) -> String {
+    if text.chars().count() <= characters {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(characters.saturating_sub(1)).collect();
+    truncated.push('…');
+
+    truncated
+}
+
+fn row_width(entry: &Entry, depth: usize) -> u16 {
+    let name = entry.name_length() as u16;
+
+    (depth as u16 * INDENT_WIDTH + TOGGLE_WIDTH)
+        .saturating_add(name.saturating_mul(CHARACTER_WIDTH))
+        .saturating_add(BUTTON_PADDING)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    PathBar,
+    Tree,
+}
+
+pub struct State {
+    tree: Tree,
+    tasks: Tasks,
+    scans: Vec<(tasks::Handle, Scan)>,
+    focus: Option<Focus>,
+    show_indent_guides: bool,
+    age_colors: bool,
+    widest_row: (usize, u16),
+    scrollable: scrollable::State,
+    scroll_positions: HashMap<PathBuf, scrollable::State>,
+    tree_view: tree_view::State,
+    breadcrumb: breadcrumb::State,
+    path_input: text_input::State,
+    completion_buttons: Vec<button::State>,
+    recent_list: pick_list::State<Recent>,
+    recent_filter_list: pick_list::State<RecentFilter>,
+    tag_filter_list: pick_list::State<TagFilter>,
+    horizontal_slider: slider::State,
+    up_button: button::State,
+    trash_button: button::State,
+    expand_all_button: button::State,
+    collapse_all_button: button::State,
+    verify_button: button::State,
+    sort_list: pick_list::State<SortKey>,
+    sort_order_button: button::State,
+    column_headers: [ColumnHeader; DetailColumn::ALL.len()],
+    view_mode_buttons: [button::State; ViewMode::ALL.len()],
+    grid_buttons: Vec<button::State>,
+    statistics_button: button::State,
+    retry_button: button::State,
+    back_button: button::State,
+    pin_buttons: Vec<(button::State, button::State)>,
+    volume_buttons: Vec<button::State>,
+    shortcuts: Vec<(&'static str, PathBuf, button::State)>,
+    filter_buttons: [button::State; Category::ALL.len()],
+    trash_back_button: button::State,
+    trash_scrollable: scrollable::State,
+    trash_rows: Vec<TrashRow>,
+    smart_folder_rows: Vec<SmartFolderRow>,
+    smart_folder_input: text_input::State,
+}
+
+#[derive(Default, Clone)]
+struct SmartFolderRow {
+    toggle_button: button::State,
+    remove_button: button::State,
+    result_buttons: Vec<button::State>,
+}
+
+#[derive(Default)]
+struct ColumnHeader {
+    sort_button: button::State,
+    handle: column_resize::State,
+}
+
+#[derive(Default, Clone)]
+struct TrashRow {
+    restore_button: button::State,
+    delete_button: button::State,
+}
+
+impl State {
+    pub fn new(directory: PathBuf, tasks: Tasks) -> Self {
+        Self {
+            tree: Tree::new(directory, dirs_next::home_dir(), Stored::load()),
+            tasks,
+            scans: vec![],
+            focus: None,
+            show_indent_guides: true,
+            age_colors: false,
+            widest_row: (0, 0),
+            scrollable: scrollable::State::new(),
+            scroll_positions: HashMap::new(),
+            tree_view: tree_view::State::new(),
+            breadcrumb: breadcrumb::State::default(),
+            path_input: text_input::State::new(),
+            completion_buttons: vec![],
+            recent_list: pick_list::State::default(),
+            recent_filter_list: pick_list::State::default(),
+            tag_filter_list: pick_list::State::default(),
+            horizontal_slider: slider::State::new(),
+            up_button: button::State::new(),
+            trash_button: button::State::new(),
+            expand_all_button: button::State::new(),
+            collapse_all_button: button::State::new(),
+            verify_button: button::State::new(),
+            sort_list: pick_list::State::default(),
+            sort_order_button: button::State::new(),
+            column_headers: Default::default(),
+            view_mode_buttons: Default::default(),
+            grid_buttons: vec![],
+            statistics_button: button::State::new(),
+            retry_button: button::State::new(),
+            back_button: button::State::new(),
+            pin_buttons: vec![],
+            volume_buttons: vec![],
+            shortcuts: SHORTCUTS
+                .iter()
+                .filter_map(|(label, resolve)| Some((*label, resolve()?, button::State::new())))
+                .collect(),
+            filter_buttons: Default::default(),
+            trash_back_button: button::State::new(),
+            trash_scrollable: scrollable::State::new(),
+            trash_rows: vec![],
+            smart_folder_rows: vec![],
+            smart_folder_input: text_input::State::new(),
+        }
+    }
+
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    pub fn set_focus(&mut self, focus: Option<Focus>) {
+        self.focus = focus;
+    }
+
+    pub fn reveal(&mut self, path: PathBuf) {
+        self.tree.reveal(path);
+    }
+
+    pub fn navigate_to(&mut self, path: PathBuf) -> Command<Message> {
+        self.update(Message::NavigateTo(path)).0
+    }
+
+    pub fn reveal_path(&mut self, path: PathBuf) -> Command<Message> {
+        self.update(Message::Locate(path, Destination::Reveal)).0
+    }
+
+    pub fn open_path(&mut self, path: PathBuf) -> Command<Message> {
+        self.update(Message::Locate(path, Destination::Open)).0
+    }
+
+    pub fn open_file(&mut self, path: PathBuf) -> Command<Message> {
+        self.update(Message::ReadFile(path)).0
+    }
+
+    pub fn type_ahead(&mut self, character: char) {
+        self.tree.type_ahead(character);
+    }
+
+    pub fn delete_filter_character(&mut self) {
+        self.tree.delete_filter_character();
+    }
+
+    pub fn clear_text_filter(&mut self) -> Command<Message> {
+        perform(self.tree.clear_text_filter())
+    }
+
+    pub fn set_max_file_size(&mut self, max_file_size: u64) {
+        self.tree.set_max_file_size(max_file_size);
+    }
+
+    pub fn set_indent_guides(&mut self, show_indent_guides: bool) {
+        self.show_indent_guides = show_indent_guides;
+    }
+
+    pub fn set_age_colors(&mut self, age_colors: bool) {
+        self.age_colors = age_colors;
+    }
+
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        self.tree.set_reduce_motion(reduce_motion);
+    }
+
+    pub fn set_expand_depth(&mut self, expand_depth: usize) {
+        self.tree.set_expand_depth(expand_depth);
+    }
+
+    pub fn set_grouping(&mut self, grouping: Grouping) {
+        let effect = self.tree.set_grouping(grouping);
+
+        self.track_scans(&effect);
+        self.keep_item_state();
+    }
+
+    pub fn update(&mut self, message: Message) -> (Command<Message>, Option<Event>) {
+        let previous = self.tree.current_dir().to_path_buf();
+        let was_editing = self.tree.path_input().is_some();
+        let completed = matches!(
+            message,
+            Message::AcceptCompletion | Message::PickCompletion(_)
+        );
+        let message = match message {
+            Message::Scanned(scan::Event::Finished(id)) => {
+                let index = self.scans.iter().position(|(task, _)| task.id() == id);
+
+                match index.map(|index| self.scans.remove(index)) {
+                    Some((task, scan)) => Message::ScanFinished(scan.id, task.is_cancelled()),
+                    None => return (Command::none(), None),
+                }
+            }
+            message => message,
+        };
+        let (effect, event) = self.tree.apply(message);
+
+        self.track_scans(&effect);
+
+        if self.tree.path_input().is_some() && (!was_editing || completed) {
+            self.path_input = text_input::State::focused();
+            self.path_input.move_cursor_to_end();
+        }
+
+        if self.tree.current_dir() != previous {
+            let scrollable = self
+                .scroll_positions
+                .remove(self.tree.current_dir())
+                .unwrap_or_default();
+
+            self.scroll_positions
+                .insert(previous, mem::replace(&mut self.scrollable, scrollable));
+            self.tree_view = tree_view::State::new();
+        }
+
+        self.keep_item_state();
+
+        (perform(effect), event)
+    }
+
+    fn track_scans(&mut self, effect: &Effect) {
+        match effect {
+            Effect::StartScan(scan) => {
+                let task = self.tasks.start(scan.label.clone());
+
+                self.scans.push((task, scan.clone()));
+            }
+            Effect::CancelScan(id) => {
+                if let Some(index) = self.scans.iter().position(|(_, scan)| scan.id == *id) {
+                    let (task, _) = self.scans.remove(index);
+
+                    task.cancel();
+                }
+            }
+            Effect::Batch(effects) => {
+                for effect in effects {
+                    self.track_scans(effect);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn keep_item_state(&mut self) {
+        let previous = match self.tree.take_previous_items() {
+            Some(previous) => previous,
+            None => return,
+        };
+
+        let current = self.tree.item_keys();
+
+        self.tree_view.remap(|index| {
+            let key = previous.get(index)?;
+
+            current.iter().position(|current| current == key)
+        });
+    }
+
+    fn scroll_to_reveal(&mut self) {
+        let (index, (visible_top, visible_height)) =
+            match (self.tree.pending_reveal(), self.tree_view.visible()) {
+                (Some(index), Some(visible)) => (index, visible),
+                _ => return,
+            };
+
+        self.tree.finish_reveal();
+
+        if self.tree.view_mode() == ViewMode::Grid {
+            return;
+        }
+
+        let row_height = f32::from(tree_view::ROW_HEIGHT);
+        let top = index as f32 * row_height;
+
+        let delta = if top < visible_top {
+            top - visible_top
+        } else if top + row_height > visible_top + visible_height {
+            top + row_height - visible_top - visible_height
+        } else {
+            return;
+        };
+
+        self.scrollable.scroll(
+            -delta,
+            Rectangle::default(),
+            Rectangle {
+                height: f32::MAX,
+                ..Rectangle::default()
+            },
+        );
+    }
+
+    pub fn refresh_directory(&self) -> iced::Subscription<Message> {
+        let poll = || {
+            iced_futures::time::every(time::Duration::from_secs(1))
+                .map(|_| Message::RefreshDirectory)
+        };
+
+        let directory = match self.tree.listing() {
+            Listing::Loaded { directory, .. } if watcher::is_supported() => directory,
+            _ => return poll(),
+        };
+
+        let (watched, unwatched): (Vec<_>, Vec<_>) = std::iter::once(directory)
+            .chain(self.tree.expanded_directories())
+            .partition(|directory| !self.tree.is_unwatched(directory));
+
+        let watches = watched
+            .into_iter()
+            .map(|directory| watcher::watch(directory.clone()).map(Message::Watched));
+
+        if unwatched.is_empty() {
+            iced::Subscription::batch(watches)
+        } else {
+            iced::Subscription::batch(watches.chain(std::iter::once(poll())))
+        }
+    }
+
+    pub fn watch_volumes(&self) -> iced::Subscription<Message> {
+        iced_futures::time::every(VOLUME_POLL_INTERVAL).map(|_| Message::RefreshVolumes)
+    }
+
+    pub fn git_status_refresh(&self) -> iced::Subscription<Message> {
+        if !self.tree.is_git_status_due() {
+            return iced::Subscription::none();
+        }
+
+        iced_futures::time::every(time::Duration::from_millis(100)).map(Message::GitStatusDue)
+    }
+
+    pub fn animation_frames(&self) -> iced::Subscription<Message> {
+        if !self.tree.is_animating() {
+            return iced::Subscription::none();
+        }
+
+        iced_futures::time::every(time::Duration::from_millis(16)).map(Message::AnimationFrame)
+    }
+
+    pub fn scans(&self) -> iced::Subscription<Message> {
+        let scans = self.scans.iter().map(|(task, scan)| {
+            let (task, paths) = (task.clone(), scan.paths.clone());
+
+            match scan.kind {
+                ScanKind::Sizes => scan::sizes(task, paths),
+                ScanKind::Metadata => scan::metadata(task, paths),
+                ScanKind::Checksums => scan::checksums(task, paths),
+            }
+        });
+
+        iced::Subscription::batch(scans).map(Message::Scanned)
+    }
+}
+
+pub fn list_volumes() -> Command<Message> {
+    perform(Effect::ListVolumes)
+}
+
+fn path_status_color(status: &PathStatus) -> iced::Color {
+    match status {
+        PathStatus::Directory => style::DIFF_ADDED,
+        PathStatus::File => style::LOG_WARN,
+        PathStatus::Missing | PathStatus::Inaccessible(_) => style::ERROR,
+    }
+}
+
+fn perform(effect: Effect) -> Command<Message> {
+    match effect {
+        Effect::None => Command::none(),
+        Effect::ReadDirectory(path) => Command::perform(read_directory(path), |message| message),
+        Effect::Revalidate(directory) => {
+            let path = directory.clone();
+
+            Command::perform(
+                with_timeout(Probe::Directory(path.clone()), move || {
+                    navigation::modified(&path)
+                }),
+                move |result| match result {
+                    Some(modified) => Message::Revalidated(directory.clone(), modified),
+                    None => Message::DirectoryTimedOut(directory.clone()),
+                },
+            )
+        }
+        Effect::ReadEntry(path) => {
+            let read = path.clone();
+
+            Command::perform(
+                with_timeout(Probe::Entry(read.clone()), move || {
+                    navigation::read_entry(&read)
+                }),
+                move |entry| Message::EntryRead(path.clone(), entry.flatten()),
+            )
+        }
+        Effect::Locate(path, destination) => {
+            let located = path.clone();
+
+            Command::perform(check_path(path), move |status| {
+                Message::Located(located.clone(), destination, status)
+            })
+        }
+        Effect::ReadChildren(directory) => {
+            let path = directory.clone();
+
+            Command::perform(
+                with_timeout(Probe::Directory(path.clone()), move || {
+                    navigation::read_directory(path)
+                }),
+                move |result| match result {
+                    Some(result) => Message::ChildrenRead(
+                        directory.clone(),
+                        result.ok().map(|(_, _, entries)| entries),
+                    ),
+                    None => Message::DirectoryTimedOut(directory.clone()),
+                },
+            )
+        }
+        Effect::ReadFile(path, max_size) => {
+            Command::perform(read_file(path, max_size), |message| message)
+        }
+        Effect::ReadGitStatus(directory) => {
+            Command::perform(git::status(directory.clone()), move |status| {
+                Message::GitStatusRead(directory.clone(), status)
+            })
+        }
+        Effect::ListTrash => Command::perform(trash::list(), Message::TrashRead),
+        Effect::RestoreFromTrash(item) => {
+            let original = item.original.clone();
+
+            Command::perform(trash::restore_item(item), move |result| {
+                Message::TrashRestored(original.clone(), result.map(|_| ()))
+            })
+        }
+        Effect::DeleteFromTrash(item) => {
+            let original = item.original.clone();
+
+            Command::perform(trash::delete(item), move |result| {
+                Message::TrashDeleted(original.clone(), result)
+            })
+        }
+        Effect::SavePins(lines) => {
+            Command::perform(storage::save(navigation::PINS, lines), Message::PinsSaved)
+        }
+        Effect::CompletePath(path) => {
+            let typed = path.clone();
+
+            Command::perform(
+                with_timeout(Probe::Completion(path.clone()), move || {
+                    navigation::complete_path(path)
+                }),
+                move |completions| {
+                    Message::PathCompleted(typed.clone(), completions.unwrap_or_default())
+                },
+            )
+        }
+        Effect::CheckPath(path) => {
+            let checked = path.clone();
+
+            Command::perform(check_path(path), move |status| {
+                Message::PathChecked(checked.clone(), status)
+            })
+        }
+        Effect::ReadSpace(directory) => {
+            let path = directory.clone();
+
+            Command::perform(
+                with_timeout(Probe::Space(path.clone()), move || volumes::space(&path)),
+                move |space| Message::SpaceRead(directory.clone(), space.flatten()),
+            )
+        }
+        Effect::ListVolumes => Command::perform(
+            with_timeout(Probe::Volumes, volumes::list),
+            Message::VolumesListed,
+        ),
+        Effect::SaveRecent(lines) => Command::perform(
+            storage::save(navigation::RECENT, lines),
+            Message::RecentSaved,
+        ),
+        Effect::SaveExpanded(lines) => Command::perform(
+            storage::save(navigation::EXPANDED, lines),
+            Message::ExpandedSaved,
+        ),
+        Effect::SaveSorts(lines) => {
+            Command::perform(storage::save(navigation::SORTS, lines), Message::SortsSaved)
+        }
+        Effect::SaveColumns(lines) => Command::perform(
+            storage::save(navigation::COLUMNS, lines),
+            Message::ColumnsSaved,
+        ),
+        Effect::SaveTags(lines) => {
+            Command::perform(storage::save(tags::TAGS, lines), Message::TagsSaved)
+        }
+        Effect::SaveSmartFolders(lines) => Command::perform(
+            storage::save(navigation::SMART_FOLDERS, lines),
+            Message::SmartFoldersSaved,
+        ),
+        Effect::ReadSmartFolder(query) => {
+            let text = query.text.clone();
+
+            Command::perform(smart_folder::read(query), move |paths| {
+                Message::SmartFolderRead(text.clone(), paths)
+            })
+        }
+        Effect::SaveFilterHistory(lines) => Command::perform(
+            storage::save(navigation::FILTER_HISTORY, lines),
+            Message::FilterHistorySaved,
+        ),
+        Effect::StartScan(_) | Effect::CancelScan(_) => Command::none(),
+        Effect::Batch(effects) => Command::batch(effects.into_iter().map(perform)),
+    }
+}
+
+pub fn read_directory(path: PathBuf) -> impl Future<Output = Message> {
+    let directory = path.clone();
+
+    with_timeout(Probe::Directory(path.clone()), move || {
+        navigation::read_directory(path)
+    })
+    .map(move |result| match result {
+        Some(result) => Message::DirectoryRead(result),
+        None => Message::DirectoryTimedOut(directory),
+    })
+}
+
+pub fn check_path(path: PathBuf) -> impl Future<Output = PathStatus> {
+    with_timeout(Probe::Check(path.clone()), move || {
+        navigation::check_path(&path)
+    })
+    .map(|status| {
+        status.unwrap_or_else(|| PathStatus::Inaccessible(i18n::tr("not responding").to_string()))
+    })
+}
+
+fn read_file(path: PathBuf, max_size: u64) -> impl Future<Output = Message> {
+    let file = path.clone();
+
+    with_timeout(Probe::File(path.clone()), move || {
+        navigation::read_file(path, max_size)
+    })
+    .map(move |result| match result {
+        Some(result) => Message::FileRead(result),
+        None => Message::FileTimedOut(file),
+    })
+}
+
+fn guides(depths: impl DoubleEndedIterator<Item = usize>) -> Vec<Vec<bool>> {
+    let mut below = vec![];
+
+    let mut guides: Vec<_> = depths
+        .rev()
+        .map(|depth| {
+            below.resize(depth + 1, false);
+
+            let continues = below[1..].to_vec();
+            below[depth] = true;
+
+            continues
+        })
+        .collect();
+
+    guides.reverse();
+    guides
+}
+
+fn statistics(tree: &Tree) -> Element<'static, Message> {
+    let statistics = tree.statistics();
+    let total: u64 = statistics.iter().map(|statistic| statistic.bytes).sum();
+
+    statistics
+        .iter()
+        .fold(Column::new().spacing(2), |column, statistic| {
+            let label = match statistic.category {
+                Some(category) => category.to_string(),
+                None => i18n::tr("Other").to_string(),
+            };
+
+            let share = if total == 0 {
+                0.0
+            } else {
+                statistic.bytes as f32 / total as f32
+            };
+
+            column.push(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Align::Center)
+                    .push(Text::new(label).size(12).width(Length::Units(70)))
+                    .push(
+                        ProgressBar::new(0.0..=1.0, share)
+                            .width(Length::Units(80))
+                            .height(Length::Units(6)),
+                    )
+                    .push(
+                        Text::new(format!(
+                            "{} · {}",
+                            statistic.count,
+                            format::size(statistic.bytes)
+                        ))
+                        .size(12)
+                        .color(style::GUTTER),
+                    ),
+            )
+        })
+        .into()
+}
+
+fn summary<'a>(tree: &Tree, entries: impl Iterator<Item = &'a Entry>) -> String {
+    let mut count = 0;
+    let mut total = Some(0);
+
+    for entry in entries {
+        count += 1;
+
+        total = total.and_then(|total| Some(total + tree.size(entry.path())?));
+    }
+
+    match (count, total) {
+        (1, Some(total)) => i18n::tr_with("1 item, {}", &[&format::size(total)]),
+        (1, None) => i18n::tr("1 item, calculating size...").to_string(),
+        (count, Some(total)) => i18n::tr_with("{} items, {}", &[&count, &format::size(total)]),
+        (count, None) => i18n::tr_with("{} items, calculating size...", &[&count]),
+    }
+}
@@ -0,0 +1,3357 @@
+use crate::bidi;
+use crate::checksum::{self, Verdict};
+use crate::export;
+use crate::file_type::{self, Category};
+use crate::git;
+use crate::history::History;
+use crate::i18n;
+use crate::scan;
+use crate::smart_folder::{self, Contents};
+use crate::storage;
+use crate::tags::{self, Tag};
+use crate::trash;
+use crate::volumes::{Space, Volume};
+use crate::watcher;
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::time;
+
+const LISTING_CACHE_SIZE: usize = 32;
+const ANIMATION_DURATION: time::Duration = time::Duration::from_millis(150);
+const FLASH_DURATION: time::Duration = time::Duration::from_millis(800);
+const GIT_STATUS_DELAY: time::Duration = time::Duration::from_millis(500);
+const MAX_RECENT: usize = 10;
+const MAX_COMPLETIONS: usize = 12;
+const MIN_COLUMN_WIDTH: f32 = 40.0;
+const MAX_COLUMN_WIDTH: f32 = 800.0;
+pub const PINS: &str = "pins";
+pub const RECENT: &str = "recent";
+pub const FILTER_HISTORY: &str = "filter_history";
+pub const SMART_FOLDERS: &str = "smart_folders";
+pub const EXPANDED: &str = "expanded";
+pub const SORTS: &str = "sorts";
+pub const COLUMNS: &str = "columns";
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ChangeDirectory(PathBuf),
+    Revalidated(PathBuf, Option<time::SystemTime>),
+    NavigateTo(PathBuf),
+    Locate(PathBuf, Destination),
+    Located(PathBuf, Destination, PathStatus),
+    ScanFinished(usize, bool),
+    GoToParent,
+    GoBack,
+    GoForward,
+    OpenInNewWindow(PathBuf),
+    Dropped(PathBuf),
+    Select(PathBuf),
+    SelectPrevious,
+    SelectNext,
+    OpenSelected,
+    DirectoryRead(Result<(PathBuf, Option<time::SystemTime>, Vec<Entry>), ReadError>),
+    DirectoryTimedOut(PathBuf),
+    LeaveUnresponsive,
+    ReadFile(PathBuf),
+    FileRead(Result<(PathBuf, Result<Vec<u8>, u64>), ReadError>),
+    FileTimedOut(PathBuf),
+    RefreshDirectory,
+    OpenTrash,
+    TrashRead(Result<Vec<trash::Item>, String>),
+    RestoreFromTrash(trash::Item),
+    DeleteFromTrash(trash::Item),
+    TrashRestored(PathBuf, Result<(), String>),
+    TrashDeleted(PathBuf, Result<(), String>),
+    ToggleFilter(Category),
+    VerifyChecksums,
+    HideLargeFiles(bool),
+    HideBinaryFiles(bool),
+    HideHiddenFiles(bool),
+    CycleTag(PathBuf),
+    FilterByTag(Option<Tag>),
+    TagsSaved(Result<(), String>),
+    Scanned(scan::Event),
+    Watched(watcher::Change),
+    GroupByType(bool),
+    SortBy(SortKey),
+    ReverseSort,
+    SortsSaved(Result<(), String>),
+    SetViewMode(ViewMode),
+    ResizeColumn(DetailColumn, f32),
+    FinishColumnResize,
+    ColumnsSaved(Result<(), String>),
+    ToggleGroup(&'static str),
+    ToggleStatistics,
+    ToggleExpanded(PathBuf),
+    ChildrenRead(PathBuf, Option<Vec<Entry>>),
+    EntryRead(PathBuf, Option<(Entry, fs::Metadata)>),
+    ExpandAll,
+    CollapseAll,
+    ScrolledHorizontally(u16),
+    TreeScrolled,
+    AnimationFrame(time::Instant),
+    GitStatusRead(PathBuf, Option<git::Status>),
+    GitStatusDue(time::Instant),
+    TogglePin(PathBuf),
+    OpenPinned(PathBuf),
+    PinsSaved(Result<(), String>),
+    SmartFolderInputChanged(String),
+    AddSmartFolder,
+    RemoveSmartFolder(String),
+    ToggleSmartFolder(String),
+    SmartFolderRead(String, Vec<PathBuf>),
+    SmartFoldersSaved(Result<(), String>),
+    RecentSaved(Result<(), String>),
+    ExpandedSaved(Result<(), String>),
+    RecallFilter(String),
+    FilterHistorySaved(Result<(), String>),
+    RefreshVolumes,
+    SpaceRead(PathBuf, Option<Space>),
+    VolumesListed(Option<Vec<Volume>>),
+    EditPath,
+    PathInputChanged(String),
+    DeselectPathInput,
+    SubmitPath,
+    CancelPathInput,
+    PathCompleted(PathBuf, Vec<PathBuf>),
+    NextCompletion,
+    PreviousCompletion,
+    AcceptCompletion,
+    PickCompletion(PathBuf),
+    PathChecked(PathBuf, PathStatus),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanKind {
+    Sizes,
+    Metadata,
+    Checksums,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scan {
+    pub id: usize,
+    pub kind: ScanKind,
+    pub label: String,
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub struct Stored {
+    pub tags: Vec<String>,
+    pub sorts: Vec<String>,
+    pub columns: Vec<String>,
+    pub expanded: Vec<String>,
+    pub pins: Vec<String>,
+    pub smart_folders: Vec<String>,
+    pub recent: Vec<String>,
+    pub filter_history: Vec<String>,
+}
+
+impl Stored {
+    pub fn load() -> Self {
+        Self {
+            tags: storage::load(tags::TAGS),
+            sorts: storage::load(SORTS),
+            columns: storage::load(COLUMNS),
+            expanded: storage::load(EXPANDED),
+            pins: storage::load(PINS),
+            smart_folders: storage::load(SMART_FOLDERS),
+            recent: storage::load(RECENT),
+            filter_history: storage::load(FILTER_HISTORY),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Destination {
+    Navigate,
+    Reveal,
+    Open,
+    Pinned,
+    Submitted,
+    Back,
+    Forward,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathStatus {
+    Directory,
+    File,
+    Missing,
+    Inaccessible(String),
+}
+
+impl fmt::Display for PathStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathStatus::Directory => f.write_str(i18n::tr("Directory")),
+            PathStatus::File => f.write_str(i18n::tr("File (its folder will open)")),
+            PathStatus::Missing => f.write_str(i18n::tr("Does not exist")),
+            PathStatus::Inaccessible(error) => {
+                f.write_str(&i18n::tr_with("Cannot access: {}", &[error]))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    FileRead(PathBuf, Vec<u8>),
+    FileTooLarge(PathBuf, u64),
+    OpenInNewWindow(PathBuf),
+    Dropped { from: PathBuf, into: PathBuf },
+    Restored(PathBuf, Result<(), String>),
+    DeletedFromTrash(PathBuf, Result<(), String>),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    None,
+    ReadDirectory(PathBuf),
+    Revalidate(PathBuf),
+    ReadChildren(PathBuf),
+    ReadEntry(PathBuf),
+    Locate(PathBuf, Destination),
+    ReadFile(PathBuf, u64),
+    ReadGitStatus(PathBuf),
+    ListTrash,
+    RestoreFromTrash(trash::Item),
+    DeleteFromTrash(trash::Item),
+    SavePins(Vec<String>),
+    SaveTags(Vec<String>),
+    SaveSmartFolders(Vec<String>),
+    ReadSmartFolder(smart_folder::Query),
+    SaveRecent(Vec<String>),
+    SaveExpanded(Vec<String>),
+    SaveSorts(Vec<String>),
+    SaveColumns(Vec<String>),
+    SaveFilterHistory(Vec<String>),
+    ListVolumes,
+    ReadSpace(PathBuf),
+    CompletePath(PathBuf),
+    CheckPath(PathBuf),
+    StartScan(Scan),
+    CancelScan(usize),
+    Batch(Vec<Effect>),
+}
+
+#[derive(Debug, Clone)]
+pub enum ReadError {
+    NotADirectory(PathBuf),
+    NotAFile(PathBuf),
+    Io(PathBuf, String),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ReadError::NotADirectory(path) => {
+                i18n::tr_with("{} is no longer a directory", &[&format!("{:?}", path)])
+            }
+            ReadError::NotAFile(path) => {
+                i18n::tr_with("{} is no longer a file", &[&format!("{:?}", path)])
+            }
+            ReadError::Io(path, error) => {
+                i18n::tr_with("Cannot read {}: {}", &[&format!("{:?}", path), error])
+            }
+        };
+
+        f.write_str(&message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    File {
+        path: PathBuf,
+        name: String,
+        executable: bool,
+    },
+    Directory {
+        path: PathBuf,
+        name: String,
+    },
+}
+
+impl Entry {
+    fn new(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_string();
+
+        let metadata = fs::metadata(&path).ok()?;
+
+        Self::with_metadata(path, name, &metadata)
+    }
+
+    fn with_metadata(path: PathBuf, name: String, metadata: &fs::Metadata) -> Option<Self> {
+        if metadata.is_file() {
+            let executable = is_executable(&path, metadata);
+
+            Some(Entry::File {
+                path,
+                name,
+                executable,
+            })
+        } else if metadata.is_dir() {
+            Some(Entry::Directory { path, name })
+        } else {
+            None
+        }
+    }
+
+    fn listed(entry: fs::DirEntry) -> Option<Self> {
+        let file_type = entry.file_type().ok()?;
+        let path = entry.path();
+
+        if file_type.is_symlink() {
+            return Entry::new(path);
+        }
+
+        let name = path.file_name()?.to_string_lossy().to_string();
+
+        if file_type.is_file() {
+            Some(Entry::File {
+                path,
+                name,
+                executable: false,
+            })
+        } else if file_type.is_dir() {
+            Some(Entry::Directory { path, name })
+        } else {
+            None
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Entry::File { path, .. } | Entry::Directory { path, .. } => path,
+        }
+    }
+
+    fn file_name(&self) -> &str {
+        match self {
+            Entry::File { name, .. } | Entry::Directory { name, .. } => name,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        let name = match self {
+            Entry::File {
+                name,
+                executable: true,
+                ..
+            } => format!("X - {}", name),
+            Entry::File { name, .. } => format!("F - {}", name),
+            Entry::Directory { name, .. } => format!("D - {}", name),
+        };
+
+        bidi::display(&name, i18n::is_right_to_left())
+    }
+
+    pub fn name_length(&self) -> usize {
+        self.file_name().chars().count() + 4
+    }
+
+    pub fn is_executable(&self) -> bool {
+        matches!(
+            self,
+            Entry::File {
+                executable: true,
+                ..
+            }
+        )
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Entry::File { path, .. } => file_type::type_name(path, false),
+            Entry::Directory { path, .. } => file_type::type_name(path, true),
+        }
+    }
+
+    fn category(&self) -> Option<Category> {
+        match self {
+            Entry::File { path, .. } => Category::of(path, false),
+            Entry::Directory { path, .. } => Category::of(path, true),
+        }
+    }
+
+    pub fn message(&self) -> Message {
+        match self {
+            Entry::File { path, .. } => Message::ReadFile(path.clone()),
+            Entry::Directory { path, .. } => Message::ChangeDirectory(path.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Grouping {
+    #[default]
+    DirectoriesFirst,
+    DirectoriesLast,
+    Mixed,
+}
+
+impl Grouping {
+    pub const ALL: [Grouping; 3] = [
+        Grouping::DirectoriesFirst,
+        Grouping::DirectoriesLast,
+        Grouping::Mixed,
+    ];
+
+    fn compare(self, a: &Entry, b: &Entry) -> Ordering {
+        let by_name = a.file_name().cmp(b.file_name());
+
+        match self {
+            Grouping::Mixed => by_name.then(directories_first(a, b)),
+            _ => self.compare_kinds(a, b).then(by_name),
+        }
+    }
+
+    fn compare_kinds(self, a: &Entry, b: &Entry) -> Ordering {
+        match self {
+            Grouping::DirectoriesFirst => directories_first(a, b),
+            Grouping::DirectoriesLast => directories_first(a, b).reverse(),
+            Grouping::Mixed => Ordering::Equal,
+        }
+    }
+}
+
+impl fmt::Display for Grouping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Grouping::DirectoriesFirst => "Directories first",
+            Grouping::DirectoriesLast => "Directories last",
+            Grouping::Mixed => "Mixed",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    #[default]
+    Name,
+    Size,
+    Modified,
+    Type,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 4] = [
+        SortKey::Name,
+        SortKey::Size,
+        SortKey::Modified,
+        SortKey::Type,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Size => "size",
+            SortKey::Modified => "modified",
+            SortKey::Type => "type",
+        }
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SortKey::Name => "Name",
+            SortKey::Size => "Size",
+            SortKey::Modified => "Modified",
+            SortKey::Type => "Type",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ViewMode {
+    #[default]
+    List,
+    Details,
+    Grid,
+}
+
+impl ViewMode {
+    pub const ALL: [ViewMode; 3] = [ViewMode::List, ViewMode::Details, ViewMode::Grid];
+}
+
+impl fmt::Display for ViewMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ViewMode::List => "List",
+            ViewMode::Details => "Details",
+            ViewMode::Grid => "Grid",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailColumn {
+    Name,
+    Size,
+    Modified,
+}
+
+impl DetailColumn {
+    pub const ALL: [DetailColumn; 3] = [
+        DetailColumn::Name,
+        DetailColumn::Size,
+        DetailColumn::Modified,
+    ];
+
+    pub fn sort_key(self) -> SortKey {
+        match self {
+            DetailColumn::Name => SortKey::Name,
+            DetailColumn::Size => SortKey::Size,
+            DetailColumn::Modified => SortKey::Modified,
+        }
+    }
+
+    fn default_width(self) -> f32 {
+        match self {
+            DetailColumn::Name => 240.0,
+            DetailColumn::Size => 90.0,
+            DetailColumn::Modified => 170.0,
+        }
+    }
+}
+
+impl fmt::Display for DetailColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.sort_key().fmt(f)
+    }
+}
+
+pub struct Tree {
+    listing: Listing,
+    selected: Option<PathBuf>,
+    filter: Filter,
+    tags: HashMap<PathBuf, Tag>,
+    tag_filter: Option<Tag>,
+    sizes: HashMap<PathBuf, u64>,
+    size_scan: Option<(usize, Vec<PathBuf>)>,
+    executables: HashMap<PathBuf, bool>,
+    modified: HashMap<PathBuf, time::SystemTime>,
+    metadata_scans: Vec<(usize, Vec<PathBuf>)>,
+    verdicts: HashMap<PathBuf, Verdict>,
+    checksum_scan: Option<(usize, Vec<PathBuf>)>,
+    next_scan: usize,
+    scan_effects: Vec<Effect>,
+    listing_cache: HashMap<PathBuf, CachedListing>,
+    unwatched: HashSet<PathBuf>,
+    grouping: Grouping,
+    sort: Sort,
+    remembered_sorts: HashMap<PathBuf, Sort>,
+    view_mode: ViewMode,
+    column_widths: [f32; DetailColumn::ALL.len()],
+    group_by_type: bool,
+    show_statistics: bool,
+    collapsed_groups: HashSet<&'static str>,
+    git_status: Option<git::Status>,
+    git_status_due: Option<time::Instant>,
+    git_status_in_flight: bool,
+    horizontal_offset: u16,
+    expanded: HashMap<PathBuf, Option<Vec<Entry>>>,
+    remembered_expansions: HashMap<PathBuf, Vec<PathBuf>>,
+    expand_depth: usize,
+    reduce_motion: bool,
+    animation: Option<Animation>,
+    pending_reveal: Option<PathBuf>,
+    flash: Option<(PathBuf, time::Instant)>,
+    back: Vec<PathBuf>,
+    forward: Vec<PathBuf>,
+    traveling: bool,
+    max_file_size: u64,
+    expand_all_limit: Option<usize>,
+    previous_items: Option<Vec<ItemKey>>,
+    pinned: Vec<PathBuf>,
+    smart_folders: Vec<(smart_folder::Query, Contents)>,
+    smart_folder_input: String,
+    recent: Vec<PathBuf>,
+    filter_history: History,
+    volumes: Vec<Volume>,
+    volumes_in_flight: bool,
+    space: Option<Space>,
+    space_in_flight: HashSet<PathBuf>,
+    path_input: Option<String>,
+    path_input_selected: bool,
+    completions: Vec<PathBuf>,
+    completion: Option<usize>,
+    path_status: Option<PathStatus>,
+    home: Option<PathBuf>,
+    updating: bool,
+    row_cache: RefCell<Option<Vec<RowPosition>>>,
+    row_generation: Cell<usize>,
+}
+
+pub enum Listing {
+    Loading(PathBuf),
+    Loaded {
+        directory: PathBuf,
+        entries: Vec<Entry>,
+    },
+    NotResponding {
+        directory: PathBuf,
+        previous: Option<Box<Listing>>,
+    },
+    Trash {
+        return_to: PathBuf,
+        items: Vec<trash::Item>,
+        confirm_delete: Option<PathBuf>,
+    },
+}
+
+pub struct TreeRow<'a> {
+    pub entry: &'a Entry,
+    pub depth: usize,
+    pub group: &'static str,
+}
+
+struct RowPosition {
+    parent: Option<usize>,
+    index: usize,
+    depth: usize,
+    group: &'static str,
+}
+
+pub struct Statistic {
+    pub category: Option<Category>,
+    pub count: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ItemKey {
+    Group(&'static str),
+    Entry(PathBuf),
+}
+
+struct Animation {
+    directory: PathBuf,
+    collapsing: bool,
+    started: time::Instant,
+}
+
+impl Animation {
+    fn new(directory: PathBuf, collapsing: bool) -> Self {
+        Self {
+            directory,
+            collapsing,
+            started: time::Instant::now(),
+        }
+    }
+
+    fn progress(&self, now: time::Instant) -> f32 {
+        let elapsed = now.saturating_duration_since(self.started);
+
+        (elapsed.as_secs_f32() / ANIMATION_DURATION.as_secs_f32()).min(1.0)
+    }
+
+    fn fraction(&self) -> f32 {
+        let eased = 1.0 - (1.0 - self.progress(time::Instant::now())).powi(2);
+
+        if self.collapsing {
+            1.0 - eased
+        } else {
+            eased
+        }
+    }
+}
+
+struct CachedListing {
+    modified: time::SystemTime,
+    entries: Vec<Entry>,
+    used: time::Instant,
+}
+
+impl Tree {
+    pub fn new(directory: PathBuf, home: Option<PathBuf>, stored: Stored) -> Self {
+        Self {
+            listing: Listing::Loading(directory),
+            selected: None,
+            filter: Filter::default(),
+            tags: tags::parse(&stored.tags),
+            tag_filter: None,
+            sizes: HashMap::new(),
+            size_scan: None,
+            executables: HashMap::new(),
+            modified: HashMap::new(),
+            metadata_scans: vec![],
+            verdicts: HashMap::new(),
+            checksum_scan: None,
+            next_scan: 0,
+            scan_effects: vec![],
+            listing_cache: HashMap::new(),
+            unwatched: HashSet::new(),
+            grouping: Grouping::default(),
+            sort: Sort::default(),
+            remembered_sorts: parse_sorts(&stored.sorts),
+            view_mode: ViewMode::default(),
+            column_widths: parse_column_widths(&stored.columns),
+            group_by_type: false,
+            show_statistics: false,
+            collapsed_groups: HashSet::new(),
+            git_status: None,
+            git_status_due: None,
+            git_status_in_flight: false,
+            horizontal_offset: 0,
+            expanded: HashMap::new(),
+            remembered_expansions: parse_expansions(&stored.expanded),
+            expand_depth: 3,
+            reduce_motion: false,
+            animation: None,
+            pending_reveal: None,
+            flash: None,
+            back: vec![],
+            forward: vec![],
+            traveling: false,
+            max_file_size: 10 * 1024 * 1024,
+            expand_all_limit: None,
+            previous_items: None,
+            pinned: stored.pins.into_iter().map(PathBuf::from).collect(),
+            smart_folders: stored
+                .smart_folders
+                .iter()
+                .filter_map(|line| smart_folder::Query::parse(line).ok())
+                .map(|query| (query, Contents::Collapsed))
+                .collect(),
+            smart_folder_input: String::new(),
+            recent: stored.recent.into_iter().map(PathBuf::from).collect(),
+            filter_history: History::new(stored.filter_history),
+            volumes: vec![],
+            volumes_in_flight: false,
+            space: None,
+            space_in_flight: HashSet::new(),
+            path_input: None,
+            path_input_selected: false,
+            completions: vec![],
+            completion: None,
+            path_status: None,
+            home,
+            updating: false,
+            row_cache: RefCell::new(None),
+            row_generation: Cell::new(0),
+        }
+    }
+
+    pub fn listing(&self) -> &Listing {
+        &self.listing
+    }
+
+    pub fn current_dir(&self) -> &Path {
+        match &self.listing {
+            Listing::Loading(directory)
+            | Listing::Loaded { directory, .. }
+            | Listing::NotResponding { directory, .. }
+            | Listing::Trash {
+                return_to: directory,
+                ..
+            } => directory,
+        }
+    }
+
+    pub fn export_records(&self) -> Vec<export::Record> {
+        self.visible_entries()
+            .into_iter()
+            .map(|entry| export::Record {
+                path: entry.path().to_path_buf(),
+                is_dir: matches!(entry, Entry::Directory { .. }),
+                type_name: entry.type_name(),
+                size: self.sizes.get(entry.path()).copied(),
+            })
+            .collect()
+    }
+
+    pub fn selected(&self) -> Option<&Path> {
+        self.selected.as_deref()
+    }
+
+    pub fn selected_modified(&self) -> Option<&Path> {
+        let selected = self.selected.as_deref()?;
+        let status = self.git_status.as_ref()?;
+
+        status.modified.contains(selected).then_some(selected)
+    }
+
+    pub fn selected_executable(&self) -> Option<&Path> {
+        self.selected_entry()
+            .filter(|entry| entry.is_executable())
+            .map(Entry::path)
+    }
+
+    pub fn git_status(&self) -> Option<&git::Status> {
+        self.git_status.as_ref()
+    }
+
+    pub fn pinned(&self) -> &[PathBuf] {
+        &self.pinned
+    }
+
+    pub fn smart_folders(&self) -> &[(smart_folder::Query, Contents)] {
+        &self.smart_folders
+    }
+
+    pub fn smart_folder_input(&self) -> &str {
+        &self.smart_folder_input
+    }
+
+    pub fn recent(&self) -> &[PathBuf] {
+        &self.recent
+    }
+
+    pub fn volumes(&self) -> &[Volume] {
+        &self.volumes
+    }
+
+    pub fn space(&self) -> Option<Space> {
+        self.space
+    }
+
+    pub fn path_input(&self) -> Option<&str> {
+        self.path_input.as_deref()
+    }
+
+    pub fn is_path_input_selected(&self) -> bool {
+        self.path_input.is_some() && self.path_input_selected
+    }
+
+    pub fn completions(&self) -> &[PathBuf] {
+        &self.completions
+    }
+
+    pub fn path_status(&self) -> Option<&PathStatus> {
+        self.path_status.as_ref()
+    }
+
+    pub fn completion(&self) -> Option<&Path> {
+        self.completions.get(self.completion?).map(PathBuf::as_path)
+    }
+
+    pub fn resolved_path_input(&self) -> Option<PathBuf> {
+        let input = self.path_input.as_ref()?;
+
+        Some(
+            self.current_dir()
+                .join(expand_path(input, self.home.as_deref())),
+        )
+    }
+
+    pub fn size(&self, path: &Path) -> Option<u64> {
+        self.sizes.get(path).copied()
+    }
+
+    pub fn modified(&self, path: &Path) -> Option<time::SystemTime> {
+        self.modified.get(path).copied()
+    }
+
+    pub fn verdict(&self, path: &Path) -> Option<Verdict> {
+        self.verdicts.get(path).copied()
+    }
+
+    pub fn missing_checksums(&self) -> usize {
+        self.verdicts
+            .values()
+            .filter(|verdict| **verdict == Verdict::Missing)
+            .count()
+    }
+
+    pub fn manifests(&self) -> Vec<&Path> {
+        self.entries()
+            .iter()
+            .filter(|entry| matches!(entry, Entry::File { .. }))
+            .filter(|entry| checksum::is_manifest(entry.file_name()))
+            .map(Entry::path)
+            .collect()
+    }
+
+    pub fn is_git_status_due(&self) -> bool {
+        self.git_status_due.is_some()
+    }
+
+    pub fn is_unwatched(&self, directory: &Path) -> bool {
+        self.unwatched.contains(directory)
+    }
+
+    pub fn expanded_directories(&self) -> impl Iterator<Item = &PathBuf> {
+        self.expanded.keys()
+    }
+
+    pub fn horizontal_offset(&self) -> u16 {
+        self.horizontal_offset
+    }
+
+    pub fn group_by_type(&self) -> bool {
+        self.group_by_type
+    }
+
+    pub fn shows_statistics(&self) -> bool {
+        self.show_statistics
+    }
+
+    pub fn is_group_collapsed(&self, group: &str) -> bool {
+        self.collapsed_groups.contains(group)
+    }
+
+    pub fn is_expanded(&self, directory: &Path) -> bool {
+        let collapsing = self
+            .animation
+            .as_ref()
+            .is_some_and(|animation| animation.collapsing && animation.directory == directory);
+
+        self.expanded.contains_key(directory) && !collapsing
+    }
+
+    pub fn animation(&self) -> Option<(&Path, f32)> {
+        self.animation
+            .as_ref()
+            .map(|animation| (animation.directory.as_path(), animation.fraction()))
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some() || self.flash.is_some()
+    }
+
+    pub fn flash(&self) -> Option<(&Path, f32)> {
+        self.flash.as_ref().map(|(path, started)| {
+            let elapsed = started.elapsed().as_secs_f32() / FLASH_DURATION.as_secs_f32();
+
+            (path.as_path(), 1.0 - elapsed.min(1.0))
+        })
+    }
+
+    pub fn matches(&self, entry: &Entry) -> bool {
+        let too_large = self.filter.hide_large
+            && matches!(entry, Entry::File { .. })
+            && self
+                .sizes
+                .get(entry.path())
+                .is_some_and(|size| *size > self.max_file_size);
+
+        let by_tag = self
+            .tag_filter
+            .is_none_or(|tag| self.tags.get(entry.path()) == Some(&tag));
+
+        self.filter.matches(entry) && !too_large && by_tag
+    }
+
+    pub fn tag(&self, path: &Path) -> Option<Tag> {
+        self.tags.get(path).copied()
+    }
+
+    pub fn has_tags(&self) -> bool {
+        !self.tags.is_empty()
+    }
+
+    pub fn tag_filter(&self) -> Option<Tag> {
+        self.tag_filter
+    }
+
+    pub fn hides_large_files(&self) -> bool {
+        self.filter.hide_large
+    }
+
+    pub fn hides_binary_files(&self) -> bool {
+        self.filter.hide_binary
+    }
+
+    pub fn sort(&self) -> Sort {
+        self.sort
+    }
+
+    pub fn view_mode(&self) -> ViewMode {
+        self.view_mode
+    }
+
+    pub fn column_width(&self, column: DetailColumn) -> u16 {
+        self.column_widths[column as usize].round() as u16
+    }
+
+    pub fn hides_hidden_files(&self) -> bool {
+        self.filter.hide_hidden
+    }
+
+    pub fn is_filtered_by(&self, category: Category) -> bool {
+        self.filter.categories.contains(&category)
+    }
+
+    pub fn filter_text(&self) -> &str {
+        &self.filter.text
+    }
+
+    pub fn reveal(&mut self, path: PathBuf) {
+        self.selected = Some(path.clone());
+        self.pending_reveal = Some(path.clone());
+        self.flash = Some((path, time::Instant::now()));
+    }
+
+    pub fn pending_reveal(&mut self) -> Option<usize> {
+        let path = self.pending_reveal.clone()?;
+
+        if self.selected.as_ref() != Some(&path) {
+            self.pending_reveal = None;
+
+            return None;
+        }
+
+        self.item_index(&path)
+    }
+
+    pub fn finish_reveal(&mut self) {
+        self.pending_reveal = None;
+    }
+
+    pub fn has_text_filter(&self) -> bool {
+        !self.filter.text.is_empty()
+    }
+
+    pub fn type_ahead(&mut self, character: char) {
+        self.update(|tree| {
+            if let Listing::Loaded { .. } = tree.listing {
+                tree.filter.text.push(character);
+                tree.select_first_match();
+            }
+        })
+    }
+
+    pub fn delete_filter_character(&mut self) {
+        self.update(|tree| {
+            tree.filter.text.pop();
+            tree.select_first_match();
+        })
+    }
+
+    pub fn clear_text_filter(&mut self) -> Effect {
+        self.update(|tree| {
+            let effect = tree.remember_filter();
+
+            tree.filter.text.clear();
+
+            effect
+        })
+    }
+
+    pub fn filter_history(&self) -> &[String] {
+        self.filter_history.queries()
+    }
+
+    fn save_smart_folders(&self) -> Effect {
+        let lines = self
+            .smart_folders
+            .iter()
+            .map(|(query, _)| query.text.clone())
+            .collect();
+
+        Effect::SaveSmartFolders(lines)
+    }
+
+    fn remember_filter(&mut self) -> Effect {
+        match self.filter_history.record(&self.filter.text) {
+            Some(lines) => Effect::SaveFilterHistory(lines),
+            None => Effect::None,
+        }
+    }
+
+    fn remember_expanded(&mut self) -> Effect {
+        let root = match &self.listing {
+            Listing::Loaded { directory, .. } => directory.clone(),
+            _ => return Effect::None,
+        };
+
+        let collapsing = self
+            .animation
+            .as_ref()
+            .filter(|animation| animation.collapsing)
+            .map(|animation| animation.directory.as_path());
+
+        let mut expanded: Vec<_> = self
+            .expanded
+            .keys()
+            .filter(|path| collapsing.is_none_or(|collapsing| !path.starts_with(collapsing)))
+            .cloned()
+            .collect();
+
+        expanded.sort();
+
+        let remembered = self.remembered_expansions.get(&root);
+
+        if remembered.map_or(expanded.is_empty(), |remembered| *remembered == expanded) {
+            return Effect::None;
+        }
+
+        if expanded.is_empty() {
+            self.remembered_expansions.remove(&root);
+        } else {
+            self.remembered_expansions.insert(root, expanded);
+        }
+
+        let mut lines: Vec<_> = self
+            .remembered_expansions
+            .iter()
+            .flat_map(|(root, expanded)| {
+                expanded.iter().map(move |path| {
+                    format!("{}\t{}", root.to_string_lossy(), path.to_string_lossy())
+                })
+            })
+            .collect();
+
+        lines.sort();
+
+        Effect::SaveExpanded(lines)
+    }
+
+    fn remember_sort(&mut self) -> Effect {
+        let root = self.current_dir().to_path_buf();
+
+        if self.sort == Sort::default() {
+            self.remembered_sorts.remove(&root);
+        } else {
+            self.remembered_sorts.insert(root, self.sort);
+        }
+
+        let mut lines: Vec<_> = self
+            .remembered_sorts
+            .iter()
+            .map(|(path, sort)| {
+                format!(
+                    "{}\t{}\t{}",
+                    sort.key.key(),
+                    if sort.descending { "desc" } else { "asc" },
+                    path.to_string_lossy()
+                )
+            })
+            .collect();
+
+        lines.sort();
+
+        Effect::SaveSorts(lines)
+    }
+
+    fn column_lines(&self) -> Vec<String> {
+        DetailColumn::ALL
+            .iter()
+            .map(|column| {
+                format!(
+                    "{}\t{}",
+                    column.sort_key().key(),
+                    self.column_width(*column)
+                )
+            })
+            .collect()
+    }
+
+    fn compare_sorted(&self, a: &Entry, b: &Entry) -> Ordering {
+        let by_key = match self.sort.key {
+            SortKey::Name => a.file_name().cmp(b.file_name()),
+            SortKey::Size => self.size(a.path()).cmp(&self.size(b.path())),
+            SortKey::Modified => self.modified(a.path()).cmp(&self.modified(b.path())),
+            SortKey::Type => extension(a).cmp(&extension(b)),
+        };
+
+        let by_key = if self.sort.descending {
+            by_key.reverse()
+        } else {
+            by_key
+        };
+
+        self.grouping
+            .compare_kinds(a, b)
+            .then(by_key)
+            .then_with(|| a.file_name().cmp(b.file_name()))
+    }
+
+    fn schedule_git_status(&mut self) {
+        self.git_status_due
+            .get_or_insert_with(|| time::Instant::now() + GIT_STATUS_DELAY);
+    }
+
+    fn read_space(&mut self, directory: PathBuf) -> Option<Effect> {
+        self.space_in_flight
+            .insert(directory.clone())
+            .then_some(Effect::ReadSpace(directory))
+    }
+
+    fn select_first_match(&mut self) {
+        let selected_matches = self
+            .selected_entry()
+            .is_some_and(|entry| self.matches(entry));
+
+        if !selected_matches {
+            self.selected = self
+                .visible_entries()
+                .first()
+                .map(|entry| entry.path().to_path_buf());
+        }
+    }
+
+    pub fn set_max_file_size(&mut self, max_file_size: u64) {
+        self.update(|tree| tree.max_file_size = max_file_size)
+    }
+
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        self.reduce_motion = reduce_motion;
+
+        if reduce_motion {
+            self.finish_animation();
+        }
+    }
+
+    pub fn set_expand_depth(&mut self, expand_depth: usize) {
+        self.expand_depth = expand_depth;
+    }
+
+    pub fn set_grouping(&mut self, grouping: Grouping) -> Effect {
+        if self.grouping == grouping {
+            return Effect::None;
+        }
+
+        self.update(|tree| {
+            tree.grouping = grouping;
+
+            for children in tree.expanded.values_mut().flatten() {
+                children.sort_by(|a, b| grouping.compare(a, b));
+            }
+
+            if let Listing::Loaded {
+                directory, entries, ..
+            } = &tree.listing
+            {
+                let (directory, entries) = (directory.clone(), entries.clone());
+
+                tree.show_directory(directory, entries);
+            }
+
+            Effect::Batch(mem::take(&mut tree.scan_effects))
+        })
+    }
+
+    pub fn apply(&mut self, message: Message) -> (Effect, Option<Event>) {
+        let (effect, event) = self.update(|tree| tree.handle(message));
+
+        if self.scan_effects.is_empty() {
+            return (effect, event);
+        }
+
+        let mut effects = mem::take(&mut self.scan_effects);
+        effects.push(effect);
+
+        (Effect::Batch(effects), event)
+    }
+
+    fn update<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.updating = true;
+        self.row_cache.get_mut().take();
+
+        let result = f(self);
+
+        self.updating = false;
+        self.row_cache.get_mut().take();
+
+        result
+    }
+
+    fn handle(&mut self, message: Message) -> (Effect, Option<Event>) {
+        match message {
+            Message::ChangeDirectory(path) => {
+                if self.listing_cache.contains_key(&path) {
+                    return (Effect::Revalidate(path), None);
+                }
+
+                return (Effect::ReadDirectory(path), None);
+            }
+            Message::Revalidated(path, modified) => {
+                if let Some(entries) = self.cached_entries(&path, modified) {
+                    return (self.visit(path, entries), None);
+                }
+
+                return (Effect::ReadDirectory(path), None);
+            }
+            Message::NavigateTo(path) => {
+                return (Effect::Locate(path, Destination::Navigate), None);
+            }
+            Message::Locate(path, destination) => {
+                return (Effect::Locate(path, destination), None);
+            }
+            Message::Located(path, destination, status) => {
+                return self.located(path, destination, status);
+            }
+            Message::GoToParent => {
+                if let Listing::Loaded { directory, .. } = &self.listing {
+                    if let Some(parent) = directory.parent() {
+                        return self.handle(Message::ChangeDirectory(parent.to_path_buf()));
+                    }
+                }
+            }
+            Message::GoBack => {
+                if let Some(path) = self.back.pop() {
+                    return (Effect::Locate(path, Destination::Back), None);
+                }
+            }
+            Message::GoForward => {
+                if let Some(path) = self.forward.pop() {
+                    return (Effect::Locate(path, Destination::Forward), None);
+                }
+            }
+            Message::OpenInNewWindow(path) => {
+                return (Effect::None, Some(Event::OpenInNewWindow(path)));
+            }
+            Message::Dropped(from) => {
+                if let Listing::Loaded { directory, .. } = &self.listing {
+                    if directory.starts_with(&from) {
+                        let error =
+                            i18n::tr_with("Cannot copy {} into itself", &[&format!("{:?}", from)]);
+
+                        return (Effect::None, Some(Event::Failed(error)));
+                    }
+
+                    if from.parent() != Some(directory.as_path()) {
+                        let into = directory.clone();
+
+                        return (Effect::None, Some(Event::Dropped { from, into }));
+                    }
+                }
+            }
+            Message::DirectoryRead(result) => match result {
+                Ok((directory, modified, entries)) => {
+                    if let Some(modified) = modified {
+                        self.cache_entries(directory.clone(), modified, entries.clone());
+                    }
+
+                    return (self.visit(directory, entries), None);
+                }
+                Err(error) => {
+                    self.traveling = false;
+
+                    return (Effect::None, Some(Event::Failed(error.to_string())));
+                }
+            },
+            Message::DirectoryTimedOut(directory) => {
+                let error = i18n::tr_with("{} is not responding", &[&format!("{:?}", directory)]);
+
+                if self.expanded.remove(&directory).is_some() {
+                    return (Effect::None, Some(Event::Failed(error)));
+                }
+
+                let previous =
+                    match mem::replace(&mut self.listing, Listing::Loading(directory.clone())) {
+                        Listing::NotResponding { previous, .. } => previous,
+                        Listing::Loading(_) => None,
+                        listing => Some(Box::new(listing)),
+                    };
+
+                self.listing = Listing::NotResponding {
+                    directory,
+                    previous,
+                };
+            }
+            Message::LeaveUnresponsive => {
+                if let Listing::NotResponding {
+                    directory,
+                    previous,
+                } = &mut self.listing
+                {
+                    match previous.take() {
+                        Some(previous) => self.listing = *previous,
+                        None => {
+                            if let Some(parent) = directory.parent() {
+                                let parent = parent.to_path_buf();
+
+                                return self.handle(Message::ChangeDirectory(parent));
+                            }
+                        }
+                    }
+                }
+            }
+            Message::FileTimedOut(path) => {
+                let error = i18n::tr_with("{} is not responding", &[&format!("{:?}", path)]);
+
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::SelectPrevious => self.move_selection(-1),
+            Message::SelectNext => self.move_selection(1),
+            Message::OpenSelected => {
+                if let Some(message) = self.selected_entry().map(Entry::message) {
+                    return self.handle(message);
+                }
+            }
+            Message::Select(path) => {
+                self.selected = Some(path);
+            }
+            Message::ReadFile(path) => {
+                self.selected = Some(path.clone());
+
+                return (Effect::ReadFile(path, self.max_file_size), None);
+            }
+            Message::FileRead(result) => match result {
+                Ok((path, Ok(content))) => {
+                    return (Effect::None, Some(Event::FileRead(path, content)));
+                }
+                Ok((path, Err(size))) => {
+                    return (Effect::None, Some(Event::FileTooLarge(path, size)));
+                }
+                Err(error) => {
+                    return (Effect::None, Some(Event::Failed(error.to_string())));
+                }
+            },
+            Message::RefreshDirectory => match &self.listing {
+                Listing::Loaded { directory, .. } => {
+                    let children = self.expanded.keys().cloned().map(Effect::ReadChildren);
+
+                    return (
+                        Effect::Batch(
+                            std::iter::once(Effect::ReadDirectory(directory.clone()))
+                                .chain(children)
+                                .collect(),
+                        ),
+                        None,
+                    );
+                }
+                Listing::Trash { .. } => {
+                    return (Effect::ListTrash, None);
+                }
+                Listing::Loading(_) | Listing::NotResponding { .. } => {}
+            },
+            Message::OpenTrash => {
+                return (Effect::ListTrash, None);
+            }
+            Message::TrashRead(Ok(items)) => {
+                if let Listing::Trash {
+                    items: current_items,
+                    ..
+                } = &mut self.listing
+                {
+                    *current_items = items;
+                } else {
+                    self.listing = Listing::Trash {
+                        return_to: self.current_dir().to_path_buf(),
+                        items,
+                        confirm_delete: None,
+                    };
+                }
+            }
+            Message::RestoreFromTrash(item) => {
+                return (Effect::RestoreFromTrash(item), None);
+            }
+            Message::DeleteFromTrash(item) => {
+                if let Listing::Trash { confirm_delete, .. } = &mut self.listing {
+                    if confirm_delete.as_ref() != Some(&item.trashed) {
+                        *confirm_delete = Some(item.trashed);
+                    } else {
+                        *confirm_delete = None;
+
+                        return (Effect::DeleteFromTrash(item), None);
+                    }
+                }
+            }
+            Message::TrashRead(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::TrashRestored(path, result) => {
+                let effect = if result.is_ok() {
+                    Effect::ListTrash
+                } else {
+                    Effect::None
+                };
+
+                return (effect, Some(Event::Restored(path, result)));
+            }
+            Message::TrashDeleted(path, result) => {
+                let effect = if result.is_ok() {
+                    Effect::ListTrash
+                } else {
+                    Effect::None
+                };
+
+                return (effect, Some(Event::DeletedFromTrash(path, result)));
+            }
+            Message::Scanned(scan::Event::Size(path, size)) => {
+                self.sizes.insert(path, size);
+            }
+            Message::Scanned(scan::Event::Metadata(batch)) => {
+                for (path, metadata) in batch {
+                    if metadata.is_file() {
+                        let executable = is_executable(&path, &metadata);
+
+                        if let Ok(modified) = metadata.modified() {
+                            self.modified.insert(path.clone(), modified);
+                        }
+
+                        self.sizes.insert(path.clone(), metadata.len());
+                        self.patch_executable(&path, executable);
+                        self.executables.insert(path, executable);
+                    }
+                }
+            }
+            Message::Scanned(scan::Event::Checksum(path, verdict)) => {
+                self.verdicts.insert(path, verdict);
+            }
+            Message::Scanned(scan::Event::Finished(_)) => {}
+            Message::ScanFinished(id, cancelled) => {
+                self.metadata_scans.retain(|(scan, _)| *scan != id);
+
+                if self.checksum_scan.as_ref().map(|(scan, _)| *scan) == Some(id) {
+                    self.checksum_scan = None;
+                }
+
+                if self.size_scan.as_ref().map(|(scan, _)| *scan) == Some(id) {
+                    self.size_scan = None;
+
+                    if !cancelled {
+                        self.scan_missing_sizes();
+                    }
+                }
+            }
+            Message::Watched(watcher::Change::Unavailable(directory)) => {
+                self.unwatched.insert(directory);
+            }
+            Message::Watched(change) if change.directory() != Some(self.current_dir()) => {
+                let directory = change
+                    .directory()
+                    .filter(|directory| self.expanded.contains_key(*directory))
+                    .map(Path::to_path_buf);
+
+                if let Some(directory) = directory {
+                    self.schedule_git_status();
+
+                    return (Effect::ReadChildren(directory), None);
+                }
+            }
+            Message::Watched(watcher::Change::Rescan(_)) => {
+                return self.handle(Message::RefreshDirectory);
+            }
+            Message::Watched(change) => {
+                self.previous_items = Some(self.item_keys());
+                self.schedule_git_status();
+
+                return (self.apply_change(change), None);
+            }
+            Message::EntryRead(path, entry) => {
+                self.insert_entry(path, entry);
+            }
+            Message::GitStatusRead(directory, status) => {
+                self.git_status_in_flight = false;
+
+                if directory == self.current_dir() {
+                    self.git_status = status;
+                } else {
+                    self.schedule_git_status();
+                }
+            }
+            Message::GitStatusDue(now) => {
+                let due = self.git_status_due.filter(|due| *due <= now);
+
+                if due.is_some() && !self.git_status_in_flight {
+                    self.git_status_due = None;
+                    self.git_status_in_flight = true;
+
+                    return (
+                        Effect::ReadGitStatus(self.current_dir().to_path_buf()),
+                        None,
+                    );
+                }
+            }
+            Message::TogglePin(path) => {
+                match self.pinned.iter().position(|pinned| *pinned == path) {
+                    Some(index) => {
+                        self.pinned.remove(index);
+                    }
+                    None => self.pinned.push(path),
+                }
+
+                let lines = self
+                    .pinned
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect();
+
+                return (Effect::SavePins(lines), None);
+            }
+            Message::SmartFolderInputChanged(input) => {
+                self.smart_folder_input = input;
+            }
+            Message::AddSmartFolder => {
+                let query = match smart_folder::Query::parse(&self.smart_folder_input) {
+                    Ok(query) => query,
+                    Err(error) => return (Effect::None, Some(Event::Failed(error))),
+                };
+
+                self.smart_folder_input.clear();
+                self.smart_folders
+                    .retain(|(folder, _)| folder.text != query.text);
+                self.smart_folders.push((query, Contents::Collapsed));
+
+                return (self.save_smart_folders(), None);
+            }
+            Message::RemoveSmartFolder(text) => {
+                self.smart_folders.retain(|(folder, _)| folder.text != text);
+
+                return (self.save_smart_folders(), None);
+            }
+            Message::ToggleSmartFolder(text) => {
+                if let Some((query, contents)) = self
+                    .smart_folders
+                    .iter_mut()
+                    .find(|(folder, _)| folder.text == text)
+                {
+                    if *contents == Contents::Collapsed {
+                        *contents = Contents::Loading;
+
+                        return (Effect::ReadSmartFolder(query.clone()), None);
+                    }
+
+                    *contents = Contents::Collapsed;
+                }
+            }
+            Message::SmartFolderRead(text, paths) => {
+                if let Some((_, contents)) = self
+                    .smart_folders
+                    .iter_mut()
+                    .find(|(folder, _)| folder.text == text)
+                    .filter(|(_, contents)| *contents == Contents::Loading)
+                {
+                    *contents = Contents::Loaded(paths);
+                }
+            }
+            Message::SmartFoldersSaved(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::SmartFoldersSaved(Ok(())) => {}
+            Message::OpenPinned(path) => {
+                return (Effect::Locate(path, Destination::Pinned), None);
+            }
+            Message::PinsSaved(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::PinsSaved(Ok(())) => {}
+            Message::RecentSaved(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::RecentSaved(Ok(())) => {}
+            Message::ExpandedSaved(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::ExpandedSaved(Ok(())) => {}
+            Message::SortBy(key) => {
+                self.sort = Sort {
+                    key,
+                    descending: self.sort.key == key && !self.sort.descending,
+                };
+
+                return (self.remember_sort(), None);
+            }
+            Message::ReverseSort => {
+                self.sort.descending = !self.sort.descending;
+
+                return (self.remember_sort(), None);
+            }
+            Message::SortsSaved(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::SortsSaved(Ok(())) => {}
+            Message::SetViewMode(view_mode) => {
+                self.view_mode = view_mode;
+            }
+            Message::ResizeColumn(column, delta) => {
+                let width = &mut self.column_widths[column as usize];
+
+                *width = (*width + delta).clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+            }
+            Message::FinishColumnResize => {
+                return (Effect::SaveColumns(self.column_lines()), None);
+            }
+            Message::ColumnsSaved(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::ColumnsSaved(Ok(())) => {}
+            Message::RecallFilter(query) => {
+                if let Listing::Loaded { .. } = self.listing {
+                    self.filter.text = query;
+                    self.select_first_match();
+                }
+            }
+            Message::FilterHistorySaved(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::FilterHistorySaved(Ok(())) => {}
+            Message::RefreshVolumes => {
+                if let Listing::NotResponding { .. } = self.listing {
+                    return (Effect::None, None);
+                }
+
+                let mut effects = vec![];
+
+                if !self.volumes_in_flight {
+                    self.volumes_in_flight = true;
+                    effects.push(Effect::ListVolumes);
+                }
+
+                let current = self.current_dir().to_path_buf();
+                effects.extend(self.read_space(current));
+
+                return (Effect::Batch(effects), None);
+            }
+            Message::SpaceRead(directory, space) => {
+                self.space_in_flight.remove(&directory);
+
+                if directory == self.current_dir() {
+                    self.space = space;
+                }
+            }
+            Message::VolumesListed(Some(volumes)) => {
+                self.volumes_in_flight = false;
+
+                let current = self.current_dir().to_path_buf();
+
+                let unmounted = self
+                    .volumes
+                    .iter()
+                    .map(|volume| &volume.root)
+                    .filter(|root| !volumes.iter().any(|volume| volume.root == **root))
+                    .find(|root| current.starts_with(root))
+                    .cloned();
+
+                self.volumes = volumes;
+
+                if let Some((root, home)) = unmounted.zip(self.home.clone()) {
+                    let notice = i18n::tr_with(
+                        "{} was unmounted, showing the home folder instead",
+                        &[&format!("{:?}", root)],
+                    );
+
+                    let (effect, _) = self.handle(Message::ChangeDirectory(home));
+
+                    return (effect, Some(Event::Failed(notice)));
+                }
+            }
+            Message::VolumesListed(None) => {
+                self.volumes_in_flight = false;
+            }
+            Message::HideLargeFiles(hide_large) => {
+                self.filter.hide_large = hide_large;
+            }
+            Message::CycleTag(path) => {
+                match Tag::after(self.tags.get(&path).copied()) {
+                    Some(tag) => {
+                        self.tags.insert(path, tag);
+                    }
+                    None => {
+                        self.tags.remove(&path);
+                    }
+                }
+
+                return (Effect::SaveTags(tags::lines(&self.tags)), None);
+            }
+            Message::FilterByTag(tag) => {
+                self.tag_filter = tag;
+                self.select_first_match();
+            }
+            Message::TagsSaved(Err(error)) => {
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            Message::TagsSaved(Ok(())) => {}
+            Message::HideBinaryFiles(hide_binary) => {
+                self.filter.hide_binary = hide_binary;
+            }
+            Message::HideHiddenFiles(hide_hidden) => {
+                self.filter.hide_hidden = hide_hidden;
+            }
+            Message::GroupByType(group_by_type) => {
+                self.group_by_type = group_by_type;
+            }
+            Message::ToggleStatistics => {
+                self.show_statistics = !self.show_statistics;
+            }
+            Message::ToggleGroup(group) => {
+                if !self.collapsed_groups.remove(group) {
+                    self.collapsed_groups.insert(group);
+                }
+            }
+            Message::ToggleExpanded(path) => {
+                let collapsing = self
+                    .animation
+                    .as_ref()
+                    .is_some_and(|animation| animation.collapsing && animation.directory == path);
+
+                if collapsing {
+                    self.animation = None;
+                } else if self.expanded.contains_key(&path) {
+                    self.animate(path, true);
+                } else {
+                    self.animate(path.clone(), false);
+
+                    let expand = self.expand(path);
+
+                    return (Effect::Batch(vec![expand, self.remember_expanded()]), None);
+                }
+
+                return (self.remember_expanded(), None);
+            }
+            Message::AnimationFrame(now) => {
+                let finished = self.animation.as_ref().is_some_and(|animation| {
+                    animation.progress(now) >= 1.0
+                        && (animation.collapsing
+                            || matches!(self.expanded.get(&animation.directory), Some(Some(_))))
+                });
+
+                if finished {
+                    self.finish_animation();
+                }
+
+                let flashed = self.flash.as_ref().is_some_and(|(_, started)| {
+                    now.saturating_duration_since(*started) >= FLASH_DURATION
+                });
+
+                if flashed {
+                    self.flash = None;
+                }
+            }
+            Message::ChildrenRead(path, entries) => {
+                if let Some(children) = self.expanded.get_mut(&path) {
+                    let grouping = self.grouping;
+                    let mut entries = entries.unwrap_or_default();
+
+                    fill_executables(&mut entries, &self.executables);
+                    entries.sort_by(|a, b| grouping.compare(a, b));
+
+                    let scan = missing_executables(&entries, &self.executables);
+
+                    *children = Some(entries);
+
+                    self.scan_metadata(&path, scan);
+
+                    if let Some(animation) = self
+                        .animation
+                        .as_mut()
+                        .filter(|animation| animation.directory == path)
+                    {
+                        animation.started = time::Instant::now();
+                    }
+
+                    if let Some(limit) = self.expand_all_limit {
+                        let expand = self.expand_directories(limit);
+
+                        return (Effect::Batch(vec![expand, self.remember_expanded()]), None);
+                    }
+                }
+            }
+            Message::ExpandAll => {
+                self.expand_all_limit = Some(self.expand_depth);
+
+                let expand = self.expand_directories(self.expand_depth);
+
+                return (Effect::Batch(vec![expand, self.remember_expanded()]), None);
+            }
+            Message::CollapseAll => {
+                self.expanded.clear();
+                self.animation = None;
+                self.expand_all_limit = None;
+
+                return (self.remember_expanded(), None);
+            }
+            Message::ScrolledHorizontally(offset) => {
+                self.horizontal_offset = offset;
+            }
+            Message::TreeScrolled => {}
+            Message::EditPath => {
+                self.path_input = Some(self.current_dir().to_string_lossy().to_string());
+                self.path_input_selected = true;
+                self.path_status = Some(PathStatus::Directory);
+                self.completions.clear();
+                self.completion = None;
+            }
+            Message::PathInputChanged(value) => {
+                if let Some(input) = self.path_input.as_mut() {
+                    *input = match value.strip_prefix(input.as_str()) {
+                        Some(typed) if self.path_input_selected => typed.to_string(),
+                        None if self.path_input_selected && input.starts_with(&value) => {
+                            String::new()
+                        }
+                        _ => value,
+                    };
+                    self.path_input_selected = false;
+                    self.completion = None;
+
+                    return (self.inspect_path_input(), None);
+                }
+            }
+            Message::DeselectPathInput => {
+                self.path_input_selected = false;
+            }
+            Message::PathCompleted(path, completions) => {
+                if self.resolved_path_input() == Some(path) {
+                    self.completions = completions;
+                    self.completion = None;
+                }
+            }
+            Message::PathChecked(path, status) => {
+                if self.resolved_path_input() == Some(path) {
+                    self.path_status = Some(status);
+                }
+            }
+            Message::NextCompletion | Message::PreviousCompletion => {
+                let count = self.completions.len();
+
+                if count > 0 {
+                    self.completion = Some(match (self.completion, message) {
+                        (None, Message::NextCompletion) => 0,
+                        (None, _) => count - 1,
+                        (Some(index), Message::NextCompletion) => (index + 1) % count,
+                        (Some(index), _) => (index + count - 1) % count,
+                    });
+                }
+            }
+            Message::AcceptCompletion => {
+                let completion = self
+                    .completion()
+                    .or_else(|| self.completions.first().map(PathBuf::as_path))
+                    .map(Path::to_path_buf);
+
+                if let Some(completion) = completion {
+                    return self.handle(Message::PickCompletion(completion));
+                }
+            }
+            Message::PickCompletion(path) => {
+                if self.path_input.is_some() {
+                    let mut value = path.to_string_lossy().to_string();
+                    value.push(std::path::MAIN_SEPARATOR);
+
+                    self.path_input = Some(value);
+                    self.path_input_selected = false;
+                    self.completion = None;
+
+                    return (self.inspect_path_input(), None);
+                }
+            }
+            Message::SubmitPath => {
+                let path = self
+                    .completion()
+                    .map(Path::to_path_buf)
+                    .or_else(|| self.resolved_path_input());
+
+                if let Some(path) = path {
+                    self.path_input = None;
+                    self.completions.clear();
+                    self.completion = None;
+
+                    return (Effect::Locate(path, Destination::Submitted), None);
+                }
+            }
+            Message::CancelPathInput => {
+                self.path_input = None;
+                self.completions.clear();
+                self.completion = None;
+            }
+            Message::VerifyChecksums => {
+                if let Some((scan, _)) = self.checksum_scan.take() {
+                    self.cancel_scan(scan);
+                }
+
+                let manifests: Vec<_> = self
+                    .manifests()
+                    .into_iter()
+                    .map(Path::to_path_buf)
+                    .collect();
+
+                self.verdicts.clear();
+
+                if !manifests.is_empty() {
+                    let label = format!("Verifying checksums in {:?}", self.current_dir());
+                    let scan = self.start_scan(ScanKind::Checksums, label, manifests.clone());
+
+                    self.checksum_scan = Some((scan, manifests));
+                }
+            }
+            Message::ToggleFilter(category) => {
+                if !self.filter.categories.remove(&category) {
+                    self.filter.categories.insert(category);
+                }
+            }
+        }
+
+        (Effect::None, None)
+    }
+
+    fn inspect_path_input(&mut self) -> Effect {
+        self.path_status = None;
+
+        match self.resolved_path_input() {
+            Some(path) => Effect::Batch(vec![
+                Effect::CompletePath(path.clone()),
+                Effect::CheckPath(path),
+            ]),
+            None => Effect::None,
+        }
+    }
+
+    fn visit(&mut self, directory: PathBuf, entries: Vec<Entry>) -> Effect {
+        let moved = self.current_dir() != directory;
+        let entering = !matches!(
+            &self.listing,
+            Listing::Loaded { directory: current, .. } if *current == directory
+        );
+
+        let mut effects = vec![];
+
+        self.schedule_git_status();
+
+        if moved {
+            effects.push(self.remember_filter());
+        }
+
+        if entering {
+            effects.push(self.remember_expanded());
+
+            if moved {
+                self.unwatched.clear();
+            }
+
+            self.sort = self
+                .remembered_sorts
+                .get(&directory)
+                .copied()
+                .unwrap_or_default();
+        }
+
+        self.show_directory(directory.clone(), entries);
+
+        if entering {
+            let remembered = self
+                .remembered_expansions
+                .get(&directory)
+                .cloned()
+                .unwrap_or_default();
+
+            effects.extend(remembered.into_iter().map(|path| self.expand(path)));
+        }
+
+        if moved {
+            effects.extend(self.read_space(directory.clone()));
+        }
+
+        if self.recent.first() == Some(&directory) {
+            return Effect::Batch(effects);
+        }
+
+        self.recent.retain(|recent| *recent != directory);
+        self.recent.insert(0, directory.clone());
+        self.recent.truncate(MAX_RECENT);
+
+        let lines = self
+            .recent
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+
+        effects.push(Effect::SaveRecent(lines));
+
+        Effect::Batch(effects)
+    }
+
+    fn show_directory(&mut self, directory: PathBuf, mut entries: Vec<Entry>) {
+        let grouping = self.grouping;
+        let previous = self.current_dir().to_path_buf();
+
+        entries.sort_by(|a, b| grouping.compare(a, b));
+
+        let mut unchanged = false;
+
+        if let Listing::Loaded {
+            directory: current_directory,
+            entries: current_entries,
+        } = &self.listing
+        {
+            if *current_directory != directory {
+                self.sizes.clear();
+                self.filter.text.clear();
+                self.expanded.clear();
+                self.animation = None;
+                self.expand_all_limit = None;
+                self.horizontal_offset = 0;
+                self.git_status = None;
+                self.executables.clear();
+                self.modified.clear();
+
+                for (scan, _) in mem::take(&mut self.metadata_scans) {
+                    self.scan_effects.push(Effect::CancelScan(scan));
+                }
+
+                if let Some((scan, _)) = self.checksum_scan.take() {
+                    self.scan_effects.push(Effect::CancelScan(scan));
+                }
+
+                self.verdicts.clear();
+            }
+
+            fill_executables(&mut entries, &self.executables);
+
+            unchanged = *current_directory == directory && *current_entries == entries;
+        }
+
+        if !unchanged {
+            if let Some((scan, _)) = self.size_scan.take() {
+                self.cancel_scan(scan);
+            }
+
+            let paths: Vec<_> = entries
+                .iter()
+                .map(|entry| entry.path().to_path_buf())
+                .collect();
+            let label = format!("Calculating sizes in {:?}", directory);
+            let scan = self.start_scan(ScanKind::Sizes, label, paths.clone());
+
+            self.size_scan = Some((scan, paths));
+
+            let scan = missing_executables(&entries, &self.executables);
+
+            self.scan_metadata(&directory, scan);
+        }
+
+        let moved = previous != directory || self.selected.is_none();
+
+        if !moved && !unchanged {
+            self.previous_items = Some(self.item_keys());
+        }
+
+        if previous != directory {
+            if self.traveling {
+                self.traveling = false;
+            } else {
+                self.back.push(previous.clone());
+                self.forward.clear();
+            }
+        }
+
+        self.listing = Listing::Loaded { directory, entries };
+
+        if moved && self.selected_entry().is_none() {
+            let entries = self.visible_entries();
+
+            self.selected = entries
+                .iter()
+                .find(|entry| entry.path() == previous)
+                .or_else(|| entries.first())
+                .map(|entry| entry.path().to_path_buf());
+        }
+    }
+
+    fn apply_change(&mut self, change: watcher::Change) -> Effect {
+        let (directory, entries) = match &mut self.listing {
+            Listing::Loaded { directory, entries } => (directory, entries),
+            _ => return Effect::None,
+        };
+
+        self.listing_cache.remove(directory.as_path());
+
+        let mut remove = |path: &Path| {
+            entries.retain(|entry| entry.path() != path);
+        };
+
+        let effect = match change {
+            watcher::Change::Created(path) | watcher::Change::Modified(path) => {
+                Effect::ReadEntry(path)
+            }
+            watcher::Change::Removed(path) => {
+                remove(&path);
+                self.sizes.remove(&path);
+
+                Effect::None
+            }
+            watcher::Change::Renamed { from, to } => {
+                remove(&from);
+
+                if let Some(size) = self.sizes.remove(&from) {
+                    self.sizes.insert(to.clone(), size);
+                }
+
+                if self.selected.as_ref() == Some(&from) {
+                    self.selected = Some(to.clone());
+                }
+
+                Effect::ReadEntry(to)
+            }
+            watcher::Change::Rescan(_) | watcher::Change::Unavailable(_) => Effect::None,
+        };
+
+        self.scan_missing_sizes();
+
+        effect
+    }
+
+    fn insert_entry(&mut self, path: PathBuf, read: Option<(Entry, fs::Metadata)>) {
+        let grouping = self.grouping;
+
+        let (directory, entries) = match &mut self.listing {
+            Listing::Loaded { directory, entries } if path.parent() == Some(directory) => {
+                (directory, entries)
+            }
+            _ => return,
+        };
+
+        self.listing_cache.remove(directory.as_path());
+        entries.retain(|entry| entry.path() != path);
+
+        if let Some((entry, metadata)) = read {
+            let index = entries
+                .binary_search_by(|probe| grouping.compare(probe, &entry))
+                .unwrap_or_else(|index| index);
+
+            entries.insert(index, entry);
+
+            if metadata.is_file() {
+                if let Ok(modified) = metadata.modified() {
+                    self.modified.insert(path.clone(), modified);
+                }
+
+                self.sizes.insert(path, metadata.len());
+            }
+        }
+
+        self.scan_missing_sizes();
+    }
+
+    fn located(
+        &mut self,
+        path: PathBuf,
+        destination: Destination,
+        status: PathStatus,
+    ) -> (Effect, Option<Event>) {
+        let is_dir = status == PathStatus::Directory;
+
+        match destination {
+            Destination::Back | Destination::Forward if !is_dir => {
+                let message = match destination {
+                    Destination::Back => Message::GoBack,
+                    _ => Message::GoForward,
+                };
+
+                return self.handle(message);
+            }
+            Destination::Back | Destination::Forward => {
+                let current = self.current_dir().to_path_buf();
+
+                match destination {
+                    Destination::Back => self.forward.push(current),
+                    _ => self.back.push(current),
+                }
+
+                self.traveling = true;
+            }
+            Destination::Submitted if status == PathStatus::Missing => {
+                let error = i18n::tr_with("{} does not exist", &[&format!("{:?}", path)]);
+
+                return (Effect::None, Some(Event::Failed(error)));
+            }
+            _ if is_dir => {}
+            Destination::Pinned => return self.handle(Message::ReadFile(path)),
+            Destination::Reveal | Destination::Open => {
+                let parent = path
+                    .parent()
+                    .filter(|parent| *parent != self.current_dir())
+                    .map(Path::to_path_buf);
+
+                self.reveal(path.clone());
+
+                let change = match parent {
+                    Some(parent) => self.handle(Message::ChangeDirectory(parent)).0,
+                    None => Effect::None,
+                };
+
+                if destination == Destination::Reveal {
+                    return (change, None);
+                }
+
+                let (read, event) = self.handle(Message::ReadFile(path));
+
+                return (Effect::Batch(vec![change, read]), event);
+            }
+            Destination::Navigate | Destination::Submitted => {
+                if let Some(parent) = path.parent().map(Path::to_path_buf) {
+                    self.selected = Some(path);
+
+                    return self.handle(Message::ChangeDirectory(parent));
+                }
+
+                return (Effect::None, None);
+            }
+        }
+
+        self.handle(Message::ChangeDirectory(path))
+    }
+
+    fn scan_metadata(&mut self, directory: &Path, paths: Vec<PathBuf>) {
+        let pending = |path: &PathBuf| {
+            self.metadata_scans
+                .iter()
+                .any(|(_, scanning)| scanning.contains(path))
+        };
+
+        let paths: Vec<_> = paths.into_iter().filter(|path| !pending(path)).collect();
+
+        if !paths.is_empty() {
+            let label = format!("Reading metadata in {:?}", directory);
+            let scan = self.start_scan(ScanKind::Metadata, label, paths.clone());
+
+            self.metadata_scans.push((scan, paths));
+        }
+    }
+
+    fn start_scan(&mut self, kind: ScanKind, label: String, paths: Vec<PathBuf>) -> usize {
+        let id = self.next_scan;
+        self.next_scan += 1;
+
+        self.scan_effects.push(Effect::StartScan(Scan {
+            id,
+            kind,
+            label,
+            paths,
+        }));
+
+        id
+    }
+
+    fn cancel_scan(&mut self, id: usize) {
+        self.scan_effects.push(Effect::CancelScan(id));
+    }
+
+    fn patch_executable(&mut self, path: &Path, executable: bool) {
+        let grouping = self.grouping;
+        let parent = match path.parent() {
+            Some(parent) => parent,
+            None => return,
+        };
+
+        let entries = match &mut self.listing {
+            Listing::Loaded { directory, entries } if directory == parent => Some(entries),
+            _ => self.expanded.get_mut(parent).and_then(Option::as_mut),
+        };
+
+        let probe = match path.file_name() {
+            Some(name) => Entry::File {
+                path: path.to_path_buf(),
+                name: name.to_string_lossy().to_string(),
+                executable: false,
+            },
+            None => return,
+        };
+
+        if let Some(entries) = entries {
+            if let Ok(index) = entries.binary_search_by(|entry| grouping.compare(entry, &probe)) {
+                if let Entry::File {
+                    executable: current,
+                    ..
+                } = &mut entries[index]
+                {
+                    *current = executable;
+                }
+            }
+        }
+    }
+
+    fn scan_missing_sizes(&mut self) {
+        if self.size_scan.is_some() {
+            return;
+        }
+
+        let missing: Vec<_> = self
+            .entries()
+            .iter()
+            .map(Entry::path)
+            .filter(|path| !self.sizes.contains_key(*path))
+            .map(Path::to_path_buf)
+            .collect();
+
+        if !missing.is_empty() {
+            let label = format!("Calculating sizes in {:?}", self.current_dir());
+            let scan = self.start_scan(ScanKind::Sizes, label, missing.clone());
+
+            self.size_scan = Some((scan, missing));
+        }
+    }
+
+    fn cached_entries(
+        &mut self,
+        directory: &Path,
+        modified: Option<time::SystemTime>,
+    ) -> Option<Vec<Entry>> {
+        let cached = self.listing_cache.get_mut(directory)?;
+
+        if modified != Some(cached.modified) {
+            self.listing_cache.remove(directory);
+
+            return None;
+        }
+
+        cached.used = time::Instant::now();
+
+        Some(cached.entries.clone())
+    }
+
+    fn cache_entries(
+        &mut self,
+        directory: PathBuf,
+        modified: time::SystemTime,
+        entries: Vec<Entry>,
+    ) {
+        if self.listing_cache.len() >= LISTING_CACHE_SIZE
+            && !self.listing_cache.contains_key(&directory)
+        {
+            let oldest = self
+                .listing_cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.used)
+                .map(|(path, _)| path.clone());
+
+            if let Some(oldest) = oldest {
+                self.listing_cache.remove(&oldest);
+            }
+        }
+
+        self.listing_cache.insert(
+            directory,
+            CachedListing {
+                modified,
+                entries,
+                used: time::Instant::now(),
+            },
+        );
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        match &self.listing {
+            Listing::Loading(_) | Listing::NotResponding { .. } | Listing::Trash { .. } => &[],
+            Listing::Loaded { entries, .. } => entries,
+        }
+    }
+
+    pub fn statistics(&self) -> Vec<Statistic> {
+        let mut statistics: Vec<_> = Category::ALL
+            .iter()
+            .copied()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .map(|category| Statistic {
+                category,
+                count: 0,
+                bytes: 0,
+            })
+            .collect();
+
+        for entry in self.entries().iter().filter(|entry| self.matches(entry)) {
+            if let Some(statistic) = statistics
+                .iter_mut()
+                .find(|statistic| statistic.category == entry.category())
+            {
+                statistic.count += 1;
+                statistic.bytes += self.size(entry.path()).unwrap_or(0);
+            }
+        }
+
+        statistics.retain(|statistic| statistic.count > 0);
+
+        statistics
+    }
+
+    pub fn groups(&self) -> Vec<(&'static str, usize)> {
+        if !self.group_by_type {
+            return vec![("", 0)];
+        }
+
+        let mut names: Vec<_> = self
+            .entries()
+            .iter()
+            .filter(|entry| self.matches(entry))
+            .map(Entry::type_name)
+            .collect();
+
+        names.sort_by_key(|name| group_rank(name));
+
+        let mut groups: Vec<(&'static str, usize)> = vec![];
+
+        for name in names {
+            match groups.last_mut() {
+                Some((group, count)) if *group == name => *count += 1,
+                _ => groups.push((name, 1)),
+            }
+        }
+
+        groups
+    }
+
+    pub fn item_keys(&self) -> Vec<ItemKey> {
+        let rows = self.rows();
+        let mut rows = rows.iter().peekable();
+        let mut keys = vec![];
+
+        for (group, _) in self.groups() {
+            if self.group_by_type {
+                keys.push(ItemKey::Group(group));
+            }
+
+            while let Some(row) = rows.next_if(|row| !self.group_by_type || row.group == group) {
+                keys.push(ItemKey::Entry(row.entry.path().to_path_buf()));
+            }
+        }
+
+        keys
+    }
+
+    pub fn take_previous_items(&mut self) -> Option<Vec<ItemKey>> {
+        self.previous_items.take()
+    }
+
+    fn item_index(&self, path: &Path) -> Option<usize> {
+        let rows = self.rows();
+        let index = rows.iter().position(|row| row.entry.path() == path)?;
+
+        if !self.group_by_type {
+            return Some(index);
+        }
+
+        let group = rows[index].group;
+        let headers = self.groups().iter().position(|(name, _)| *name == group)? + 1;
+
+        Some(index + headers)
+    }
+
+    pub fn rows(&self) -> Vec<TreeRow<'_>> {
+        if self.updating {
+            return self.resolve_rows(&self.arrange_rows());
+        }
+
+        let mut cache = self.row_cache.borrow_mut();
+
+        let positions = cache.get_or_insert_with(|| {
+            self.row_generation.set(self.row_generation.get() + 1);
+            self.arrange_rows()
+        });
+
+        self.resolve_rows(positions)
+    }
+
+    pub fn row_generation(&self) -> usize {
+        self.row_generation.get()
+    }
+
+    fn arrange_rows(&self) -> Vec<RowPosition> {
+        let mut entries: Vec<_> = self
+            .entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.matches(entry))
+            .collect();
+
+        if self.sort != Sort::default() {
+            entries.sort_by(|(_, a), (_, b)| self.compare_sorted(a, b));
+        }
+
+        if self.group_by_type {
+            entries.retain(|(_, entry)| !self.collapsed_groups.contains(entry.type_name()));
+            entries.sort_by_key(|(_, entry)| group_rank(entry.type_name()));
+        }
+
+        let mut rows = vec![];
+
+        for (index, entry) in entries {
+            let position = RowPosition {
+                parent: None,
+                index,
+                depth: 0,
+                group: entry.type_name(),
+            };
+
+            self.push_rows(&mut rows, entry, position);
+        }
+
+        rows
+    }
+
+    fn push_rows(&self, rows: &mut Vec<RowPosition>, entry: &Entry, position: RowPosition) {
+        let parent = rows.len();
+        let (depth, group) = (position.depth, position.group);
+
+        rows.push(position);
+
+        if let Some(Some(children)) = self.expanded.get(entry.path()) {
+            let mut children: Vec<_> = children
+                .iter()
+                .enumerate()
+                .filter(|(_, child)| self.matches(child))
+                .collect();
+
+            if self.sort != Sort::default() {
+                children.sort_by(|(_, a), (_, b)| self.compare_sorted(a, b));
+            }
+
+            for (index, child) in children {
+                let position = RowPosition {
+                    parent: Some(parent),
+                    index,
+                    depth: depth + 1,
+                    group,
+                };
+
+                self.push_rows(rows, child, position);
+            }
+        }
+    }
+
+    fn resolve_rows(&self, positions: &[RowPosition]) -> Vec<TreeRow<'_>> {
+        let mut rows: Vec<TreeRow<'_>> = Vec::with_capacity(positions.len());
+
+        for position in positions {
+            let siblings = match position.parent {
+                None => self.entries(),
+                Some(parent) => match self.expanded.get(rows[parent].entry.path()) {
+                    Some(Some(children)) => children.as_slice(),
+                    _ => &[],
+                },
+            };
+
+            rows.push(TreeRow {
+                entry: &siblings[position.index],
+                depth: position.depth,
+                group: position.group,
+            });
+        }
+
+        rows
+    }
+
+    fn visible_entries(&self) -> Vec<&Entry> {
+        self.rows().into_iter().map(|row| row.entry).collect()
+    }
+
+    pub fn selected_file(&self) -> Option<&Path> {
+        self.selected_entry()
+            .filter(|entry| matches!(entry, Entry::File { .. }))
+            .map(Entry::path)
+    }
+
+    pub fn selected_directory(&self) -> Option<&Path> {
+        self.selected_entry()
+            .filter(|entry| matches!(entry, Entry::Directory { .. }))
+            .map(Entry::path)
+    }
+
+    fn selected_entry(&self) -> Option<&Entry> {
+        let selected = self.selected.as_deref()?;
+
+        self.visible_entries()
+            .into_iter()
+            .find(|entry| entry.path() == selected)
+    }
+
+    fn expand(&mut self, directory: PathBuf) -> Effect {
+        self.expanded.insert(directory.clone(), None);
+
+        Effect::ReadChildren(directory)
+    }
+
+    fn collapse(&mut self, directory: &Path) {
+        self.expanded.retain(|path, _| !path.starts_with(directory));
+    }
+
+    fn animate(&mut self, directory: PathBuf, collapsing: bool) {
+        self.finish_animation();
+
+        if self.reduce_motion {
+            if collapsing {
+                self.collapse(&directory);
+            }
+
+            return;
+        }
+
+        self.animation = Some(Animation::new(directory, collapsing));
+    }
+
+    fn finish_animation(&mut self) {
+        if let Some(animation) = self.animation.take() {
+            if animation.collapsing {
+                self.collapse(&animation.directory);
+            }
+        }
+    }
+
+    fn expand_directories(&mut self, limit: usize) -> Effect {
+        let current_dir = self.current_dir().to_path_buf();
+
+        let directories: Vec<_> = self
+            .visible_entries()
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Entry::Directory { path, .. } => Some(path.clone()),
+                Entry::File { .. } => None,
+            })
+            .filter(|path| !self.expanded.contains_key(path))
+            .filter(|path| depth(&current_dir, path) <= limit)
+            .collect();
+
+        Effect::Batch(
+            directories
+                .into_iter()
+                .map(|path| self.expand(path))
+                .collect(),
+        )
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        let entries = self.visible_entries();
+
+        if entries.is_empty() {
+            return;
+        }
+
+        let index = self
+            .selected
+            .as_deref()
+            .and_then(|selected| entries.iter().position(|entry| entry.path() == selected));
+
+        let index = match index {
+            Some(index) => (index as isize + offset).clamp(0, entries.len() as isize - 1) as usize,
+            None => 0,
+        };
+
+        self.selected = Some(entries[index].path().to_path_buf());
+    }
+}
+
+#[derive(Default)]
+struct Filter {
+    categories: HashSet<Category>,
+    text: String,
+    hide_large: bool,
+    hide_binary: bool,
+    hide_hidden: bool,
+}
+
+impl Filter {
+    fn matches(&self, entry: &Entry) -> bool {
+        let by_category = self.categories.is_empty()
+            || entry
+                .category()
+                .is_some_and(|category| self.categories.contains(&category));
+
+        let by_text = self.text.is_empty()
+            || entry
+                .file_name()
+                .to_lowercase()
+                .contains(&self.text.to_lowercase());
+
+        let by_type = !self.hide_binary
+            || match entry {
+                Entry::File { path, .. } => !file_type::is_binary(path),
+                Entry::Directory { .. } => true,
+            };
+
+        let by_visibility = !self.hide_hidden || !entry.file_name().starts_with('.');
+
+        by_category && by_text && by_type && by_visibility
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(_path: &Path, metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path, _metadata: &fs::Metadata) -> bool {
+    const EXTENSIONS: &[&str] = &["bat", "cmd", "com", "exe"];
+
+    path.extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .is_some_and(|extension| EXTENSIONS.contains(&extension.as_str()))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn is_executable(_path: &Path, _metadata: &fs::Metadata) -> bool {
+    false
+}
+
+fn directories_first(a: &Entry, b: &Entry) -> Ordering {
+    match (a, b) {
+        (Entry::Directory { .. }, Entry::File { .. }) => Ordering::Less,
+        (Entry::File { .. }, Entry::Directory { .. }) => Ordering::Greater,
+        _ => Ordering::Equal,
+    }
+}
+
+fn extension(entry: &Entry) -> Option<String> {
+    match entry {
+        Entry::File { path, .. } => path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase()),
+        Entry::Directory { .. } => None,
+    }
+}
+
+fn parse_sorts(lines: &[String]) -> HashMap<PathBuf, Sort> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+
+            let key = fields.next()?;
+            let key = SortKey::ALL
+                .iter()
+                .find(|candidate| candidate.key() == key)?;
+            let descending = fields.next()? == "desc";
+
+            Some((
+                PathBuf::from(fields.next()?),
+                Sort {
+                    key: *key,
+                    descending,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn parse_column_widths(lines: &[String]) -> [f32; DetailColumn::ALL.len()] {
+    let mut widths = DetailColumn::ALL.map(DetailColumn::default_width);
+
+    for line in lines {
+        let mut fields = line.splitn(2, '\t');
+
+        let (key, width) = match (fields.next(), fields.next()) {
+            (Some(key), Some(width)) => (key, width),
+            _ => continue,
+        };
+
+        let column = DetailColumn::ALL
+            .iter()
+            .position(|column| column.sort_key().key() == key);
+
+        if let (Some(column), Ok(width)) = (column, width.parse::<f32>()) {
+            widths[column] = width.clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+        }
+    }
+
+    widths
+}
+
+fn parse_expansions(lines: &[String]) -> HashMap<PathBuf, Vec<PathBuf>> {
+    let mut expansions: HashMap<_, Vec<_>> = HashMap::new();
+
+    for line in lines {
+        if let Some((root, path)) = line.split_once('\t') {
+            expansions
+                .entry(PathBuf::from(root))
+                .or_default()
+                .push(PathBuf::from(path));
+        }
+    }
+
+    expansions
+}
+
+fn depth(root: &Path, path: &Path) -> usize {
+    path.strip_prefix(root)
+        .map(|relative| relative.components().count())
+        .unwrap_or(0)
+}
+
+fn group_rank(group: &'static str) -> (u8, &'static str) {
+    match group {
+        file_type::FOLDERS => (0, group),
+        file_type::OTHER => (2, group),
+        _ => (1, group),
+    }
+}
+
+fn fill_executables(entries: &mut [Entry], executables: &HashMap<PathBuf, bool>) {
+    for entry in entries {
+        if let Entry::File {
+            path, executable, ..
+        } = entry
+        {
+            if let Some(known) = executables.get(path) {
+                *executable = *known;
+            }
+        }
+    }
+}
+
+fn missing_executables(entries: &[Entry], executables: &HashMap<PathBuf, bool>) -> Vec<PathBuf> {
+    entries
+        .iter()
+        .filter(|entry| matches!(entry, Entry::File { .. }))
+        .map(Entry::path)
+        .filter(|path| !executables.contains_key(*path))
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+pub fn expand_path(input: &str, home: Option<&Path>) -> PathBuf {
+    let expanded = expand_variables(input.trim());
+
+    let home = expanded
+        .strip_prefix('~')
+        .filter(|rest| rest.is_empty() || rest.starts_with(std::path::is_separator))
+        .and_then(|rest| Some(home?.join(rest.trim_start_matches(std::path::is_separator))));
+
+    home.unwrap_or_else(|| PathBuf::from(expanded))
+}
+
+fn expand_variables(input: &str) -> String {
+    let is_marker = |character: char| character == '$' || (cfg!(windows) && character == '%');
+
+    let mut expanded = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find(is_marker) {
+        expanded.push_str(&rest[..start]);
+
+        let after = &rest[start + 1..];
+
+        let (name, consumed) = if rest[start..].starts_with('%') {
+            after
+                .find('%')
+                .map_or(("", 0), |end| (&after[..end], end + 1))
+        } else if let Some(braced) = after.strip_prefix('{') {
+            braced
+                .find('}')
+                .map_or(("", 0), |end| (&braced[..end], end + 2))
+        } else {
+            let end = after
+                .find(|character: char| !(character.is_alphanumeric() || character == '_'))
+                .unwrap_or(after.len());
+
+            (&after[..end], end)
+        };
+
+        match std::env::var(name).ok().filter(|_| !name.is_empty()) {
+            Some(value) => expanded.push_str(&value),
+            None => expanded.push_str(&rest[start..start + 1 + consumed]),
+        }
+
+        rest = &after[consumed..];
+    }
+
+    expanded.push_str(rest);
+
+    expanded
+}
+
+pub fn read_directory(
+    path: PathBuf,
+) -> Result<(PathBuf, Option<time::SystemTime>, Vec<Entry>), ReadError> {
+    let io_error = |error: std::io::Error| ReadError::Io(path.clone(), error.to_string());
+
+    let metadata = fs::metadata(&path).map_err(io_error)?;
+
+    if !metadata.is_dir() {
+        return Err(ReadError::NotADirectory(path));
+    }
+
+    let read_dir = fs::read_dir(&path).map_err(io_error)?;
+
+    let entries = read_dir.flatten().filter_map(Entry::listed).collect();
+
+    Ok((path, metadata.modified().ok(), entries))
+}
+
+pub fn modified(path: &Path) -> Option<time::SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+pub fn read_entry(path: &Path) -> Option<(Entry, fs::Metadata)> {
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let metadata = fs::metadata(path).ok()?;
+
+    Entry::with_metadata(path.to_path_buf(), name, &metadata).map(|entry| (entry, metadata))
+}
+
+pub fn check_path(path: &Path) -> PathStatus {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_dir() => match fs::read_dir(path) {
+            Ok(_) => PathStatus::Directory,
+            Err(error) => PathStatus::Inaccessible(error.to_string()),
+        },
+        Ok(_) => PathStatus::File,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => PathStatus::Missing,
+        Err(error) => PathStatus::Inaccessible(error.to_string()),
+    }
+}
+
+pub fn complete_path(path: PathBuf) -> Vec<PathBuf> {
+    let typed = path.to_string_lossy();
+
+    let (directory, prefix) = if typed.ends_with(std::path::is_separator) {
+        (path.as_path(), String::new())
+    } else {
+        match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => (parent, name.to_string_lossy().to_lowercase()),
+            _ => return vec![],
+        }
+    };
+
+    let mut completions: Vec<_> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .to_lowercase()
+                .starts_with(&prefix)
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+
+    completions.sort();
+    completions.truncate(MAX_COMPLETIONS);
+
+    completions
+}
+
+pub fn read_file(
+    path: PathBuf,
+    max_size: u64,
+) -> Result<(PathBuf, Result<Vec<u8>, u64>), ReadError> {
+    let io_error = |error: std::io::Error| ReadError::Io(path.clone(), error.to_string());
+
+    let metadata = fs::metadata(&path).map_err(io_error)?;
+
+    if !metadata.is_file() {
+        return Err(ReadError::NotAFile(path));
+    }
+
+    if metadata.len() > max_size {
+        return Ok((path, Err(metadata.len())));
+    }
+
+    let contents = fs::read(&path).map_err(io_error)?;
+
+    Ok((path, Ok(contents)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Tree {
+        Tree::new(PathBuf::from("/root"), None, Stored::default())
+    }
+
+    fn file(path: &str) -> Entry {
+        let path = PathBuf::from(path);
+
+        Entry::File {
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path,
+            executable: false,
+        }
+    }
+
+    fn directory(path: &str) -> Entry {
+        let path = PathBuf::from(path);
+
+        Entry::Directory {
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path,
+        }
+    }
+
+    fn flatten(effect: Effect) -> Vec<Effect> {
+        match effect {
+            Effect::Batch(effects) => effects.into_iter().flat_map(flatten).collect(),
+            Effect::None => vec![],
+            effect => vec![effect],
+        }
+    }
+
+    fn read(tree: &mut Tree, path: &str, entries: Vec<Entry>) -> Vec<Effect> {
+        let modified = time::SystemTime::UNIX_EPOCH;
+        let message = Message::DirectoryRead(Ok((PathBuf::from(path), Some(modified), entries)));
+
+        flatten(tree.apply(message).0)
+    }
+
+    #[test]
+    fn new_parses_stored_state() {
+        let stored = Stored {
+            pins: vec!["/root/pinned".into()],
+            recent: vec!["/root/recent".into()],
+            ..Stored::default()
+        };
+
+        let tree = Tree::new(PathBuf::from("/root"), None, stored);
+
+        assert_eq!(tree.pinned(), [PathBuf::from("/root/pinned")]);
+        assert_eq!(tree.recent(), [PathBuf::from("/root/recent")]);
+    }
+
+    #[test]
+    fn reading_a_directory_lists_it_and_starts_a_size_scan() {
+        let mut tree = tree();
+
+        let effects = read(
+            &mut tree,
+            "/root",
+            vec![file("/root/b"), directory("/root/a")],
+        );
+
+        let entries: Vec<_> = tree.entries().iter().map(Entry::path).collect();
+        assert_eq!(entries, [Path::new("/root/a"), Path::new("/root/b")]);
+        assert_eq!(tree.selected(), Some(Path::new("/root/a")));
+
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            Effect::StartScan(Scan {
+                kind: ScanKind::Sizes,
+                ..
+            })
+        )));
+    }
+
+    #[test]
+    fn cached_listings_are_revalidated_before_use() {
+        let mut tree = tree();
+        read(&mut tree, "/root", vec![file("/root/a")]);
+        read(&mut tree, "/other", vec![]);
+
+        let (effect, _) = tree.apply(Message::ChangeDirectory(PathBuf::from("/root")));
+        assert_eq!(effect, Effect::Revalidate(PathBuf::from("/root")));
+
+        let changed = Some(time::SystemTime::now());
+        let (effect, _) = tree.apply(Message::Revalidated(PathBuf::from("/root"), changed));
+        assert_eq!(effect, Effect::ReadDirectory(PathBuf::from("/root")));
+
+        read(&mut tree, "/root", vec![file("/root/a")]);
+        read(&mut tree, "/other", vec![]);
+
+        let unchanged = Some(time::SystemTime::UNIX_EPOCH);
+        tree.apply(Message::Revalidated(PathBuf::from("/root"), unchanged));
+        assert_eq!(tree.current_dir(), Path::new("/root"));
+        assert_eq!(tree.entries().len(), 1);
+    }
+
+    #[test]
+    fn navigating_to_a_file_selects_it_in_its_parent() {
+        let mut tree = tree();
+        read(&mut tree, "/root", vec![]);
+
+        let target = PathBuf::from("/other/file");
+        let (effect, _) = tree.apply(Message::NavigateTo(target.clone()));
+        assert_eq!(
+            effect,
+            Effect::Locate(target.clone(), Destination::Navigate)
+        );
+
+        let located = Message::Located(target.clone(), Destination::Navigate, PathStatus::File);
+        let (effect, _) = tree.apply(located);
+
+        assert_eq!(effect, Effect::ReadDirectory(PathBuf::from("/other")));
+        assert_eq!(tree.selected(), Some(target.as_path()));
+    }
+
+    #[test]
+    fn submitting_a_missing_path_fails() {
+        let mut tree = tree();
+        let path = PathBuf::from("/missing");
+
+        let located = Message::Located(path, Destination::Submitted, PathStatus::Missing);
+        let (effect, event) = tree.apply(located);
+
+        assert_eq!(effect, Effect::None);
+        assert!(matches!(event, Some(Event::Failed(_))));
+    }
+
+    #[test]
+    fn going_back_skips_directories_that_are_gone() {
+        let mut tree = tree();
+        read(&mut tree, "/root", vec![]);
+        read(&mut tree, "/first", vec![]);
+        read(&mut tree, "/second", vec![]);
+
+        let (effect, _) = tree.apply(Message::GoBack);
+        assert_eq!(
+            effect,
+            Effect::Locate(PathBuf::from("/first"), Destination::Back)
+        );
+
+        let gone = Message::Located(
+            PathBuf::from("/first"),
+            Destination::Back,
+            PathStatus::Missing,
+        );
+        let (effect, _) = tree.apply(gone);
+        assert_eq!(
+            effect,
+            Effect::Locate(PathBuf::from("/root"), Destination::Back)
+        );
+
+        let found = Message::Located(
+            PathBuf::from("/root"),
+            Destination::Back,
+            PathStatus::Directory,
+        );
+        tree.apply(found);
+        read(&mut tree, "/root", vec![]);
+
+        assert_eq!(tree.current_dir(), Path::new("/root"));
+        assert_eq!(
+            tree.apply(Message::GoForward).0,
+            Effect::Locate(PathBuf::from("/second"), Destination::Forward)
+        );
+    }
+
+    #[test]
+    fn watched_changes_read_the_entry_before_inserting_it() {
+        let mut tree = tree();
+        read(&mut tree, "/root", vec![file("/root/a")]);
+
+        let created = PathBuf::from("/root/b");
+        let (effect, _) = tree.apply(Message::Watched(watcher::Change::Created(created.clone())));
+        assert!(flatten(effect).contains(&Effect::ReadEntry(created.clone())));
+        assert_eq!(tree.entries().len(), 1);
+
+        let metadata = fs::metadata(env!("CARGO_MANIFEST_DIR")).unwrap();
+        tree.apply(Message::EntryRead(
+            created.clone(),
+            Some((directory("/root/b"), metadata)),
+        ));
+
+        let entries: Vec<_> = tree.entries().iter().map(Entry::path).collect();
+        assert_eq!(entries, [created.as_path(), Path::new("/root/a")]);
+    }
+
+    #[test]
+    fn finished_size_scans_only_restart_when_not_cancelled() {
+        let started = |effects: Vec<Effect>| {
+            effects.into_iter().find_map(|effect| match effect {
+                Effect::StartScan(scan) => Some((scan.id, scan.paths)),
+                _ => None,
+            })
+        };
+
+        let mut tree = tree();
+        let (id, _) = started(read(&mut tree, "/root", vec![file("/root/a")])).unwrap();
+
+        let (effect, _) = tree.apply(Message::ScanFinished(id, false));
+        let (restarted, paths) = started(flatten(effect)).unwrap();
+        assert_ne!(restarted, id);
+        assert_eq!(paths, [PathBuf::from("/root/a")]);
+
+        let (effect, _) = tree.apply(Message::ScanFinished(restarted, true));
+        assert_eq!(flatten(effect), []);
+    }
+
+    #[test]
+    fn dropping_a_folder_into_its_own_descendant_fails() {
+        let mut tree = tree();
+        read(&mut tree, "/root/a/b", vec![]);
+
+        let (_, event) = tree.apply(Message::Dropped(PathBuf::from("/root/a")));
+        assert!(matches!(event, Some(Event::Failed(_))));
+
+        let (_, event) = tree.apply(Message::Dropped(PathBuf::from("/root/a/b")));
+        assert!(matches!(event, Some(Event::Failed(_))));
+
+        let (_, event) = tree.apply(Message::Dropped(PathBuf::from("/other/c")));
+        assert!(matches!(event, Some(Event::Dropped { .. })));
+    }
+
+    #[test]
+    fn git_status_reads_are_coalesced_across_visits() {
+        let mut tree = tree();
+        let effects = read(&mut tree, "/root", vec![]);
+        assert!(!effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::ReadGitStatus(_))));
+
+        let now = time::Instant::now() + GIT_STATUS_DELAY;
+        let (effect, _) = tree.apply(Message::GitStatusDue(now));
+        assert_eq!(effect, Effect::ReadGitStatus(PathBuf::from("/root")));
+
+        read(&mut tree, "/first", vec![]);
+        read(&mut tree, "/second", vec![]);
+        assert_eq!(tree.apply(Message::GitStatusDue(now)).0, Effect::None);
+
+        tree.apply(Message::GitStatusRead(PathBuf::from("/root"), None));
+
+        let later = now + GIT_STATUS_DELAY;
+        assert_eq!(
+            tree.apply(Message::GitStatusDue(later)).0,
+            Effect::ReadGitStatus(PathBuf::from("/second"))
+        );
+        assert!(!tree.is_git_status_due());
+    }
+
+    #[test]
+    fn cached_rows_follow_changes() {
+        let mut tree = tree();
+        read(&mut tree, "/root", vec![file("/root/a"), file("/root/b")]);
+
+        assert_eq!(tree.rows().len(), 2);
+        let generation = tree.row_generation();
+        assert_eq!(tree.rows().len(), 2);
+        assert_eq!(tree.row_generation(), generation);
+
+        tree.type_ahead('b');
+        assert_eq!(tree.rows().len(), 1);
+
+        tree.delete_filter_character();
+        tree.apply(Message::Watched(watcher::Change::Removed(PathBuf::from(
+            "/root/a",
+        ))));
+        assert_eq!(tree.rows().len(), 1);
+        assert!(tree.row_generation() > generation);
+    }
+
+    #[test]
+    fn volume_polls_wait_for_the_previous_probe() {
+        let mut tree = tree();
+        read(&mut tree, "/root", vec![]);
+
+        let polled = [
+            Effect::ListVolumes,
+            Effect::ReadSpace(PathBuf::from("/root")),
+        ];
+
+        let effects = flatten(tree.apply(Message::RefreshVolumes).0);
+        assert_eq!(effects, polled);
+        assert_eq!(flatten(tree.apply(Message::RefreshVolumes).0), []);
+
+        tree.apply(Message::VolumesListed(Some(vec![])));
+        tree.apply(Message::SpaceRead(PathBuf::from("/root"), None));
+
+        let effects = flatten(tree.apply(Message::RefreshVolumes).0);
+        assert_eq!(effects, polled);
+
+        tree.apply(Message::VolumesListed(Some(vec![])));
+        tree.apply(Message::SpaceRead(PathBuf::from("/root"), None));
+        tree.apply(Message::DirectoryTimedOut(PathBuf::from("/root")));
+
+        assert_eq!(flatten(tree.apply(Message::RefreshVolumes).0), []);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::plugin;
+use crate::zip;
+
+use std::path::Path;
+
+pub struct Office;
+
+impl plugin::Previewer for Office {
+    fn preview(&self, path: &Path, bytes: &[u8]) -> Option<String> {
+        extract(path, bytes)
+    }
+}
+
+fn extract(path: &Path, bytes: &[u8]) -> Option<String> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+
+    let member = match extension.as_str() {
+        "docx" => "word/document.xml",
+        "odt" => "content.xml",
+        _ => return None,
+    };
+
+    let xml = zip::read(bytes, member)?;
+
+    Some(text(&String::from_utf8_lossy(&xml)))
+}
+
+fn text(xml: &str) -> String {
+    let mut text = String::new();
+    let mut rest = xml;
+    let mut in_tabs = false;
+
+    while let Some(start) = rest.find('<') {
+        push_unescaped(&mut text, &rest[..start]);
+
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        let tag = &rest[start + 1..end];
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (tag.starts_with('/'), name) {
+            (true, "w:p") | (true, "text:p") | (true, "text:h") => text.push('\n'),
+            (false, "w:tabs") => in_tabs = !tag.ends_with('/'),
+            (true, "w:tabs") => in_tabs = false,
+            (false, "w:tab") if !in_tabs => text.push('\t'),
+            (false, "text:tab") => text.push('\t'),
+            (false, "w:br") | (false, "w:cr") | (false, "text:line-break") => text.push('\n'),
+            (false, "text:s") => {
+                let count = attribute(tag, "text:c").and_then(|count| count.parse().ok());
+
+                text.extend(std::iter::repeat_n(' ', count.unwrap_or(1)));
+            }
+            _ => {}
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    text
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let (_, value) = tag.split_once(&format!(" {}=\"", name))?;
+
+    value.split('"').next()
+}
+
+fn push_unescaped(text: &mut String, mut escaped: &str) {
+    while let Some(start) = escaped.find('&') {
+        text.push_str(&escaped[..start]);
+
+        let end = match escaped[start..].find(';') {
+            Some(end) => start + end,
+            None => {
+                text.push_str(&escaped[start..]);
+                return;
+            }
+        };
+
+        let entity = &escaped[start + 1..end];
+
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+
+        match character {
+            Some(character) => {
+                text.push(character);
+                escaped = &escaped[end + 1..];
+            }
+            None => {
+                text.push('&');
+                escaped = &escaped[start + 1..];
+            }
+        }
+    }
+
+    text.push_str(escaped);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_docx_paragraphs_without_tab_stops() {
+        let xml = concat!(
+            r#"<w:document><w:body><w:p><w:pPr><w:tabs><w:tab w:val="left" w:pos="720"/>"#,
+            r#"</w:tabs></w:pPr><w:r><w:t>Fish &amp; chips</w:t><w:tab/><w:t>4</w:t></w:r>"#,
+            r#"</w:p><w:p><w:r><w:t>AT&T &lt;3</w:t></w:r></w:p></w:body></w:document>"#,
+        );
+
+        assert_eq!(text(xml), "Fish & chips\t4\nAT&T <3\n");
+    }
+
+    #[test]
+    fn extracts_odt_paragraphs_with_repeated_spaces() {
+        let xml = concat!(
+            r#"<office:text><text:h>Title</text:h><text:p>a<text:s text:c="3"/>b"#,
+            r#"<text:s/>c<text:tab/>d<text:line-break/>&#x41;&#66;</text:p></office:text>"#,
+        );
+
+        assert_eq!(text(xml), "Title\na   b c\td\nAB\n");
+    }
+
+    #[test]
+    fn keeps_an_unterminated_entity_once() {
+        let mut text = String::new();
+        push_unescaped(&mut text, "fish & chips");
+
+        assert_eq!(text, "fish & chips");
+    }
+}
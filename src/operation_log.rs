@@ -0,0 +1,254 @@
+use iced::button::{self, Button};
+use iced::scrollable::{self, Scrollable};
+use iced::text_input::{self, TextInput};
+use iced::{Align, Column, Command, Element, Length, Row, Text};
+
+use crate::file_ops::Operation;
+use crate::format;
+use crate::i18n;
+use crate::style;
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const LOG_FILE: &str = "operations.log";
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PathChanged(String),
+    Export,
+    Exported(Result<(), String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Applied,
+    Undone,
+    Redone,
+    Ran,
+    Deleted,
+    Restored,
+    Saved,
+    Exported,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Kind::Applied => "Done",
+            Kind::Undone => "Undone",
+            Kind::Redone => "Redone",
+            Kind::Ran => "Ran",
+            Kind::Deleted => "Deleted",
+            Kind::Restored => "Restored",
+            Kind::Saved => "Saved",
+            Kind::Exported => "Exported",
+        };
+
+        f.write_str(label)
+    }
+}
+
+struct Entry {
+    time: SystemTime,
+    user: String,
+    kind: Kind,
+    outcome: Result<String, String>,
+}
+
+impl Entry {
+    fn line(&self) -> String {
+        let (status, description) = match &self.outcome {
+            Ok(description) => ("OK", description),
+            Err(error) => ("FAILED", error),
+        };
+
+        format!(
+            "{} UTC\t{}\t{}\t{}\t{}",
+            format::timestamp(self.time, ' '),
+            self.user,
+            self.kind,
+            status,
+            description
+        )
+    }
+}
+
+pub struct State {
+    entries: Vec<Entry>,
+    user: String,
+    path: String,
+    status: Option<Result<(), String>>,
+    scrollable: scrollable::State,
+    input: text_input::State,
+    export_button: button::State,
+}
+
+impl State {
+    pub fn new(directory: PathBuf) -> Self {
+        let user = env::var("USER")
+            .or_else(|_| env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".into());
+
+        Self {
+            entries: vec![],
+            user,
+            path: directory.join(LOG_FILE).to_string_lossy().to_string(),
+            status: None,
+            scrollable: scrollable::State::new(),
+            input: text_input::State::new(),
+            export_button: button::State::new(),
+        }
+    }
+
+    pub fn record(&mut self, kind: Kind, result: &Result<Operation, String>) {
+        self.push(
+            kind,
+            result
+                .as_ref()
+                .map(Operation::to_string)
+                .map_err(Clone::clone),
+        );
+    }
+
+    pub fn record_deletion(&mut self, result: &Result<PathBuf, String>) {
+        self.push(
+            Kind::Deleted,
+            result
+                .as_ref()
+                .map(|path| format!("Deleted {:?} permanently", path))
+                .map_err(Clone::clone),
+        );
+    }
+
+    pub fn record_restore(&mut self, path: &Path, result: &Result<(), String>) {
+        self.push(
+            Kind::Restored,
+            result
+                .as_ref()
+                .map(|()| format!("Restored {:?} from the trash", path))
+                .map_err(Clone::clone),
+        );
+    }
+
+    pub fn record_save(&mut self, path: &Path, result: &Result<(), String>) {
+        self.push(
+            Kind::Saved,
+            result
+                .as_ref()
+                .map(|()| format!("Saved edited bytes to {:?}", path))
+                .map_err(Clone::clone),
+        );
+    }
+
+    pub fn record_export(&mut self, result: &Result<(PathBuf, usize), String>) {
+        self.push(
+            Kind::Exported,
+            result
+                .as_ref()
+                .map(|(path, count)| format!("Exported {} entries to {:?}", count, path))
+                .map_err(Clone::clone),
+        );
+    }
+
+    pub fn record_command(&mut self, label: &str, result: &Result<String, String>) {
+        self.push(
+            Kind::Ran,
+            result
+                .as_ref()
+                .map(|status| format!("{}: {}", label, status))
+                .map_err(|error| format!("{}: {}", label, error)),
+        );
+    }
+
+    fn push(&mut self, kind: Kind, outcome: Result<String, String>) {
+        self.entries.push(Entry {
+            time: SystemTime::now(),
+            user: self.user.clone(),
+            kind,
+            outcome,
+        });
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::PathChanged(path) => {
+                self.path = path;
+                self.status = None;
+            }
+            Message::Export => {
+                let lines: Vec<_> = self.entries.iter().map(Entry::line).collect();
+                let path = PathBuf::from(self.path.trim());
+
+                return Command::perform(
+                    async move {
+                        let mut contents = lines.join("\n");
+                        contents.push('\n');
+
+                        fs::write(&path, contents)
+                            .map_err(|error| format!("Cannot write {:?}: {}", path, error))
+                    },
+                    Message::Exported,
+                );
+            }
+            Message::Exported(result) => {
+                self.status = Some(result);
+            }
+        }
+
+        Command::none()
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let input = TextInput::new(&mut state.input, "", &state.path, Message::PathChanged)
+        .padding(5)
+        .width(Length::Units(400))
+        .on_submit(Message::Export);
+
+    let mut export = Button::new(&mut state.export_button, Text::new(i18n::tr("Export")));
+
+    if !state.entries.is_empty() {
+        export = export.on_press(Message::Export);
+    }
+
+    let mut controls = Row::new()
+        .spacing(10)
+        .align_items(Align::Center)
+        .push(input)
+        .push(export);
+
+    match &state.status {
+        Some(Ok(())) => controls = controls.push(Text::new(i18n::tr("Exported")).size(14)),
+        Some(Err(error)) => {
+            controls = controls.push(Text::new(error.as_str()).size(14).color(style::ERROR))
+        }
+        None => {}
+    }
+
+    let mut scrollable = Scrollable::new(&mut state.scrollable).spacing(5);
+
+    if state.entries.is_empty() {
+        scrollable = scrollable.push(Text::new(i18n::tr("No file operations yet")));
+    }
+
+    for entry in state.entries.iter().rev() {
+        let text = Text::new(entry.line().replace('\t', "  ")).size(14);
+
+        scrollable = scrollable.push(match entry.outcome {
+            Ok(_) => text,
+            Err(_) => text.color(style::ERROR),
+        });
+    }
+
+    Column::new()
+        .spacing(10)
+        .width(Length::Fill)
+        .push(Text::new(i18n::tr("Operation log")).size(30))
+        .push(controls)
+        .push(scrollable)
+        .into()
+}
@@ -0,0 +1,176 @@
+use iced::button::{self, Button};
+use iced::scrollable::{self, Scrollable};
+use iced::text_input::{self, TextInput};
+use iced::{Column, Container, Element, Length, Row, Space, Text};
+
+use crate::font;
+use crate::fuzzy;
+use crate::i18n;
+use crate::index::Index;
+use crate::keymap::{Action, KeyMap};
+use crate::style;
+
+use std::path::PathBuf;
+
+const MIN_FILE_QUERY: usize = 2;
+const MAX_FILES: usize = 20;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    QueryChanged(String),
+    Submit,
+    Run(Action),
+    Open(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub enum Choice {
+    Action(Action),
+    File(PathBuf),
+}
+
+pub struct State {
+    query: String,
+    input: text_input::State,
+    index: Index,
+    matches: Vec<Choice>,
+    buttons: Vec<button::State>,
+    selected: usize,
+    scrollable: scrollable::State,
+}
+
+impl State {
+    pub fn new(index: Index) -> Self {
+        let mut state = Self {
+            query: String::new(),
+            input: text_input::State::focused(),
+            index,
+            matches: vec![],
+            buttons: vec![],
+            selected: 0,
+            scrollable: scrollable::State::new(),
+        };
+
+        state.filter();
+        state
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Choice> {
+        match message {
+            Message::QueryChanged(query) => {
+                self.query = query;
+                self.filter();
+
+                None
+            }
+            Message::Submit => self.matches.get(self.selected).cloned(),
+            Message::Run(action) => Some(Choice::Action(action)),
+            Message::Open(path) => Some(Choice::File(path)),
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+
+    fn filter(&mut self) {
+        let mut matches: Vec<_> = Action::ALL
+            .iter()
+            .filter_map(|action| {
+                fuzzy::score(&self.query, action.description()).map(|score| (score, *action))
+            })
+            .collect();
+
+        matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        self.matches = matches
+            .into_iter()
+            .map(|(_, action)| Choice::Action(action))
+            .collect();
+
+        if self.query.trim().len() >= MIN_FILE_QUERY {
+            self.matches.extend(
+                self.index
+                    .find(&self.query, MAX_FILES)
+                    .into_iter()
+                    .map(Choice::File),
+            );
+        }
+
+        self.buttons = vec![button::State::new(); self.matches.len()];
+        self.selected = 0;
+    }
+}
+
+pub fn view<'a>(state: &'a mut State, keymap: &KeyMap) -> Element<'a, Message> {
+    let input = TextInput::new(
+        &mut state.input,
+        i18n::tr("Type a command"),
+        &state.query,
+        Message::QueryChanged,
+    )
+    .padding(10)
+    .on_submit(Message::Submit);
+
+    let mut scrollable = Scrollable::new(&mut state.scrollable);
+
+    for (index, (choice, button)) in state
+        .matches
+        .iter()
+        .zip(state.buttons.iter_mut())
+        .enumerate()
+    {
+        let (content, message) = match choice {
+            Choice::Action(action) => {
+                let binding = keymap
+                    .binding(*action)
+                    .map(|binding| binding.to_string())
+                    .unwrap_or_default();
+
+                let content = Row::new()
+                    .push(Text::new(action.description()))
+                    .push(Space::with_width(Length::Fill))
+                    .push(Text::new(binding).font(font::MONOSPACE));
+
+                (content, Message::Run(*action))
+            }
+            Choice::File(path) => {
+                let content = Row::new()
+                    .push(Text::new(path.to_string_lossy()))
+                    .push(Space::with_width(Length::Fill))
+                    .push(Text::new(i18n::tr("File")).color(style::GUTTER));
+
+                (content, Message::Open(path.clone()))
+            }
+        };
+
+        let mut button = Button::new(button, content)
+            .width(Length::Fill)
+            .on_press(message);
+
+        if index == state.selected {
+            button = button.style(style::Selected);
+        }
+
+        scrollable = scrollable.push(button);
+    }
+
+    let content = Column::new()
+        .spacing(10)
+        .max_width(600)
+        .push(input)
+        .push(scrollable);
+
+    Container::new(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(40)
+        .center_x()
+        .into()
+}
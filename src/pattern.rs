@@ -0,0 +1,895 @@
+use std::cell::Cell;
+
+const MAX_STEPS: usize = 100_000;
+const STEPS_PER_CHAR: usize = 64;
+const MAX_DEPTH: usize = 2048;
+
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    node: Node,
+    groups: usize,
+    case_sensitive: bool,
+    expand: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found {
+    pub start: usize,
+    pub end: usize,
+    pub groups: Vec<Option<(usize, usize)>>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Literal(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    WordBoundary,
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Class {
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl Class {
+    fn of(ranges: &[(char, char)], negated: bool) -> Self {
+        Self {
+            ranges: ranges.to_vec(),
+            negated,
+        }
+    }
+
+    fn contains(&self, character: char, case_sensitive: bool) -> bool {
+        let in_ranges = |character: char| {
+            self.ranges
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&character))
+        };
+
+        let found = in_ranges(character)
+            || (!case_sensitive
+                && (in_ranges(lowercase(character)) || in_ranges(uppercase(character))));
+
+        found != self.negated
+    }
+}
+
+const DIGIT: &[(char, char)] = &[('0', '9')];
+const WORD: &[(char, char)] = &[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+const SPACE: &[(char, char)] = &[(' ', ' '), ('\t', '\r')];
+
+impl Pattern {
+    pub fn new(pattern: &str, case_sensitive: bool) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            position: 0,
+            groups: 0,
+        };
+
+        let node = parser.alternation()?;
+
+        if parser.position < parser.chars.len() {
+            return Err("Unmatched closing parenthesis".into());
+        }
+
+        Ok(Self {
+            node,
+            groups: parser.groups,
+            case_sensitive,
+            expand: true,
+        })
+    }
+
+    pub fn literal(text: &str, case_sensitive: bool) -> Self {
+        Self {
+            node: Node::Concat(text.chars().map(Node::Literal).collect()),
+            groups: 0,
+            case_sensitive,
+            expand: false,
+        }
+    }
+
+    pub fn find(&self, text: &str) -> Result<Option<Found>, String> {
+        let haystack = Haystack::new(text);
+
+        Ok(self
+            .find_at(&haystack, 0)?
+            .map(|(start, end, groups)| haystack.found(start, end, groups)))
+    }
+
+    pub fn replace_all(
+        &self,
+        text: &str,
+        replacement: &str,
+        limit: Option<usize>,
+    ) -> Result<(String, usize), String> {
+        let mut replaced = String::with_capacity(text.len());
+        let mut count = 0;
+
+        for line in text.split_inclusive('\n') {
+            let content = line.trim_end_matches(&['\r', '\n'][..]);
+            let haystack = Haystack::new(content);
+            let mut position = 0;
+            let mut copied = 0;
+
+            while limit.is_none_or(|limit| count < limit) {
+                let (start, end, groups) = match self.find_at(&haystack, position)? {
+                    Some(found) => found,
+                    None => break,
+                };
+                let found = haystack.found(start, end, groups);
+
+                replaced.push_str(&content[copied..found.start]);
+                replaced.push_str(&self.expand(content, &found, replacement));
+                copied = found.end;
+                count += 1;
+
+                position = if end > start {
+                    end
+                } else if end < haystack.chars.len() {
+                    end + 1
+                } else {
+                    break;
+                };
+            }
+
+            replaced.push_str(&line[copied..]);
+        }
+
+        Ok((replaced, count))
+    }
+
+    fn expand(&self, text: &str, found: &Found, replacement: &str) -> String {
+        if !self.expand {
+            return replacement.to_string();
+        }
+
+        let mut expanded = String::new();
+        let mut chars = replacement.chars().peekable();
+
+        while let Some(character) = chars.next() {
+            match (character, chars.peek().copied()) {
+                ('$', Some('$')) => {
+                    chars.next();
+                    expanded.push('$');
+                }
+                ('$', Some(digit)) if digit.is_ascii_digit() => {
+                    chars.next();
+
+                    let group = match digit.to_digit(10).unwrap_or_default() as usize {
+                        0 => Some((found.start, found.end)),
+                        index => found.groups.get(index - 1).copied().flatten(),
+                    };
+
+                    if let Some((start, end)) = group {
+                        expanded.push_str(&text[start..end]);
+                    }
+                }
+                (character, _) => expanded.push(character),
+            }
+        }
+
+        expanded
+    }
+
+    fn find_at(
+        &self,
+        haystack: &Haystack,
+        first: usize,
+    ) -> Result<Option<(usize, usize, Groups)>, String> {
+        let chars = &haystack.chars;
+
+        let matcher = Matcher {
+            pattern: self,
+            chars,
+            budget: MAX_STEPS + chars.len() * STEPS_PER_CHAR,
+            steps: Cell::new(0),
+            depth: Cell::new(0),
+            exhausted: Cell::new(false),
+        };
+
+        for start in first..=chars.len() {
+            let mut groups = vec![None; self.groups];
+            let mut end = None;
+
+            let matched = matcher.matches(&self.node, start, &mut groups, &mut |position, _| {
+                end = Some(position);
+                true
+            });
+
+            if matched {
+                return Ok(Some((start, end.unwrap_or(start), groups)));
+            }
+
+            if matcher.exhausted.get() {
+                return Err("Pattern is too complex to search this line".into());
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+type Groups = Vec<Option<(usize, usize)>>;
+
+struct Haystack {
+    chars: Vec<char>,
+    offsets: Vec<usize>,
+}
+
+impl Haystack {
+    fn new(text: &str) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            offsets: text
+                .char_indices()
+                .map(|(offset, _)| offset)
+                .chain(std::iter::once(text.len()))
+                .collect(),
+        }
+    }
+
+    fn found(&self, start: usize, end: usize, groups: Groups) -> Found {
+        Found {
+            start: self.offsets[start],
+            end: self.offsets[end],
+            groups: groups
+                .into_iter()
+                .map(|group| group.map(|(start, end)| (self.offsets[start], self.offsets[end])))
+                .collect(),
+        }
+    }
+}
+
+struct Matcher<'a> {
+    pattern: &'a Pattern,
+    chars: &'a [char],
+    budget: usize,
+    steps: Cell<usize>,
+    depth: Cell<usize>,
+    exhausted: Cell<bool>,
+}
+
+impl<'a> Matcher<'a> {
+    fn matches(
+        &self,
+        node: &Node,
+        position: usize,
+        groups: &mut Groups,
+        next: &mut dyn FnMut(usize, &mut Groups) -> bool,
+    ) -> bool {
+        if !self.step() || self.depth.get() >= MAX_DEPTH {
+            self.exhausted.set(true);
+            return false;
+        }
+
+        self.depth.set(self.depth.get() + 1);
+        let matched = self.matches_node(node, position, groups, next);
+        self.depth.set(self.depth.get() - 1);
+
+        matched
+    }
+
+    fn matches_node(
+        &self,
+        node: &Node,
+        position: usize,
+        groups: &mut Groups,
+        next: &mut dyn FnMut(usize, &mut Groups) -> bool,
+    ) -> bool {
+        let current = self.chars.get(position).copied();
+
+        match node {
+            Node::Empty => next(position, groups),
+            Node::Literal(_) | Node::Any | Node::Class(_) => {
+                current.is_some_and(|character| self.single(node, character))
+                    && next(position + 1, groups)
+            }
+            Node::Start => position == 0 && next(position, groups),
+            Node::End => current.is_none() && next(position, groups),
+            Node::WordBoundary => self.is_boundary(position) && next(position, groups),
+            Node::Group(node, None) => self.matches(node, position, groups, next),
+            Node::Group(node, Some(index)) => {
+                let index = *index;
+
+                self.matches(node, position, groups, &mut |end, groups| {
+                    let previous = groups[index];
+                    groups[index] = Some((position, end));
+
+                    if next(end, groups) {
+                        return true;
+                    }
+
+                    groups[index] = previous;
+                    false
+                })
+            }
+            Node::Concat(nodes) => self.concat(nodes, position, groups, next),
+            Node::Alternate(options) => options
+                .iter()
+                .any(|option| self.matches(option, position, groups, next)),
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } if matches!(**node, Node::Literal(_) | Node::Any | Node::Class(_)) => {
+                self.run(node, (*min, *max, *greedy), position, groups, next)
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } if node.fixed_width().is_some() => {
+                self.repeat_fixed(node, (*min, *max, *greedy), position, groups, next)
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } => self.repeat(node, (*min, *max, *greedy), 0, position, groups, next),
+        }
+    }
+
+    fn concat(
+        &self,
+        nodes: &[Node],
+        position: usize,
+        groups: &mut Groups,
+        next: &mut dyn FnMut(usize, &mut Groups) -> bool,
+    ) -> bool {
+        match nodes.split_first() {
+            None => next(position, groups),
+            Some((first, rest)) => self.matches(first, position, groups, &mut |end, groups| {
+                self.concat(rest, end, groups, next)
+            }),
+        }
+    }
+
+    fn repeat(
+        &self,
+        node: &Node,
+        (min, max, greedy): (usize, Option<usize>, bool),
+        count: usize,
+        position: usize,
+        groups: &mut Groups,
+        next: &mut dyn FnMut(usize, &mut Groups) -> bool,
+    ) -> bool {
+        let can_stop = count >= min;
+        let can_continue = max.is_none_or(|max| count < max);
+
+        if !greedy && can_stop && next(position, groups) {
+            return true;
+        }
+
+        if can_continue
+            && self.matches(node, position, groups, &mut |end, groups| {
+                (end != position || count < min)
+                    && self.repeat(node, (min, max, greedy), count + 1, end, groups, next)
+            })
+        {
+            return true;
+        }
+
+        greedy && can_stop && next(position, groups)
+    }
+
+    fn repeat_fixed(
+        &self,
+        node: &Node,
+        (min, max, greedy): (usize, Option<usize>, bool),
+        position: usize,
+        groups: &mut Groups,
+        next: &mut dyn FnMut(usize, &mut Groups) -> bool,
+    ) -> bool {
+        let limit = max.unwrap_or(usize::MAX);
+        let mut ends = vec![(position, groups.clone())];
+
+        loop {
+            let count = ends.len() - 1;
+            let (position, captured) = ends[count].clone();
+
+            if !greedy && count >= min {
+                let mut attempt = captured.clone();
+
+                if next(position, &mut attempt) {
+                    *groups = attempt;
+                    return true;
+                }
+            }
+
+            if count >= limit {
+                break;
+            }
+
+            let mut attempt = captured;
+            let mut end = None;
+
+            let matched = self.matches(node, position, &mut attempt, &mut |matched, _| {
+                end = Some(matched);
+                true
+            });
+
+            match end.filter(|_| matched) {
+                Some(end) if end != position || count < min => ends.push((end, attempt)),
+                _ => break,
+            }
+        }
+
+        if !greedy {
+            return false;
+        }
+
+        while ends.len() > min {
+            if let Some((position, mut attempt)) = ends.pop() {
+                if next(position, &mut attempt) {
+                    *groups = attempt;
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn run(
+        &self,
+        node: &Node,
+        (min, max, greedy): (usize, Option<usize>, bool),
+        position: usize,
+        groups: &mut Groups,
+        next: &mut dyn FnMut(usize, &mut Groups) -> bool,
+    ) -> bool {
+        let limit = max.unwrap_or(usize::MAX);
+        let mut end = position;
+
+        while end - position < limit
+            && self
+                .chars
+                .get(end)
+                .is_some_and(|character| self.single(node, *character))
+        {
+            if !self.step() {
+                self.exhausted.set(true);
+                return false;
+            }
+
+            end += 1;
+        }
+
+        if end - position < min {
+            return false;
+        }
+
+        let mut ends = position + min..=end;
+
+        if greedy {
+            ends.rev().any(|end| next(end, groups))
+        } else {
+            ends.any(|end| next(end, groups))
+        }
+    }
+
+    fn step(&self) -> bool {
+        self.steps.set(self.steps.get() + 1);
+
+        self.steps.get() <= self.budget
+    }
+
+    fn single(&self, node: &Node, character: char) -> bool {
+        match node {
+            Node::Literal(expected) => self.equal(character, *expected),
+            Node::Any => true,
+            Node::Class(class) => class.contains(character, self.pattern.case_sensitive),
+            _ => false,
+        }
+    }
+
+    fn equal(&self, a: char, b: char) -> bool {
+        a == b || (!self.pattern.case_sensitive && lowercase(a) == lowercase(b))
+    }
+
+    fn is_boundary(&self, position: usize) -> bool {
+        let is_word = |position: Option<usize>| {
+            position
+                .and_then(|position| self.chars.get(position))
+                .is_some_and(|character| Class::of(WORD, false).contains(*character, true))
+        };
+
+        is_word(position.checked_sub(1)) != is_word(Some(position))
+    }
+}
+
+impl Node {
+    fn fixed_width(&self) -> Option<usize> {
+        match self {
+            Node::Empty | Node::Start | Node::End | Node::WordBoundary => Some(0),
+            Node::Literal(_) | Node::Any | Node::Class(_) => Some(1),
+            Node::Group(node, _) => node.fixed_width(),
+            Node::Concat(nodes) => nodes.iter().map(Node::fixed_width).sum(),
+            Node::Alternate(options) => {
+                let width = options.first()?.fixed_width()?;
+
+                (!self.captures()
+                    && options
+                        .iter()
+                        .all(|option| option.fixed_width() == Some(width)))
+                .then_some(width)
+            }
+            Node::Repeat {
+                node,
+                min,
+                max: Some(max),
+                ..
+            } if min == max => Some(node.fixed_width()? * min),
+            Node::Repeat { .. } => None,
+        }
+    }
+
+    fn captures(&self) -> bool {
+        match self {
+            Node::Group(_, Some(_)) => true,
+            Node::Group(node, None) | Node::Repeat { node, .. } => node.captures(),
+            Node::Concat(nodes) | Node::Alternate(nodes) => nodes.iter().any(Node::captures),
+            _ => false,
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    groups: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.peek()?;
+        self.position += 1;
+
+        Some(character)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn alternation(&mut self) -> Result<Node, String> {
+        let mut options = vec![self.concat()?];
+
+        while self.eat('|') {
+            options.push(self.concat()?);
+        }
+
+        if options.len() == 1 {
+            Ok(options.remove(0))
+        } else {
+            Ok(Node::Alternate(options))
+        }
+    }
+
+    fn concat(&mut self) -> Result<Node, String> {
+        let mut nodes = vec![];
+
+        while let Some(character) = self.peek() {
+            if character == '|' || character == ')' {
+                break;
+            }
+
+            let atom = self.atom()?;
+            nodes.push(self.quantifier(atom)?);
+        }
+
+        match nodes.len() {
+            0 => Ok(Node::Empty),
+            1 => Ok(nodes.remove(0)),
+            _ => Ok(Node::Concat(nodes)),
+        }
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        let character = self.next().unwrap_or_default();
+
+        match character {
+            '(' => {
+                let index = if self.eat('?') {
+                    if !self.eat(':') {
+                        return Err("Only non-capturing groups (?:...) are supported".into());
+                    }
+
+                    None
+                } else {
+                    self.groups += 1;
+
+                    Some(self.groups - 1)
+                };
+
+                let node = self.alternation()?;
+
+                if !self.eat(')') {
+                    return Err("Unclosed group".into());
+                }
+
+                Ok(Node::Group(Box::new(node), index))
+            }
+            '[' => self.class().map(Node::Class),
+            '.' => Ok(Node::Any),
+            '^' => Ok(Node::Start),
+            '$' => Ok(Node::End),
+            '\\' => self.escape(),
+            '*' | '+' | '?' | '{' => Err(format!("Nothing to repeat before '{}'", character)),
+            character => Ok(Node::Literal(character)),
+        }
+    }
+
+    fn escape(&mut self) -> Result<Node, String> {
+        let character = self
+            .next()
+            .ok_or_else(|| "Trailing backslash".to_string())?;
+
+        Ok(match character {
+            'd' => Node::Class(Class::of(DIGIT, false)),
+            'D' => Node::Class(Class::of(DIGIT, true)),
+            'w' => Node::Class(Class::of(WORD, false)),
+            'W' => Node::Class(Class::of(WORD, true)),
+            's' => Node::Class(Class::of(SPACE, false)),
+            'S' => Node::Class(Class::of(SPACE, true)),
+            'b' => Node::WordBoundary,
+            character => Node::Literal(unescape(character)?),
+        })
+    }
+
+    fn class(&mut self) -> Result<Class, String> {
+        let negated = self.eat('^');
+        let mut ranges = vec![];
+        let mut first = true;
+
+        loop {
+            let character = self
+                .next()
+                .ok_or_else(|| "Unclosed character class".to_string())?;
+
+            if character == ']' && !first {
+                break;
+            }
+
+            first = false;
+
+            let start = if character == '\\' {
+                let escaped = self
+                    .next()
+                    .ok_or_else(|| "Unclosed character class".to_string())?;
+
+                match escaped {
+                    'd' => {
+                        ranges.extend_from_slice(DIGIT);
+                        continue;
+                    }
+                    'w' => {
+                        ranges.extend_from_slice(WORD);
+                        continue;
+                    }
+                    's' => {
+                        ranges.extend_from_slice(SPACE);
+                        continue;
+                    }
+                    escaped => unescape(escaped)?,
+                }
+            } else {
+                character
+            };
+
+            let is_range =
+                self.peek() == Some('-') && self.chars.get(self.position + 1) != Some(&']');
+
+            if is_range {
+                self.position += 1;
+
+                let end = match self.next() {
+                    Some('\\') => unescape(self.next().unwrap_or_default())?,
+                    Some(end) => end,
+                    None => return Err("Unclosed character class".into()),
+                };
+
+                if end < start {
+                    return Err(format!("Invalid range {}-{}", start, end));
+                }
+
+                ranges.push((start, end));
+            } else {
+                ranges.push((start, start));
+            }
+        }
+
+        Ok(Class { ranges, negated })
+    }
+
+    fn quantifier(&mut self, node: Node) -> Result<Node, String> {
+        let (min, max) = if self.eat('*') {
+            (0, None)
+        } else if self.eat('+') {
+            (1, None)
+        } else if self.eat('?') {
+            (0, Some(1))
+        } else if self.eat('{') {
+            self.counts()?
+        } else {
+            return Ok(node);
+        };
+
+        if matches!(node, Node::Start | Node::End | Node::WordBoundary) {
+            return Err("Anchors cannot be repeated".into());
+        }
+
+        let greedy = !self.eat('?');
+
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    fn counts(&mut self) -> Result<(usize, Option<usize>), String> {
+        let min = self
+            .number()
+            .ok_or_else(|| "Invalid repetition".to_string())?;
+
+        let max = if self.eat(',') {
+            self.number()
+        } else {
+            Some(min)
+        };
+
+        if !self.eat('}') {
+            return Err("Invalid repetition".into());
+        }
+
+        if max.is_some_and(|max| max < min) {
+            return Err(format!("Invalid repetition {{{},{:?}}}", min, max));
+        }
+
+        Ok((min, max))
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        let start = self.position;
+
+        while self
+            .peek()
+            .is_some_and(|character| character.is_ascii_digit())
+        {
+            self.position += 1;
+        }
+
+        self.chars[start..self.position]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+}
+
+fn unescape(character: char) -> Result<char, String> {
+    match character {
+        'n' => Ok('\n'),
+        't' => Ok('\t'),
+        'r' => Ok('\r'),
+        character if character.is_ascii_alphanumeric() => {
+            Err(format!("Unsupported escape \\{}", character))
+        }
+        character => Ok(character),
+    }
+}
+
+fn lowercase(character: char) -> char {
+    character.to_lowercase().next().unwrap_or(character)
+}
+
+fn uppercase(character: char) -> char {
+    character.to_uppercase().next().unwrap_or(character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_matches_past_the_first_kilobyte() {
+        let line = format!("{}needle", "a".repeat(2000));
+        let found = Pattern::new("needle$", true).unwrap().find(&line).unwrap();
+
+        assert_eq!(found.map(|found| found.start), Some(2000));
+        assert_eq!(Pattern::new("a$", true).unwrap().find(&line), Ok(None));
+    }
+
+    #[test]
+    fn replaces_without_dropping_the_rest_of_a_long_line() {
+        let line = format!("{}bc{}\n", "a".repeat(1022), "d".repeat(100));
+        let pattern = Pattern::new("bc", true).unwrap();
+
+        let (replaced, count) = pattern.replace_all(&line, "X", None).unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(
+            replaced,
+            format!("{}X{}\n", "a".repeat(1022), "d".repeat(100))
+        );
+    }
+
+    #[test]
+    fn repeats_across_long_lines() {
+        let line = format!("start{}end", "x".repeat(100_000));
+        let found = Pattern::new("^s.*d$", true).unwrap().find(&line).unwrap();
+
+        assert_eq!(found.map(|found| found.end), Some(line.len()));
+    }
+
+    #[test]
+    fn repeats_groups_across_long_lines() {
+        let line = format!("{}c", "ab".repeat(10_000));
+        let found = Pattern::new("^(ab)*c$", true).unwrap().find(&line).unwrap();
+
+        assert_eq!(
+            found,
+            Some(Found {
+                start: 0,
+                end: line.len(),
+                groups: vec![Some((line.len() - 3, line.len() - 1))],
+            })
+        );
+
+        let found = Pattern::new("(?:a|b)+?c", true)
+            .unwrap()
+            .find(&line)
+            .unwrap();
+
+        assert_eq!(found.map(|found| found.end), Some(line.len()));
+    }
+
+    #[test]
+    fn replaces_every_match_on_a_long_line() {
+        let line = "ab".repeat(10_000);
+        let pattern = Pattern::new("(a)b", true).unwrap();
+
+        let (replaced, count) = pattern.replace_all(&line, "$1", None).unwrap();
+
+        assert_eq!(count, 10_000);
+        assert_eq!(replaced, "a".repeat(10_000));
+    }
+
+    #[test]
+    fn reports_patterns_that_are_too_complex() {
+        let line = "a".repeat(5000);
+        let pattern = Pattern::new("(a|aa)*b", true).unwrap();
+
+        assert!(pattern.find(&line).is_err());
+        assert!(pattern.replace_all(&line, "", None).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use iced::button::{self, Button};
+use iced::{Element, Row, Text};
+
+use crate::style;
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+pub trait Previewer: Send + Sync {
+    fn preview(&self, path: &Path, bytes: &[u8]) -> Option<String>;
+}
+
+pub trait Action: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn description(&self) -> &str;
+
+    fn applies_to(&self, _path: &Path) -> bool {
+        true
+    }
+
+    fn command(&self, path: &Path) -> Result<(PathBuf, Vec<String>), String>;
+}
+
+#[derive(Default)]
+pub struct Registry {
+    previewers: Vec<Box<dyn Previewer>>,
+    actions: Vec<Box<dyn Action>>,
+}
+
+impl Registry {
+    pub fn previewer(mut self, previewer: impl Previewer + 'static) -> Self {
+        self.previewers.push(Box::new(previewer));
+        self
+    }
+
+    pub fn action(mut self, action: impl Action + 'static) -> Self {
+        self.actions.push(Box::new(action));
+        self
+    }
+
+    pub fn install(self) {
+        let _ = REGISTRY.set(self);
+    }
+}
+
+fn registry() -> &'static Registry {
+    REGISTRY.get_or_init(Registry::default)
+}
+
+pub fn preview(path: &Path, bytes: &[u8]) -> Option<String> {
+    registry()
+        .previewers
+        .iter()
+        .find_map(|previewer| previewer.preview(path, bytes))
+}
+
+pub fn action(index: usize) -> Option<&'static dyn Action> {
+    registry().actions.get(index).map(Box::as_ref)
+}
+
+#[derive(Default)]
+pub struct ActionBar {
+    buttons: Vec<button::State>,
+}
+
+impl ActionBar {
+    pub fn is_empty(&self) -> bool {
+        registry().actions.is_empty()
+    }
+}
+
+pub fn view<'a>(state: &'a mut ActionBar, selected: Option<&Path>) -> Element<'a, usize> {
+    let actions = &registry().actions;
+
+    state.buttons.resize_with(actions.len(), button::State::new);
+
+    actions
+        .iter()
+        .zip(state.buttons.iter_mut())
+        .enumerate()
+        .fold(Row::new().spacing(5), |row, (index, (action, button))| {
+            let mut button = Button::new(button, Text::new(action.name()));
+
+            if selected.is_some_and(|path| action.applies_to(path)) {
+                button = button.on_press(index);
+            }
+
+            row.push(style::labelled(button, action.description()))
+        })
+        .into()
+}
@@ -0,0 +1,67 @@
+use iced::futures::channel::oneshot;
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Probe {
+    Directory(PathBuf),
+    File(PathBuf),
+    Completion(PathBuf),
+    Check(PathBuf),
+    Entry(PathBuf),
+    Space(PathBuf),
+    Size(PathBuf),
+    Volumes,
+}
+
+fn hung() -> MutexGuard<'static, HashSet<Probe>> {
+    static HUNG: OnceLock<Mutex<HashSet<Probe>>> = OnceLock::new();
+
+    HUNG.get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub async fn with_timeout<T: Send + 'static>(
+    probe: Probe,
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    if hung().contains(&probe) {
+        return None;
+    }
+
+    let (sender, receiver) = oneshot::channel();
+    let done = Arc::new(AtomicBool::new(false));
+
+    thread::spawn({
+        let probe = probe.clone();
+        let done = done.clone();
+
+        move || {
+            let _ = sender.send(work());
+
+            done.store(true, Ordering::SeqCst);
+            hung().remove(&probe);
+        }
+    });
+
+    match async_std::future::timeout(READ_TIMEOUT, receiver).await {
+        Ok(result) => result.ok(),
+        Err(_) => {
+            let mut hung = hung();
+
+            if !done.load(Ordering::SeqCst) {
+                hung.insert(probe);
+            }
+
+            None
+        }
+    }
+}
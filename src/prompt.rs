@@ -0,0 +1,141 @@
+use iced::button::{self, Button};
+use iced::text_input::{self, TextInput};
+use iced::{Column, Element, Row, Text};
+
+use crate::file_ops::{Kind as EntryKind, Request};
+use crate::i18n;
+
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ValueChanged(String),
+    Confirm,
+    Cancel,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Submitted(Request),
+    Cancelled,
+}
+
+#[derive(Debug, Clone)]
+pub enum Kind {
+    Create { directory: PathBuf, kind: EntryKind },
+    Rename(PathBuf),
+    Copy(PathBuf),
+    Move(PathBuf),
+}
+
+pub struct State {
+    kind: Kind,
+    value: String,
+    input: text_input::State,
+    confirm_button: button::State,
+    cancel_button: button::State,
+}
+
+impl State {
+    pub fn new(kind: Kind) -> Self {
+        let value = match &kind {
+            Kind::Create { .. } => String::new(),
+            Kind::Rename(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            Kind::Copy(path) | Kind::Move(path) => path
+                .parent()
+                .map(|parent| parent.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+
+        let mut input = text_input::State::focused();
+        input.move_cursor_to_end();
+
+        Self {
+            kind,
+            value,
+            input,
+            confirm_button: button::State::new(),
+            cancel_button: button::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::ValueChanged(value) => {
+                self.value = value;
+
+                None
+            }
+            Message::Confirm => self.request().map(Event::Submitted),
+            Message::Cancel => Some(Event::Cancelled),
+        }
+    }
+
+    fn request(&self) -> Option<Request> {
+        let value = self.value.trim();
+
+        if value.is_empty() {
+            return None;
+        }
+
+        let request = match &self.kind {
+            Kind::Create { directory, kind } => Request::Create {
+                path: directory.join(value),
+                kind: *kind,
+            },
+            Kind::Rename(path) => Request::Rename {
+                from: path.clone(),
+                to: path.with_file_name(value),
+            },
+            Kind::Copy(path) => Request::Copy {
+                from: path.clone(),
+                to: PathBuf::from(value).join(path.file_name()?),
+            },
+            Kind::Move(path) => Request::Move {
+                from: path.clone(),
+                to: PathBuf::from(value).join(path.file_name()?),
+            },
+        };
+
+        Some(request)
+    }
+
+    fn label(&self) -> String {
+        match &self.kind {
+            Kind::Create {
+                kind: EntryKind::File,
+                ..
+            } => i18n::tr("New file name").into(),
+            Kind::Create {
+                kind: EntryKind::Directory,
+                ..
+            } => i18n::tr("New folder name").into(),
+            Kind::Rename(path) => i18n::tr_with("Rename {} to", &[&format!("{:?}", path)]),
+            Kind::Copy(path) => i18n::tr_with("Copy {} into", &[&format!("{:?}", path)]),
+            Kind::Move(path) => i18n::tr_with("Move {} into", &[&format!("{:?}", path)]),
+        }
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let label = Text::new(state.label());
+
+    let input = TextInput::new(&mut state.input, "", &state.value, Message::ValueChanged)
+        .padding(5)
+        .on_submit(Message::Confirm);
+
+    let confirm = Button::new(&mut state.confirm_button, Text::new(i18n::tr("OK")))
+        .on_press(Message::Confirm);
+    let cancel = Button::new(&mut state.cancel_button, Text::new(i18n::tr("Cancel")))
+        .on_press(Message::Cancel);
+
+    Column::new()
+        .spacing(5)
+        .push(label)
+        .push(input)
+        .push(Row::new().spacing(10).push(confirm).push(cancel))
+        .into()
+}
@@ -0,0 +1,339 @@
+use iced::button::{self, Button};
+use iced::{Column, Command, Container, Element, Length, Row, Text};
+
+use crate::exif::{self, Exif};
+use crate::file_ops;
+use crate::file_type;
+use crate::font;
+use crate::format;
+use crate::i18n;
+use crate::tasks::{self, Tasks};
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Loaded(Result<Properties, String>),
+    SizeComputed(Option<u64>),
+    ExifRead(Option<Exif>),
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub struct Properties {
+    path: PathBuf,
+    kind: &'static str,
+    size: u64,
+    created: Option<SystemTime>,
+    modified: Option<SystemTime>,
+    accessed: Option<SystemTime>,
+    permissions: String,
+    owner: Option<String>,
+    symlink_target: Option<PathBuf>,
+    mime_type: &'static str,
+    is_dir: bool,
+}
+
+pub struct State {
+    path: PathBuf,
+    properties: Option<Result<Properties, String>>,
+    recursive_size: Option<Option<u64>>,
+    exif: Option<Exif>,
+    tasks: Tasks,
+    size_task: Option<tasks::Handle>,
+    close_button: button::State,
+}
+
+impl State {
+    pub fn new(path: PathBuf, tasks: &Tasks) -> (Self, Command<Message>) {
+        let mut commands = vec![Command::perform(load(path.clone()), Message::Loaded)];
+
+        if exif::is_photo(&path) {
+            commands.push(Command::perform(
+                exif::read(path.clone()),
+                Message::ExifRead,
+            ));
+        }
+
+        (
+            Self {
+                path,
+                properties: None,
+                recursive_size: None,
+                exif: None,
+                tasks: tasks.clone(),
+                size_task: None,
+                close_button: button::State::new(),
+            },
+            Command::batch(commands),
+        )
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Loaded(result) => {
+                let is_dir = matches!(&result, Ok(properties) if properties.is_dir);
+
+                self.properties = Some(result);
+
+                if is_dir {
+                    let task = self
+                        .tasks
+                        .start(format!("Calculating size of {:?}", self.path));
+
+                    self.size_task = Some(task.clone());
+
+                    return Command::perform(
+                        directory_size(self.path.clone(), task),
+                        Message::SizeComputed,
+                    );
+                }
+            }
+            Message::SizeComputed(size) => {
+                self.recursive_size = Some(size);
+                self.size_task = None;
+            }
+            Message::ExifRead(exif) => {
+                self.exif = exif;
+            }
+            Message::Close => {}
+        }
+
+        Command::none()
+    }
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        if let Some(task) = self.size_task.take() {
+            task.cancel();
+        }
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let mut content = Column::new()
+        .spacing(10)
+        .max_width(700)
+        .push(Text::new(i18n::tr("Properties")).size(30));
+
+    match &state.properties {
+        None => {
+            content = content.push(Text::new(i18n::tr_with(
+                "Loading {}...",
+                &[&format!("{:?}", state.path)],
+            )));
+        }
+        Some(Err(error)) => {
+            content = content.push(Text::new(error));
+        }
+        Some(Ok(properties)) => {
+            let size = if properties.is_dir {
+                match state.recursive_size {
+                    Some(Some(size)) => format!("{} ({} bytes)", format::size(size), size),
+                    Some(None) => i18n::tr("Cancelled").into(),
+                    None => i18n::tr("Calculating...").into(),
+                }
+            } else {
+                format!(
+                    "{} ({} bytes)",
+                    format::size(properties.size),
+                    properties.size
+                )
+            };
+
+            let timestamp = |time: Option<SystemTime>| {
+                time.map(|time| format!("{} UTC", format::timestamp(time, ' ')))
+                    .unwrap_or_else(|| "Unavailable".into())
+            };
+
+            let mut rows = vec![
+                ("Path", properties.path.to_string_lossy().to_string()),
+                ("Type", properties.kind.to_string()),
+                ("Size", size),
+                ("Created", timestamp(properties.created)),
+                ("Modified", timestamp(properties.modified)),
+                ("Accessed", timestamp(properties.accessed)),
+                ("Permissions", properties.permissions.clone()),
+            ];
+
+            if let Some(owner) = &properties.owner {
+                rows.push(("Owner", owner.clone()));
+            }
+
+            if let Some(target) = &properties.symlink_target {
+                rows.push(("Link target", target.to_string_lossy().to_string()));
+            }
+
+            rows.push(("MIME type", properties.mime_type.to_string()));
+
+            let details = table(Column::new().spacing(10), rows);
+
+            content = match &state.exif {
+                Some(exif) => content.push(Row::new().spacing(40).push(details).push(photo(exif))),
+                None => content.push(details),
+            };
+        }
+    }
+
+    let close =
+        Button::new(&mut state.close_button, Text::new(i18n::tr("Close"))).on_press(Message::Close);
+
+    Container::new(content.push(close))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(40)
+        .center_x()
+        .into()
+}
+
+fn photo(exif: &Exif) -> Column<'static, Message> {
+    let mut rows = vec![];
+
+    if let Some((width, height)) = exif.dimensions {
+        rows.push(("Dimensions", format!("{} × {}", width, height)));
+    }
+
+    if let Some(camera) = &exif.camera {
+        rows.push(("Camera", camera.clone()));
+    }
+
+    if let Some(taken) = &exif.taken {
+        rows.push(("Date taken", taken.clone()));
+    }
+
+    if let Some((latitude, longitude)) = exif.location {
+        rows.push(("Location", format!("{:.5}, {:.5}", latitude, longitude)));
+    }
+
+    let header = Column::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("Photo")).size(20));
+
+    table(header, rows)
+}
+
+fn table(column: Column<'static, Message>, rows: Vec<(&str, String)>) -> Column<'static, Message> {
+    rows.into_iter().fold(column, |column, (label, value)| {
+        column.push(
+            Row::new()
+                .spacing(10)
+                .push(Text::new(label).width(Length::Units(120)))
+                .push(Text::new(value).font(font::MONOSPACE)),
+        )
+    })
+}
+
+async fn load(path: PathBuf) -> Result<Properties, String> {
+    let metadata = fs::symlink_metadata(&path).map_err(|error| error.to_string())?;
+    let file_type = metadata.file_type();
+
+    let symlink_target = if file_type.is_symlink() {
+        fs::read_link(&path).ok()
+    } else {
+        None
+    };
+
+    let is_dir = path.is_dir();
+
+    let kind = if file_type.is_symlink() {
+        if is_dir {
+            "Symbolic link to directory"
+        } else {
+            "Symbolic link"
+        }
+    } else if is_dir {
+        "Directory"
+    } else if file_type.is_file() {
+        "File"
+    } else {
+        "Special file"
+    };
+
+    let mime_type = if is_dir {
+        "inode/directory"
+    } else {
+        file_type::mime_type(&path)
+    };
+
+    Ok(Properties {
+        kind,
+        size: metadata.len(),
+        created: metadata.created().ok(),
+        modified: metadata.modified().ok(),
+        accessed: metadata.accessed().ok(),
+        permissions: permissions(&metadata),
+        owner: owner(&metadata),
+        symlink_target,
+        mime_type,
+        is_dir,
+        path,
+    })
+}
+
+async fn directory_size(path: PathBuf, task: tasks::Handle) -> Option<u64> {
+    file_ops::disk_usage(&path, || task.is_cancelled())
+}
+
+#[cfg(unix)]
+fn permissions(metadata: &fs::Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode();
+
+    let symbolic: String = (0..9)
+        .map(|bit| {
+            let enabled = mode & (0o400 >> bit) != 0;
+
+            match (enabled, bit % 3) {
+                (false, _) => '-',
+                (true, 0) => 'r',
+                (true, 1) => 'w',
+                (true, _) => 'x',
+            }
+        })
+        .collect();
+
+    format!("{} ({:o})", symbolic, mode & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn permissions(metadata: &fs::Metadata) -> String {
+    if metadata.permissions().readonly() {
+        "Read only".into()
+    } else {
+        "Read and write".into()
+    }
+}
+
+#[cfg(unix)]
+fn owner(metadata: &fs::Metadata) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let user =
+        lookup_name("/etc/passwd", metadata.uid()).unwrap_or_else(|| metadata.uid().to_string());
+    let group =
+        lookup_name("/etc/group", metadata.gid()).unwrap_or_else(|| metadata.gid().to_string());
+
+    Some(format!("{}:{}", user, group))
+}
+
+#[cfg(not(unix))]
+fn owner(_metadata: &fs::Metadata) -> Option<String> {
+    None
+}
+
+#[cfg(unix)]
+fn lookup_name(database: &str, id: u32) -> Option<String> {
+    let contents = fs::read_to_string(database).ok()?;
+
+    contents.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let id_field = fields.nth(1)?;
+
+        (id_field.parse() == Ok(id)).then(|| name.to_string())
+    })
+}
@@ -0,0 +1,254 @@
+use crate::export;
+
+use std::iter::Peekable;
+use std::path::PathBuf;
+use std::str::Chars;
+use std::sync::mpsc;
+
+#[derive(Debug, Clone)]
+pub enum Request {
+    Navigate(PathBuf),
+    Open(PathBuf),
+    Selection,
+}
+
+#[derive(Debug, Clone)]
+pub enum Response {
+    Done,
+    Selection(Option<PathBuf>),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Incoming {
+    pub request: Request,
+    reply: mpsc::Sender<String>,
+}
+
+impl Incoming {
+    pub fn new(request: Request, reply: mpsc::Sender<String>) -> Self {
+        Self { request, reply }
+    }
+
+    pub fn respond(self, response: Response) {
+        let _ = self.reply.send(encode(&response));
+    }
+}
+
+pub fn parse(line: &str) -> Result<Request, String> {
+    let line = line.trim();
+
+    if !line.starts_with('{') {
+        return Ok(Request::Open(PathBuf::from(line)));
+    }
+
+    let fields = parse_object(line)?;
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let path = || {
+        let path = PathBuf::from(field("path").ok_or_else(|| "Missing \"path\"".to_string())?);
+
+        if path.is_absolute() {
+            Ok(path)
+        } else {
+            Err(format!("{:?} is not an absolute path", path))
+        }
+    };
+
+    match field("command") {
+        Some("navigate") => path().map(Request::Navigate),
+        Some("open") => path().map(Request::Open),
+        Some("selection") => Ok(Request::Selection),
+        Some(command) => Err(format!("Unknown command {:?}", command)),
+        None => Err("Missing \"command\"".to_string()),
+    }
+}
+
+pub fn encode(response: &Response) -> String {
+    match response {
+        Response::Done => "{\"ok\":true}".to_string(),
+        Response::Selection(selection) => format!(
+            "{{\"ok\":true,\"selection\":{}}}",
+            selection
+                .as_ref()
+                .map_or("null".to_string(), |path| export::json_string(
+                    &path.to_string_lossy()
+                ))
+        ),
+        Response::Failed(error) => {
+            format!("{{\"ok\":false,\"error\":{}}}", export::json_string(error))
+        }
+    }
+}
+
+fn parse_object(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut characters = text.chars().peekable();
+    let mut fields = vec![];
+
+    expect(&mut characters, '{')?;
+
+    if skip_whitespace(&mut characters) == Some('}') {
+        characters.next();
+    } else {
+        loop {
+            skip_whitespace(&mut characters);
+            let key = parse_string(&mut characters)?;
+
+            skip_whitespace(&mut characters);
+            expect(&mut characters, ':')?;
+
+            skip_whitespace(&mut characters);
+            let value = parse_string(&mut characters)?;
+
+            fields.push((key, value));
+
+            skip_whitespace(&mut characters);
+
+            match characters.next() {
+                Some(',') => continue,
+                Some('}') => break,
+                _ => return Err("Expected , or }".to_string()),
+            }
+        }
+    }
+
+    match skip_whitespace(&mut characters) {
+        None => Ok(fields),
+        Some(_) => Err("Unexpected text after the request".to_string()),
+    }
+}
+
+fn skip_whitespace(characters: &mut Peekable<Chars<'_>>) -> Option<char> {
+    while characters
+        .next_if(|character| character.is_whitespace())
+        .is_some()
+    {}
+
+    characters.peek().copied()
+}
+
+fn expect(characters: &mut Peekable<Chars<'_>>, expected: char) -> Result<(), String> {
+    match characters.next() {
+        Some(character) if character == expected => Ok(()),
+        _ => Err(format!("Expected {}", expected)),
+    }
+}
+
+fn parse_string(characters: &mut Peekable<Chars<'_>>) -> Result<String, String> {
+    if characters.next() != Some('"') {
+        return Err("Only string values are supported".to_string());
+    }
+
+    let mut string = String::new();
+
+    loop {
+        match characters.next() {
+            Some('"') => return Ok(string),
+            Some('\\') => match characters.next() {
+                Some('"') => string.push('"'),
+                Some('\\') => string.push('\\'),
+                Some('/') => string.push('/'),
+                Some('b') => string.push('\u{8}'),
+                Some('f') => string.push('\u{c}'),
+                Some('n') => string.push('\n'),
+                Some('r') => string.push('\r'),
+                Some('t') => string.push('\t'),
+                Some('u') => {
+                    let high = parse_hex(characters)?;
+
+                    let code = if (0xd800..0xdc00).contains(&high) {
+                        expect(characters, '\\')?;
+                        expect(characters, 'u')?;
+
+                        let low = parse_hex(characters)?;
+
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return Err("Invalid \\u escape".to_string());
+                        }
+
+                        0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                    } else if (0xdc00..0xe000).contains(&high) {
+                        return Err("Invalid \\u escape".to_string());
+                    } else {
+                        high
+                    };
+
+                    string.push(
+                        char::from_u32(code).ok_or_else(|| "Invalid \\u escape".to_string())?,
+                    );
+                }
+                _ => return Err("Invalid escape".to_string()),
+            },
+            Some(character) => string.push(character),
+            None => return Err("Unterminated string".to_string()),
+        }
+    }
+}
+
+fn parse_hex(characters: &mut Peekable<Chars<'_>>) -> Result<u32, String> {
+    (0..4).try_fold(0, |code, _| {
+        characters
+            .next()
+            .and_then(|character| character.to_digit(16))
+            .map(|digit| code * 16 + digit)
+            .ok_or_else(|| "Invalid \\u escape".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(line: &str) -> Result<PathBuf, String> {
+        match parse(line)? {
+            Request::Navigate(path) | Request::Open(path) => Ok(path),
+            Request::Selection => Err("selection".to_string()),
+        }
+    }
+
+    #[test]
+    fn parses_escapes_and_surrogate_pairs() {
+        assert_eq!(
+            path(r#"{"command": "open", "path": "/a\"b\\c\/d\te\u00e9\ud83d\ude00"}"#),
+            Ok(PathBuf::from("/a\"b\\c/d\te\u{e9}\u{1f600}"))
+        );
+        assert!(matches!(
+            parse(r#"{"command":"selection"}"#),
+            Ok(Request::Selection)
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_surrogates() {
+        let invalid = Err("Invalid \\u escape".to_string());
+
+        assert_eq!(
+            path(r#"{"command":"open","path":"/\ud83d\u0041"}"#),
+            invalid
+        );
+        assert_eq!(path(r#"{"command":"open","path":"/\ude00"}"#), invalid);
+        assert_eq!(
+            path(r#"{"command":"open","path":"/\ud83dx"}"#),
+            Err("Expected \\".to_string())
+        );
+        assert_eq!(path(r#"{"command":"open","path":"/\u12"}"#), invalid);
+    }
+
+    #[test]
+    fn rejects_trailing_garbage_and_relative_paths() {
+        assert_eq!(
+            path(r#"{"command":"open","path":"/a"} extra"#),
+            Err("Unexpected text after the request".to_string())
+        );
+        assert_eq!(
+            path(r#"{"command":"navigate","path":"relative/a"}"#),
+            Err("\"relative/a\" is not an absolute path".to_string())
+        );
+        assert_eq!(path("/plain/line"), Ok(PathBuf::from("/plain/line")));
+    }
+}
@@ -0,0 +1,268 @@
+use iced::button::{self, Button};
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{BoxStream, StreamExt};
+use iced::scrollable::{self, Scrollable};
+use iced::{Column, Container, Element, Length, Row, Space, Subscription, Text};
+use iced_native::subscription::Recipe;
+
+use crate::font;
+use crate::i18n;
+use crate::style;
+use crate::tasks::{self, Tasks};
+
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+const MAX_LINES: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Output(Stream, String),
+    Exited(Result<String, String>),
+    Stop,
+    Close,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+pub struct State {
+    id: tasks::Id,
+    task: Option<tasks::Handle>,
+    label: String,
+    program: PathBuf,
+    args: Vec<String>,
+    directory: PathBuf,
+    output: Vec<(Stream, String)>,
+    exit: Option<Result<String, String>>,
+    stop_button: button::State,
+    close_button: button::State,
+    scrollable: scrollable::State,
+}
+
+impl State {
+    pub fn new(program: PathBuf, directory: PathBuf, tasks: &Tasks) -> Self {
+        let label = format!("{:?}", program);
+
+        Self::command(label, program, vec![], directory, tasks)
+    }
+
+    pub fn command(
+        label: String,
+        program: PathBuf,
+        args: Vec<String>,
+        directory: PathBuf,
+        tasks: &Tasks,
+    ) -> Self {
+        let task = tasks.start(format!("Running {}", label));
+
+        Self {
+            id: task.id(),
+            task: Some(task),
+            label,
+            program,
+            args,
+            directory,
+            output: vec![],
+            exit: None,
+            stop_button: button::State::new(),
+            close_button: button::State::new(),
+            scrollable: scrollable::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Output(stream, line) => {
+                if self.output.len() >= MAX_LINES {
+                    self.output.remove(0);
+                }
+
+                self.output.push((stream, line));
+            }
+            Message::Exited(result) => {
+                self.exit = Some(result);
+                self.task = None;
+            }
+            Message::Stop => {
+                if let Some(task) = &self.task {
+                    task.cancel();
+                }
+            }
+            Message::Close => {}
+        }
+    }
+
+    pub fn id(&self) -> tasks::Id {
+        self.id
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let task = match &self.task {
+            Some(task) => task.clone(),
+            None => return Subscription::none(),
+        };
+
+        Subscription::from_recipe(Run {
+            task,
+            program: self.program.clone(),
+            args: self.args.clone(),
+            directory: self.directory.clone(),
+        })
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let status = match &state.exit {
+        None => i18n::tr("Running").to_string(),
+        Some(Ok(status)) => status.clone(),
+        Some(Err(error)) => error.clone(),
+    };
+
+    let mut stop =
+        Button::new(&mut state.stop_button, Text::new(i18n::tr("Stop")).size(14)).padding(3);
+
+    if state.task.as_ref().is_some_and(|task| !task.is_cancelled()) {
+        stop = stop.on_press(Message::Stop);
+    }
+
+    let close = Button::new(
+        &mut state.close_button,
+        Text::new(i18n::tr("Close")).size(14),
+    )
+    .padding(3)
+    .on_press(Message::Close);
+
+    let header = Row::new()
+        .spacing(10)
+        .push(Text::new(format!("{}: {}", state.label, status)).size(16))
+        .push(Space::with_width(Length::Fill))
+        .push(stop)
+        .push(close);
+
+    let output = state.output.iter().fold(
+        Scrollable::new(&mut state.scrollable).width(Length::Fill),
+        |scrollable, (stream, line)| {
+            let mut text = Text::new(line.as_str()).size(14).font(font::MONOSPACE);
+
+            if *stream == Stream::Stderr {
+                text = text.color(style::ERROR);
+            }
+
+            scrollable.push(text)
+        },
+    );
+
+    Container::new(Column::new().spacing(5).push(header).push(output))
+        .width(Length::Fill)
+        .height(Length::Units(200))
+        .into()
+}
+
+struct Run {
+    task: tasks::Handle,
+    program: PathBuf,
+    args: Vec<String>,
+    directory: PathBuf,
+}
+
+impl<H, I> Recipe<H, I> for Run
+where
+    H: std::hash::Hasher,
+{
+    type Output = Message;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.task.id().hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Message> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        thread::spawn(move || {
+            let result = run(&self, &sender);
+
+            let _ = sender.unbounded_send(Message::Exited(result));
+        });
+
+        receiver.boxed()
+    }
+}
+
+fn run(run: &Run, sender: &mpsc::UnboundedSender<Message>) -> Result<String, String> {
+    let mut child = Command::new(&run.program)
+        .args(&run.args)
+        .current_dir(&run.directory)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("Cannot run {:?}: {}", run.program, error))?;
+
+    let readers = vec![
+        child
+            .stdout
+            .take()
+            .map(|stdout| forward(stdout, Stream::Stdout, sender.clone())),
+        child
+            .stderr
+            .take()
+            .map(|stderr| forward(stderr, Stream::Stderr, sender.clone())),
+    ];
+
+    let status = loop {
+        if run.task.is_cancelled() || sender.is_closed() {
+            let _ = child.kill();
+        }
+
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => thread::sleep(Duration::from_millis(100)),
+            Err(error) => return Err(error.to_string()),
+        }
+    };
+
+    for reader in readers.into_iter().flatten() {
+        let _ = reader.join();
+    }
+
+    match status.code() {
+        Some(code) => Ok(format!("Exited with status {}", code)),
+        None if run.task.is_cancelled() => Ok("Stopped".into()),
+        None => Ok("Terminated by a signal".into()),
+    }
+}
+
+fn forward(
+    reader: impl Read + Send + 'static,
+    stream: Stream,
+    sender: mpsc::UnboundedSender<Message>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+
+            if sender
+                .unbounded_send(Message::Output(stream, line))
+                .is_err()
+            {
+                break;
+            }
+        }
+    })
+}
@@ -0,0 +1,179 @@
+use iced::button::{self, Button};
+use iced::{Align, Column, Command, Element, Row, Text};
+
+use crate::i18n;
+use crate::search::Saved;
+use crate::storage;
+use crate::style;
+
+use std::path::PathBuf;
+
+const SAVED_SEARCHES: &str = "saved_searches";
+const SEPARATOR: char = '\t';
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Run(usize),
+    Remove(usize),
+    Saved(Result<(), String>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Run(Saved),
+    Failed(String),
+}
+
+struct Entry {
+    search: Saved,
+    run_button: button::State,
+    remove_button: button::State,
+}
+
+pub struct State {
+    entries: Vec<Entry>,
+}
+
+impl State {
+    pub fn load() -> Self {
+        let entries = storage::load(SAVED_SEARCHES)
+            .iter()
+            .filter_map(|line| decode(line))
+            .map(Entry::new)
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn add(&mut self, search: Saved) -> Command<Message> {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.search.name == search.name)
+        {
+            Some(entry) => entry.search = search,
+            None => self.entries.push(Entry::new(search)),
+        }
+
+        self.save()
+    }
+
+    pub fn update(&mut self, message: Message) -> (Command<Message>, Option<Event>) {
+        match message {
+            Message::Run(index) => {
+                if let Some(entry) = self.entries.get(index) {
+                    return (Command::none(), Some(Event::Run(entry.search.clone())));
+                }
+            }
+            Message::Remove(index) => {
+                if index < self.entries.len() {
+                    self.entries.remove(index);
+
+                    return (self.save(), None);
+                }
+            }
+            Message::Saved(Ok(())) => {}
+            Message::Saved(Err(error)) => return (Command::none(), Some(Event::Failed(error))),
+        }
+
+        (Command::none(), None)
+    }
+
+    fn save(&self) -> Command<Message> {
+        let lines = self
+            .entries
+            .iter()
+            .map(|entry| encode(&entry.search))
+            .collect();
+
+        Command::perform(storage::save(SAVED_SEARCHES, lines), Message::Saved)
+    }
+}
+
+impl Entry {
+    fn new(search: Saved) -> Self {
+        Self {
+            search,
+            run_button: button::State::new(),
+            remove_button: button::State::new(),
+        }
+    }
+}
+
+fn encode(search: &Saved) -> String {
+    let mut flags = String::new();
+
+    if search.case_sensitive {
+        flags.push('c');
+    }
+
+    if search.regex {
+        flags.push('r');
+    }
+
+    [
+        search.name.as_str(),
+        &search.root.to_string_lossy(),
+        &flags,
+        search.query.as_str(),
+    ]
+    .join(&SEPARATOR.to_string())
+}
+
+fn decode(line: &str) -> Option<Saved> {
+    let mut fields = line.splitn(4, SEPARATOR);
+
+    let name = fields.next()?.to_string();
+    let root = PathBuf::from(fields.next()?);
+    let flags = fields.next()?;
+    let query = fields.next()?.to_string();
+
+    Some(Saved {
+        name,
+        root,
+        query,
+        case_sensitive: flags.contains('c'),
+        regex: flags.contains('r'),
+    })
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let header = Text::new(i18n::tr("Searches"))
+        .size(14)
+        .color(style::GUTTER);
+
+    state
+        .entries
+        .iter_mut()
+        .enumerate()
+        .fold(
+            Column::new().spacing(2).push(header),
+            |column, (index, entry)| {
+                let run = Button::new(&mut entry.run_button, Text::new(&entry.search.name))
+                    .on_press(Message::Run(index));
+
+                let remove = Button::new(&mut entry.remove_button, Text::new("×").size(14))
+                    .padding(3)
+                    .style(style::GroupHeader)
+                    .on_press(Message::Remove(index));
+
+                let root = Text::new(entry.search.root.to_string_lossy())
+                    .size(12)
+                    .color(style::GUTTER);
+
+                column.push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Align::Center)
+                        .push(style::labelled(run, &entry.search.query))
+                        .push(root)
+                        .push(style::labelled(remove, i18n::tr("Remove"))),
+                )
+            },
+        )
+        .into()
+}
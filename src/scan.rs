@@ -0,0 +1,196 @@
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{BoxStream, StreamExt};
+use iced::Subscription;
+use iced_native::subscription::Recipe;
+use rayon::prelude::*;
+
+use crate::checksum;
+use crate::file_ops;
+use crate::tasks;
+
+use std::fs;
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const METADATA_BATCH: usize = 256;
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Size(PathBuf, u64),
+    Metadata(Vec<(PathBuf, fs::Metadata)>),
+    Checksum(PathBuf, checksum::Verdict),
+    Finished(tasks::Id),
+}
+
+pub fn sizes(task: tasks::Handle, paths: Vec<PathBuf>) -> Subscription<Event> {
+    Subscription::from_recipe(Sizes { task, paths })
+}
+
+struct Sizes {
+    task: tasks::Handle,
+    paths: Vec<PathBuf>,
+}
+
+impl<H, I> Recipe<H, I> for Sizes
+where
+    H: std::hash::Hasher,
+{
+    type Output = Event;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.task.id().hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Event> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        rayon::spawn(move || {
+            let Sizes { task, paths } = *self;
+
+            let total = paths.len() as u64;
+            let done = AtomicU64::new(0);
+
+            paths
+                .into_par_iter()
+                .for_each_with(sender.clone(), |sender, path| {
+                    if task.is_cancelled() || sender.is_closed() {
+                        return;
+                    }
+
+                    if let Some(size) = file_ops::disk_usage(&path, || task.is_cancelled()) {
+                        let _ = sender.unbounded_send(Event::Size(path, size));
+                    }
+
+                    let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+
+                    task.set_progress(done, total, format!("{} of {} entries", done, total));
+                });
+
+            let _ = sender.unbounded_send(Event::Finished(task.id()));
+        });
+
+        receiver.boxed()
+    }
+}
+
+pub fn metadata(task: tasks::Handle, paths: Vec<PathBuf>) -> Subscription<Event> {
+    Subscription::from_recipe(Metadata { task, paths })
+}
+
+struct Metadata {
+    task: tasks::Handle,
+    paths: Vec<PathBuf>,
+}
+
+impl<H, I> Recipe<H, I> for Metadata
+where
+    H: std::hash::Hasher,
+{
+    type Output = Event;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.task.id().hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Event> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        rayon::spawn(move || {
+            let Metadata { task, paths } = *self;
+
+            let total = paths.len() as u64;
+            let done = AtomicU64::new(0);
+
+            paths
+                .par_chunks(METADATA_BATCH)
+                .for_each_with(sender.clone(), |sender, chunk| {
+                    if task.is_cancelled() || sender.is_closed() {
+                        return;
+                    }
+
+                    let batch = chunk
+                        .iter()
+                        .filter_map(|path| Some((path.clone(), fs::metadata(path).ok()?)))
+                        .collect();
+
+                    let _ = sender.unbounded_send(Event::Metadata(batch));
+
+                    let done =
+                        done.fetch_add(chunk.len() as u64, Ordering::Relaxed) + chunk.len() as u64;
+
+                    task.set_progress(done, total, format!("{} of {} entries", done, total));
+                });
+
+            let _ = sender.unbounded_send(Event::Finished(task.id()));
+        });
+
+        receiver.boxed()
+    }
+}
+
+pub fn checksums(task: tasks::Handle, manifests: Vec<PathBuf>) -> Subscription<Event> {
+    Subscription::from_recipe(Checksums { task, manifests })
+}
+
+struct Checksums {
+    task: tasks::Handle,
+    manifests: Vec<PathBuf>,
+}
+
+impl<H, I> Recipe<H, I> for Checksums
+where
+    H: std::hash::Hasher,
+{
+    type Output = Event;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.task.id().hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Event> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        rayon::spawn(move || {
+            let Checksums { task, manifests } = *self;
+
+            let checks: Vec<_> = manifests
+                .iter()
+                .filter_map(|manifest| {
+                    let contents = fs::read_to_string(manifest).ok()?;
+
+                    Some(checksum::parse(manifest, &contents))
+                })
+                .flatten()
+                .collect();
+
+            let total = checks.len() as u64;
+            let done = AtomicU64::new(0);
+
+            checks
+                .into_par_iter()
+                .for_each_with(sender.clone(), |sender, (path, expected)| {
+                    if task.is_cancelled() || sender.is_closed() {
+                        return;
+                    }
+
+                    if let Some(verdict) =
+                        checksum::verify(&path, &expected, || task.is_cancelled())
+                    {
+                        let _ = sender.unbounded_send(Event::Checksum(path, verdict));
+                    }
+
+                    let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+
+                    task.set_progress(done, total, format!("{} of {} files", done, total));
+                });
+
+            let _ = sender.unbounded_send(Event::Finished(task.id()));
+        });
+
+        receiver.boxed()
+    }
+}
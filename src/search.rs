@@ -0,0 +1,969 @@
+use iced::button::{self, Button};
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{BoxStream, StreamExt};
+use iced::pick_list::{self, PickList};
+use iced::scrollable::{self, Scrollable};
+use iced::text_input::{self, TextInput};
+use iced::{
+    Align, Checkbox, Color, Column, Command, Container, Element, Length, Row, Space, Subscription,
+    Text,
+};
+use iced_native::subscription::Recipe;
+use rayon::prelude::*;
+use walkdir::WalkDir;
+
+use crate::file_ops::Request;
+use crate::font;
+use crate::history::History;
+use crate::i18n;
+use crate::index::Index;
+use crate::pattern::{Found, Pattern};
+use crate::storage;
+use crate::style;
+use crate::tasks::{self, Tasks};
+
+use std::fmt;
+use std::fs;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const MAX_RESULTS: usize = 1000;
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+const SNIFF_LENGTH: usize = 8192;
+const MAX_PREVIEW_LENGTH: usize = 200;
+const MAX_SKIPPED_SHOWN: usize = 3;
+const FIND_HISTORY: &str = "find_history";
+const SEARCH_HISTORY: &str = "search_history";
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    QueryChanged(String),
+    ScopeSelected(Scope),
+    CaseSensitiveToggled(bool),
+    RegexToggled(bool),
+    Submit,
+    RecallOlder,
+    RecallNewer,
+    Recall(Past),
+    HistorySaved(Result<(), String>),
+    NameChanged(String),
+    Save,
+    Found(Vec<Match>),
+    Skipped(String),
+    Finished,
+    Open(usize),
+    ReplacementChanged(String),
+    PreviewReplace(Option<usize>),
+    ReplacePreviewed(Result<Replacement, String>),
+    ApplyReplace,
+    CancelReplace,
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Open(PathBuf, usize),
+    Replace(Request),
+    Save(Saved),
+    Failed(String),
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    CurrentFile,
+    Directory,
+}
+
+impl Scope {
+    const ALL: [Scope; 2] = [Scope::CurrentFile, Scope::Directory];
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Scope::CurrentFile => "Current file",
+            Scope::Directory => "Directory and below",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Saved {
+    pub name: String,
+    pub root: PathBuf,
+    pub query: String,
+    pub case_sensitive: bool,
+    pub regex: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Past {
+    Placeholder,
+    Query(String),
+}
+
+impl fmt::Display for Past {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Past::Placeholder => f.write_str(i18n::tr("History")),
+            Past::Query(query) => f.write_str(query),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub path: PathBuf,
+    pub line: usize,
+    pub text: String,
+    pub found: Found,
+}
+
+#[derive(Debug, Clone)]
+pub struct Replacement {
+    path: PathBuf,
+    before: String,
+    after: String,
+    count: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Query {
+    pattern: Pattern,
+    root: PathBuf,
+    files: Option<Vec<PathBuf>>,
+}
+
+pub struct State {
+    query: String,
+    scope: Scope,
+    case_sensitive: bool,
+    regex: bool,
+    error: Option<String>,
+    directory: PathBuf,
+    file: Option<PathBuf>,
+    tasks: Tasks,
+    index: Index,
+    running: Option<(tasks::Handle, Query)>,
+    results: Vec<Match>,
+    skipped: Vec<String>,
+    searched: Option<PathBuf>,
+    replacement: String,
+    preview: Option<Result<Replacement, String>>,
+    find_history: History,
+    search_history: History,
+    name: String,
+    input: text_input::State,
+    name_input: text_input::State,
+    save_button: button::State,
+    replace_input: text_input::State,
+    scope_list: pick_list::State<Scope>,
+    history_list: pick_list::State<Past>,
+    submit_button: button::State,
+    close_button: button::State,
+    replace_first_button: button::State,
+    replace_all_button: button::State,
+    apply_button: button::State,
+    cancel_button: button::State,
+    result_buttons: Vec<button::State>,
+    scrollable: scrollable::State,
+}
+
+impl State {
+    pub fn new(
+        scope: Scope,
+        directory: PathBuf,
+        file: Option<PathBuf>,
+        tasks: &Tasks,
+        index: Index,
+    ) -> Self {
+        Self {
+            query: String::new(),
+            scope,
+            case_sensitive: false,
+            regex: false,
+            error: None,
+            directory,
+            file,
+            tasks: tasks.clone(),
+            index,
+            running: None,
+            results: vec![],
+            skipped: vec![],
+            searched: None,
+            replacement: String::new(),
+            preview: None,
+            find_history: History::new(storage::load(FIND_HISTORY)),
+            search_history: History::new(storage::load(SEARCH_HISTORY)),
+            name: String::new(),
+            input: text_input::State::focused(),
+            name_input: text_input::State::new(),
+            save_button: button::State::new(),
+            replace_input: text_input::State::new(),
+            scope_list: pick_list::State::default(),
+            history_list: pick_list::State::default(),
+            submit_button: button::State::new(),
+            close_button: button::State::new(),
+            replace_first_button: button::State::new(),
+            replace_all_button: button::State::new(),
+            apply_button: button::State::new(),
+            cancel_button: button::State::new(),
+            result_buttons: vec![],
+            scrollable: scrollable::State::new(),
+        }
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.input.is_focused() || self.replace_input.is_focused()
+    }
+
+    pub fn focus(&mut self, scope: Scope, directory: PathBuf, file: Option<PathBuf>) {
+        self.scope = scope;
+        self.directory = directory;
+        self.file = file;
+        self.input.focus();
+        self.input.move_cursor_to_end();
+    }
+
+    pub fn update(&mut self, message: Message) -> (Command<Message>, Option<Event>) {
+        match message {
+            Message::QueryChanged(query) => {
+                self.history().reset();
+                self.query = query;
+                self.error = self.compile().err();
+                self.preview = None;
+            }
+            Message::ScopeSelected(scope) => {
+                self.history().reset();
+                self.scope = scope;
+                self.preview = None;
+            }
+            Message::CaseSensitiveToggled(case_sensitive) => {
+                self.case_sensitive = case_sensitive;
+                self.preview = None;
+            }
+            Message::RegexToggled(regex) => {
+                self.regex = regex;
+                self.error = self.compile().err();
+                self.preview = None;
+            }
+            Message::Submit => {
+                let root = match (self.scope, &self.file) {
+                    (Scope::CurrentFile, Some(file)) => file.clone(),
+                    (Scope::CurrentFile, None) => return (Command::none(), None),
+                    (Scope::Directory, _) => self.directory.clone(),
+                };
+
+                let pattern = match self.compile() {
+                    Ok(pattern) if !self.query.is_empty() => pattern,
+                    _ => return (Command::none(), None),
+                };
+
+                let task = self
+                    .tasks
+                    .start(format!("Searching {:?} for {:?}", root, self.query));
+
+                let files = Some(&root)
+                    .filter(|root| self.scope == Scope::Directory && self.index.covers(root))
+                    .map(|root| self.index.files_under(root));
+
+                self.results.clear();
+                self.skipped.clear();
+                self.searched = Some(root.clone());
+                self.running = Some((
+                    task,
+                    Query {
+                        pattern,
+                        root,
+                        files,
+                    },
+                ));
+
+                let name = match self.scope {
+                    Scope::CurrentFile => FIND_HISTORY,
+                    Scope::Directory => SEARCH_HISTORY,
+                };
+                let query = self.query.clone();
+
+                if let Some(lines) = self.history().record(&query) {
+                    return (
+                        Command::perform(storage::save(name, lines), Message::HistorySaved),
+                        None,
+                    );
+                }
+            }
+            Message::RecallOlder => {
+                if let Some(query) = self.history().older().map(String::from) {
+                    self.recall(query);
+                }
+            }
+            Message::RecallNewer => {
+                if let Some(query) = self.history().newer().map(String::from) {
+                    self.recall(query);
+                }
+            }
+            Message::Recall(Past::Query(query)) => {
+                self.history().reset();
+                self.recall(query);
+                self.input.focus();
+            }
+            Message::Recall(Past::Placeholder) => {}
+            Message::HistorySaved(Ok(())) => {}
+            Message::NameChanged(name) => {
+                self.name = name;
+            }
+            Message::Save => {
+                if self.query.is_empty() || self.error.is_some() {
+                    return (Command::none(), None);
+                }
+
+                let name = match self.name.trim() {
+                    "" => self.query.clone(),
+                    name => name.to_string(),
+                };
+
+                let saved = Saved {
+                    name,
+                    root: self.directory.clone(),
+                    query: self.query.clone(),
+                    case_sensitive: self.case_sensitive,
+                    regex: self.regex,
+                };
+
+                self.name.clear();
+
+                return (Command::none(), Some(Event::Save(saved)));
+            }
+            Message::HistorySaved(Err(error)) => {
+                return (Command::none(), Some(Event::Failed(error)));
+            }
+            Message::Found(matches) => {
+                let remaining = MAX_RESULTS.saturating_sub(self.results.len());
+
+                self.results.extend(matches.into_iter().take(remaining));
+            }
+            Message::Skipped(note) => {
+                self.skipped.push(note);
+            }
+            Message::Finished => {
+                self.running = None;
+            }
+            Message::Open(index) => {
+                if let Some(found) = self.results.get(index) {
+                    return (
+                        Command::none(),
+                        Some(Event::Open(found.path.clone(), found.line)),
+                    );
+                }
+            }
+            Message::ReplacementChanged(replacement) => {
+                self.replacement = replacement;
+                self.preview = None;
+            }
+            Message::PreviewReplace(limit) => {
+                let path = self
+                    .file
+                    .clone()
+                    .filter(|_| self.scope == Scope::CurrentFile);
+
+                if let (Some(path), Ok(pattern)) = (path, self.compile()) {
+                    if !self.query.is_empty() {
+                        return (
+                            Command::perform(
+                                preview(path, pattern, self.replacement.clone(), limit),
+                                Message::ReplacePreviewed,
+                            ),
+                            None,
+                        );
+                    }
+                }
+            }
+            Message::ReplacePreviewed(preview) => {
+                self.preview = Some(preview);
+            }
+            Message::ApplyReplace => {
+                if let Some(Ok(replacement)) = self.preview.take() {
+                    let request = Request::Replace {
+                        path: replacement.path,
+                        before: replacement.before,
+                        after: replacement.after,
+                        count: replacement.count,
+                    };
+
+                    self.results.clear();
+                    self.searched = None;
+
+                    return (Command::none(), Some(Event::Replace(request)));
+                }
+            }
+            Message::CancelReplace => {
+                self.preview = None;
+            }
+            Message::Close => {
+                if let Some((task, _)) = self.running.take() {
+                    task.cancel();
+                }
+
+                return (Command::none(), Some(Event::Closed));
+            }
+        }
+
+        (Command::none(), None)
+    }
+
+    pub fn run(&mut self, saved: Saved) -> (Command<Message>, Option<Event>) {
+        self.scope = Scope::Directory;
+        self.directory = saved.root;
+        self.case_sensitive = saved.case_sensitive;
+        self.regex = saved.regex;
+        self.recall(saved.query);
+
+        self.update(Message::Submit)
+    }
+
+    pub fn is_query_focused(&self) -> bool {
+        self.input.is_focused()
+    }
+
+    fn history(&mut self) -> &mut History {
+        match self.scope {
+            Scope::CurrentFile => &mut self.find_history,
+            Scope::Directory => &mut self.search_history,
+        }
+    }
+
+    fn recall(&mut self, query: String) {
+        self.query = query;
+        self.error = self.compile().err();
+        self.preview = None;
+        self.input.move_cursor_to_end();
+    }
+
+    fn compile(&self) -> Result<Pattern, String> {
+        if self.regex {
+            Pattern::new(&self.query, self.case_sensitive)
+        } else {
+            Ok(Pattern::literal(&self.query, self.case_sensitive))
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        match &self.running {
+            Some((task, query)) => Subscription::from_recipe(Search {
+                task: task.clone(),
+                query: query.clone(),
+            }),
+            None => Subscription::none(),
+        }
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let input = TextInput::new(
+        &mut state.input,
+        i18n::tr("Search"),
+        &state.query,
+        Message::QueryChanged,
+    )
+    .padding(5)
+    .size(16)
+    .on_submit(Message::Submit);
+
+    let scope = PickList::new(
+        &mut state.scope_list,
+        &Scope::ALL[..],
+        Some(state.scope),
+        Message::ScopeSelected,
+    )
+    .text_size(14);
+
+    let case_sensitive = Checkbox::new(
+        state.case_sensitive,
+        i18n::tr("Match case"),
+        Message::CaseSensitiveToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let regex = Checkbox::new(state.regex, i18n::tr("Regex"), Message::RegexToggled)
+        .size(16)
+        .text_size(14);
+
+    let mut submit = Button::new(
+        &mut state.submit_button,
+        Text::new(i18n::tr("Search")).size(14),
+    )
+    .padding(3);
+
+    if state.error.is_none() {
+        submit = submit.on_press(Message::Submit);
+    }
+
+    let close = Button::new(
+        &mut state.close_button,
+        Text::new(i18n::tr("Close")).size(14),
+    )
+    .padding(3)
+    .on_press(Message::Close);
+
+    let history = match state.scope {
+        Scope::CurrentFile => &state.find_history,
+        Scope::Directory => &state.search_history,
+    };
+
+    let past: Vec<_> = history.queries().iter().cloned().map(Past::Query).collect();
+
+    let mut controls = Row::new()
+        .spacing(10)
+        .align_items(Align::Center)
+        .push(input);
+
+    if !past.is_empty() {
+        controls = controls.push(
+            PickList::new(
+                &mut state.history_list,
+                past,
+                Some(Past::Placeholder),
+                Message::Recall,
+            )
+            .text_size(14),
+        );
+    }
+
+    let controls = controls
+        .push(scope)
+        .push(case_sensitive)
+        .push(regex)
+        .push(submit)
+        .push(close);
+
+    let summary = match (&state.running, state.results.len()) {
+        (Some(_), count) => i18n::tr_with("Searching... {} results so far", &[&count]),
+        (None, count) if count >= MAX_RESULTS => i18n::tr_with("First {} results", &[&MAX_RESULTS]),
+        (None, 1) => i18n::tr("1 result").to_string(),
+        (None, count) if state.searched.is_some() => i18n::tr_with("{} results", &[&count]),
+        (None, _) => String::new(),
+    };
+
+    let summary = match &state.error {
+        Some(error) => Text::new(error.as_str()).size(14).color(style::ERROR),
+        None => Text::new(summary).size(14),
+    };
+
+    let mut column = Column::new().spacing(5).push(controls);
+
+    if state.scope == Scope::Directory {
+        let can_save = state.error.is_none() && !state.query.is_empty();
+
+        let name_input = TextInput::new(
+            &mut state.name_input,
+            i18n::tr("Name this search"),
+            &state.name,
+            Message::NameChanged,
+        )
+        .padding(5)
+        .size(16)
+        .on_submit(Message::Save);
+
+        let mut save = Button::new(
+            &mut state.save_button,
+            Text::new(i18n::tr("Save search")).size(14),
+        )
+        .padding(3);
+
+        if can_save {
+            save = save.on_press(Message::Save);
+        }
+
+        column = column.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(name_input)
+                .push(save),
+        );
+    }
+
+    if state.scope == Scope::CurrentFile && state.file.is_some() {
+        let can_replace = state.error.is_none() && !state.query.is_empty();
+
+        let replace_input = TextInput::new(
+            &mut state.replace_input,
+            i18n::tr("Replace with"),
+            &state.replacement,
+            Message::ReplacementChanged,
+        )
+        .padding(5)
+        .size(16);
+
+        let mut replace_first = Button::new(
+            &mut state.replace_first_button,
+            Text::new(i18n::tr("Replace first")).size(14),
+        )
+        .padding(3);
+        let mut replace_all = Button::new(
+            &mut state.replace_all_button,
+            Text::new(i18n::tr("Replace all")).size(14),
+        )
+        .padding(3);
+
+        if can_replace {
+            replace_first = replace_first.on_press(Message::PreviewReplace(Some(1)));
+            replace_all = replace_all.on_press(Message::PreviewReplace(None));
+        }
+
+        column = column.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(replace_input)
+                .push(replace_first)
+                .push(replace_all),
+        );
+    }
+
+    match &state.preview {
+        Some(Ok(replacement)) if replacement.count == 0 => {
+            column = column.push(Text::new(i18n::tr("Nothing to replace")).size(14));
+        }
+        Some(Ok(replacement)) => {
+            let file = replacement
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let question = if replacement.count == 1 {
+                i18n::tr_with("Replace 1 occurrence in {}?", &[&file])
+            } else {
+                i18n::tr_with(
+                    "Replace {} occurrences in {}?",
+                    &[&replacement.count, &file],
+                )
+            };
+
+            let apply = Button::new(
+                &mut state.apply_button,
+                Text::new(i18n::tr("Apply")).size(14),
+            )
+            .padding(3)
+            .on_press(Message::ApplyReplace);
+            let cancel = Button::new(
+                &mut state.cancel_button,
+                Text::new(i18n::tr("Cancel")).size(14),
+            )
+            .padding(3)
+            .on_press(Message::CancelReplace);
+
+            column = column.push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Align::Center)
+                    .push(Text::new(question).size(14))
+                    .push(Space::with_width(Length::Units(10)))
+                    .push(apply)
+                    .push(cancel),
+            );
+        }
+        Some(Err(error)) => {
+            column = column.push(Text::new(error.as_str()).size(14).color(style::ERROR));
+        }
+        None => {}
+    }
+
+    state
+        .result_buttons
+        .resize_with(state.results.len(), button::State::new);
+
+    let root = state.searched.as_deref().unwrap_or_else(|| Path::new(""));
+
+    let results = state
+        .results
+        .iter()
+        .zip(state.result_buttons.iter_mut())
+        .enumerate()
+        .fold(
+            Scrollable::new(&mut state.scrollable).width(Length::Fill),
+            |scrollable, (index, (found, button))| {
+                let path = found.path.strip_prefix(root).unwrap_or(&found.path);
+                let path = if path.as_os_str().is_empty() {
+                    found.path.file_name().map(Path::new).unwrap_or(path)
+                } else {
+                    path
+                };
+
+                let label = highlighted(&found.text, &found.found).into_iter().fold(
+                    Row::new().push(segment(format!("{}:{}: ", path.display(), found.line))),
+                    |row, (text, color)| match color {
+                        Some(color) => row.push(segment(text).color(color)),
+                        None => row.push(segment(text)),
+                    },
+                );
+
+                scrollable.push(
+                    Button::new(button, label)
+                        .padding(2)
+                        .width(Length::Fill)
+                        .on_press(Message::Open(index)),
+                )
+            },
+        );
+
+    column = column.push(summary);
+
+    for note in state.skipped.iter().take(MAX_SKIPPED_SHOWN) {
+        column = column.push(Text::new(note.as_str()).size(14).color(style::ERROR));
+    }
+
+    if state.skipped.len() > MAX_SKIPPED_SHOWN {
+        column = column.push(
+            Text::new(i18n::tr_with(
+                "{} more files skipped",
+                &[&(state.skipped.len() - MAX_SKIPPED_SHOWN)],
+            ))
+            .size(14)
+            .color(style::ERROR),
+        );
+    }
+
+    Container::new(column.push(results))
+        .width(Length::Fill)
+        .height(Length::Units(250))
+        .into()
+}
+
+async fn preview(
+    path: PathBuf,
+    pattern: Pattern,
+    replacement: String,
+    limit: Option<usize>,
+) -> Result<Replacement, String> {
+    let before = fs::read_to_string(&path)
+        .map_err(|error| format!("Cannot replace in {:?}: {}", path, error))?;
+
+    let (after, count) = pattern
+        .replace_all(&before, &replacement, limit)
+        .map_err(|error| format!("Cannot replace in {:?}: {}", path, error))?;
+
+    Ok(Replacement {
+        path,
+        before,
+        after,
+        count,
+    })
+}
+
+fn segment(text: impl Into<String>) -> Text {
+    Text::new(text).size(14).font(font::MONOSPACE)
+}
+
+fn highlighted<'a>(text: &'a str, found: &Found) -> Vec<(&'a str, Option<Color>)> {
+    let color = |offset: usize| {
+        if found
+            .groups
+            .iter()
+            .flatten()
+            .any(|(start, end)| (*start..*end).contains(&offset))
+        {
+            Some(style::SEARCH_CAPTURE)
+        } else if (found.start..found.end).contains(&offset) {
+            Some(style::SEARCH_MATCH)
+        } else {
+            None
+        }
+    };
+
+    let mut segments = vec![];
+    let mut start = 0;
+
+    for (offset, _) in text.char_indices().skip(1) {
+        if color(offset) != color(start) {
+            segments.push((&text[start..offset], color(start)));
+            start = offset;
+        }
+    }
+
+    if start < text.len() {
+        segments.push((&text[start..], color(start)));
+    }
+
+    segments
+}
+
+struct Search {
+    task: tasks::Handle,
+    query: Query,
+}
+
+impl<H, I> Recipe<H, I> for Search
+where
+    H: std::hash::Hasher,
+{
+    type Output = Message;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.task.id().hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Message> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        rayon::spawn(move || {
+            let Search { task, query } = *self;
+
+            let searched = AtomicUsize::new(0);
+            let found = AtomicUsize::new(0);
+
+            let files: Box<dyn Iterator<Item = PathBuf> + Send> = match query.files.clone() {
+                Some(files) => Box::new(files.into_iter()),
+                None => Box::new(
+                    WalkDir::new(&query.root)
+                        .into_iter()
+                        .filter_entry(|entry| entry.depth() == 0 || !is_hidden(entry.path()))
+                        .filter_map(Result::ok)
+                        .filter(|entry| entry.file_type().is_file())
+                        .map(|entry| entry.into_path()),
+                ),
+            };
+
+            let _ = files.par_bridge().try_for_each(|path| {
+                if task.is_cancelled()
+                    || sender.is_closed()
+                    || found.load(Ordering::Relaxed) >= MAX_RESULTS
+                {
+                    return Err(());
+                }
+
+                let (matches, skipped) = search_file(&path, &query);
+
+                if let Some(note) = skipped {
+                    let _ = sender.unbounded_send(Message::Skipped(note));
+                }
+
+                let searched = searched.fetch_add(1, Ordering::Relaxed) + 1;
+                found.fetch_add(matches.len(), Ordering::Relaxed);
+
+                if searched.is_multiple_of(100) {
+                    task.set_progress(0, 0, format!("{} files searched", searched));
+                }
+
+                if !matches.is_empty() {
+                    let _ = sender.unbounded_send(Message::Found(matches));
+                }
+
+                Ok(())
+            });
+
+            let _ = sender.unbounded_send(Message::Finished);
+        });
+
+        receiver.boxed()
+    }
+}
+
+fn search_file(path: &Path, query: &Query) -> (Vec<Match>, Option<String>) {
+    let too_large = fs::metadata(path).map_or(true, |metadata| metadata.len() > MAX_FILE_SIZE);
+
+    if too_large {
+        return (vec![], None);
+    }
+
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(_) => return (vec![], None),
+    };
+
+    if bytes[..bytes.len().min(SNIFF_LENGTH)].contains(&0) {
+        return (vec![], None);
+    }
+
+    let mut skipped = vec![];
+
+    let matches = String::from_utf8_lossy(&bytes)
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let found = match query.pattern.find(line) {
+                Ok(found) => found?,
+                Err(_) => {
+                    skipped.push(index + 1);
+                    return None;
+                }
+            };
+
+            let indent = line.len() - line.trim_start().len();
+            let text: String = line[indent..]
+                .trim_end()
+                .chars()
+                .take(MAX_PREVIEW_LENGTH)
+                .collect();
+
+            let shift = |offset: usize| offset.saturating_sub(indent).min(text.len());
+
+            Some(Match {
+                path: path.to_path_buf(),
+                line: index + 1,
+                found: Found {
+                    start: shift(found.start),
+                    end: shift(found.end),
+                    groups: found
+                        .groups
+                        .iter()
+                        .map(|group| group.map(|(start, end)| (shift(start), shift(end))))
+                        .collect(),
+                },
+                text,
+            })
+        })
+        .collect();
+
+    let skipped = match skipped.as_slice() {
+        [] => None,
+        [line] => Some(i18n::tr_with(
+            "Skipped line {} of {}, the pattern is too complex",
+            &[line, &path.display()],
+        )),
+        lines => Some(i18n::tr_with(
+            "Skipped {} lines of {}, the pattern is too complex",
+            &[&lines.len(), &path.display()],
+        )),
+    };
+
+    (matches, skipped)
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complex_lines_are_skipped_without_losing_other_results() {
+        let path = std::env::temp_dir().join(format!("search-skipped-{}", std::process::id()));
+        fs::write(&path, format!("aab\n{}\nab\n", "a".repeat(5000))).unwrap();
+
+        let query = Query {
+            pattern: Pattern::new("(a|aa)*b", true).unwrap(),
+            root: std::env::temp_dir(),
+            files: None,
+        };
+
+        let (matches, skipped) = search_file(&path, &query);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            matches.iter().map(|found| found.line).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(skipped.is_some_and(|note| note.contains("line 2 ")));
+    }
+}
@@ -0,0 +1,166 @@
+use iced::futures::channel::oneshot;
+use iced::futures::FutureExt;
+use walkdir::WalkDir;
+
+use crate::navigation;
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::thread;
+use std::time;
+
+const MAX_RESULTS: usize = 500;
+const DAY: time::Duration = time::Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub text: String,
+    pub root: PathBuf,
+    patterns: Vec<String>,
+    max_age: Option<time::Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contents {
+    Collapsed,
+    Loading,
+    Loaded(Vec<PathBuf>),
+}
+
+impl Query {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut root = None;
+        let mut patterns = vec![];
+        let mut max_age = None;
+
+        let mut tokens = text.split_whitespace();
+
+        while let Some(token) = tokens.next() {
+            if let Some(path) = token.strip_prefix("in:") {
+                root = Some(navigation::expand_path(
+                    path,
+                    dirs_next::home_dir().as_deref(),
+                ));
+            } else if token == "under" {
+                let path = tokens
+                    .next()
+                    .ok_or_else(|| "Missing folder after under".to_string())?;
+
+                root = Some(navigation::expand_path(
+                    path,
+                    dirs_next::home_dir().as_deref(),
+                ));
+            } else if let Some(age) = token.strip_prefix("modified:") {
+                max_age = Some(parse_age(age).ok_or_else(|| format!("Unknown age {:?}", age))?);
+            } else {
+                patterns.push(token.to_lowercase());
+            }
+        }
+
+        let root = root
+            .or_else(dirs_next::home_dir)
+            .ok_or_else(|| "No folder to search in".to_string())?;
+
+        if patterns.is_empty() && max_age.is_none() {
+            return Err("A smart folder needs a name pattern or modified:<age>".to_string());
+        }
+
+        Ok(Self {
+            text: text.trim().to_string(),
+            root,
+            patterns,
+            max_age,
+        })
+    }
+
+    fn matches(&self, name: &str, age: Option<time::Duration>) -> bool {
+        let name = name.to_lowercase();
+
+        let named = self.patterns.is_empty()
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| glob(pattern.as_bytes(), name.as_bytes()));
+
+        let recent = match (self.max_age, age) {
+            (Some(max_age), Some(age)) => age <= max_age,
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+
+        named && recent
+    }
+}
+
+fn parse_age(age: &str) -> Option<time::Duration> {
+    let (count, unit) = age.split_at(age.find(|character: char| !character.is_ascii_digit())?);
+    let count: u32 = count.parse().ok()?;
+
+    let unit = match unit {
+        "h" => time::Duration::from_secs(60 * 60),
+        "d" => DAY,
+        "w" => DAY * 7,
+        _ => return None,
+    };
+
+    Some(unit * count)
+}
+
+fn glob(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut pattern_index, mut name_index) = (0, 0);
+    let mut backtrack = None;
+
+    while name_index < name.len() {
+        match pattern.get(pattern_index) {
+            Some(b'*') => {
+                backtrack = Some((pattern_index, name_index));
+                pattern_index += 1;
+            }
+            Some(&byte) if byte == b'?' || byte == name[name_index] => {
+                pattern_index += 1;
+                name_index += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    pattern_index = star + 1;
+                    name_index = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[pattern_index..].iter().all(|byte| *byte == b'*')
+}
+
+pub fn read(query: Query) -> impl Future<Output = Vec<PathBuf>> {
+    let (sender, receiver) = oneshot::channel();
+
+    thread::spawn(move || {
+        let now = time::SystemTime::now();
+
+        let mut paths: Vec<_> = WalkDir::new(&query.root)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| {
+                let age = entry
+                    .metadata()
+                    .ok()
+                    .and_then(|metadata| metadata.modified().ok())
+                    .and_then(|modified| now.duration_since(modified).ok());
+
+                query.matches(&entry.file_name().to_string_lossy(), age)
+            })
+            .map(|entry| entry.into_path())
+            .take(MAX_RESULTS)
+            .collect();
+
+        paths.sort();
+
+        let _ = sender.send(paths);
+    });
+
+    receiver.map(Result::unwrap_or_default)
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const APPLICATION: &str = "navigation_tree";
+
+pub fn load(name: &str) -> Vec<String> {
+    path(name)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn save(
+    name: &'static str,
+    lines: Vec<String>,
+) -> impl Future<Output = Result<(), String>> + Send + 'static {
+    static SAVES: AtomicUsize = AtomicUsize::new(0);
+
+    let generation = SAVES.fetch_add(1, Ordering::Relaxed);
+
+    async move {
+        let path = path(name).ok_or_else(|| "No configuration directory available".to_string())?;
+
+        write(&path, &lines, generation)
+    }
+}
+
+fn write(path: &Path, lines: &[String], generation: usize) -> Result<(), String> {
+    static WRITTEN: Mutex<BTreeMap<PathBuf, usize>> = Mutex::new(BTreeMap::new());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Cannot create {:?}: {}", parent, error))?;
+    }
+
+    let mut contents = String::new();
+
+    for line in lines.iter().filter(|line| !line.contains(['\n', '\r'])) {
+        contents.push_str(line);
+        contents.push('\n');
+    }
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temporary = path.with_file_name(format!(".{}.{}.{}.tmp", name, process::id(), generation));
+
+    let result = fs::write(&temporary, contents).and_then(|_| {
+        let mut written = WRITTEN
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if written.get(path).is_some_and(|latest| *latest > generation) {
+            return fs::remove_file(&temporary);
+        }
+
+        fs::rename(&temporary, path)?;
+        written.insert(path.to_path_buf(), generation);
+
+        Ok(())
+    });
+
+    result.map_err(|error| {
+        let _ = fs::remove_file(&temporary);
+
+        format!("Cannot save {:?}: {}", path, error)
+    })
+}
+
+fn path(name: &str) -> Option<PathBuf> {
+    dirs_next::config_dir().map(|config| config.join(APPLICATION).join(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_saves_do_not_replace_newer_ones() {
+        let directory = std::env::temp_dir().join(format!("storage-{}", process::id()));
+        let path = directory.join("history");
+
+        write(&path, &["newer".to_string()], 2).unwrap();
+        write(&path, &["older".to_string()], 1).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "newer\n");
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
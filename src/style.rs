@@ -0,0 +1,135 @@
+use iced::tooltip::{self, Tooltip};
+use iced::{button, container, text_input};
+use iced::{Background, Color, Element, Vector};
+
+pub const ERROR: Color = Color::from_rgb(0.8, 0.2, 0.2);
+pub const GIT_BRANCH: Color = Color::from_rgb(0.55, 0.35, 0.75);
+pub const DIFF_ADDED: Color = Color::from_rgb(0.15, 0.55, 0.2);
+pub const DIFF_REMOVED: Color = Color::from_rgb(0.75, 0.2, 0.2);
+pub const DIFF_HUNK: Color = Color::from_rgb(0.3, 0.45, 0.75);
+pub const LOG_WARN: Color = Color::from_rgb(0.8, 0.6, 0.1);
+pub const GUTTER: Color = Color::from_rgb(0.5, 0.5, 0.5);
+pub const EXECUTABLE: Color = Color::from_rgb(0.15, 0.55, 0.25);
+pub const RECENT: Color = Color::from_rgb(0.85, 0.35, 0.05);
+pub const SEARCH_MATCH: Color = Color::from_rgb(0.85, 0.45, 0.1);
+pub const SEARCH_CAPTURE: Color = Color::from_rgb(0.6, 0.25, 0.7);
+pub const SELECTION: Color = Color::from_rgb(0.2, 0.45, 0.8);
+pub const HOVER: Color = Color::from_rgb(0.9, 0.92, 0.95);
+pub const FLASH: Color = Color::from_rgb(1.0, 0.82, 0.3);
+pub const INDENT_GUIDE: Color = Color::from_rgb(0.78, 0.78, 0.78);
+
+pub struct Selected;
+
+impl button::StyleSheet for Selected {
+    fn active(&self) -> button::Style {
+        button::Style {
+            shadow_offset: Vector::new(0.0, 0.0),
+            background: Some(Background::Color([0.2, 0.45, 0.8].into())),
+            border_radius: 2.0,
+            border_width: 1.0,
+            border_color: [0.15, 0.35, 0.65].into(),
+            text_color: Color::WHITE,
+        }
+    }
+}
+
+pub struct GroupHeader;
+
+impl button::StyleSheet for GroupHeader {
+    fn active(&self) -> button::Style {
+        button::Style {
+            shadow_offset: Vector::new(0.0, 0.0),
+            background: None,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+            text_color: [0.3, 0.3, 0.3].into(),
+        }
+    }
+}
+
+pub struct FocusRing(pub bool);
+
+impl container::StyleSheet for FocusRing {
+    fn style(&self) -> container::Style {
+        container::Style {
+            border_radius: 2.0,
+            border_width: 2.0,
+            border_color: if self.0 {
+                [0.2, 0.45, 0.8].into()
+            } else {
+                Color::TRANSPARENT
+            },
+            ..container::Style::default()
+        }
+    }
+}
+
+pub struct ValidatedInput {
+    pub border: Option<Color>,
+    pub is_selected: bool,
+}
+
+impl text_input::StyleSheet for ValidatedInput {
+    fn active(&self) -> text_input::Style {
+        text_input::Style {
+            background: Background::Color(if self.is_selected {
+                SELECTION
+            } else {
+                Color::WHITE
+            }),
+            border_radius: 2.0,
+            border_width: 1.0,
+            border_color: self.border.unwrap_or_else(|| [0.7, 0.7, 0.7].into()),
+        }
+    }
+
+    fn focused(&self) -> text_input::Style {
+        text_input::Style {
+            border_width: 2.0,
+            ..self.active()
+        }
+    }
+
+    fn placeholder_color(&self) -> Color {
+        [0.7, 0.7, 0.7].into()
+    }
+
+    fn value_color(&self) -> Color {
+        if self.is_selected {
+            Color::WHITE
+        } else {
+            [0.3, 0.3, 0.3].into()
+        }
+    }
+
+    fn selection_color(&self) -> Color {
+        [0.8, 0.8, 1.0].into()
+    }
+}
+
+pub struct TooltipBox;
+
+impl container::StyleSheet for TooltipBox {
+    fn style(&self) -> container::Style {
+        container::Style {
+            background: Some(Background::Color([0.97, 0.97, 0.97].into())),
+            border_radius: 2.0,
+            border_width: 1.0,
+            border_color: [0.7, 0.7, 0.7].into(),
+            ..container::Style::default()
+        }
+    }
+}
+
+pub fn labelled<'a, Message: 'a>(
+    content: impl Into<Element<'a, Message>>,
+    label: &str,
+) -> Element<'a, Message> {
+    Tooltip::new(content, label, tooltip::Position::Bottom)
+        .size(14)
+        .padding(5)
+        .gap(5)
+        .style(TooltipBox)
+        .into()
+}
@@ -0,0 +1,280 @@
+use iced::button::{self, Button};
+use iced::scrollable;
+use iced::{Align, Element, Row, Text};
+use iced_native::{
+    event, layout, mouse, overlay, Clipboard, Event, Hasher, Layout, Length, Point, Rectangle,
+    Widget,
+};
+
+use crate::bidi;
+use crate::i18n;
+use crate::style;
+use crate::viewer::Document;
+
+use std::mem;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Message {
+    Select(usize),
+    Close(usize),
+}
+
+struct Tab {
+    path: PathBuf,
+    parked: Option<(Document, scrollable::State)>,
+    button: button::State,
+    close_button: button::State,
+}
+
+#[derive(Default)]
+pub struct State {
+    tabs: Vec<Tab>,
+    active: Option<usize>,
+}
+
+impl State {
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.tabs.iter().map(|tab| tab.path.as_path())
+    }
+
+    pub fn active(&self) -> Option<usize> {
+        self.active
+    }
+
+    pub fn open(
+        &mut self,
+        document: Document,
+        viewer: &mut Option<Document>,
+        scrollable: &mut scrollable::State,
+    ) {
+        match self.position(&document.path) {
+            Some(index) => {
+                self.select(index, viewer, scrollable);
+
+                if let Some(existing) = viewer.as_mut().filter(|existing| existing.is_dirty()) {
+                    existing.highlight = document.highlight;
+                    return;
+                }
+            }
+            None => {
+                self.park(viewer, scrollable);
+
+                self.tabs.push(Tab {
+                    path: document.path.clone(),
+                    parked: None,
+                    button: button::State::new(),
+                    close_button: button::State::new(),
+                });
+                self.active = Some(self.tabs.len() - 1);
+            }
+        }
+
+        *viewer = Some(document);
+    }
+
+    pub fn select(
+        &mut self,
+        index: usize,
+        viewer: &mut Option<Document>,
+        scrollable: &mut scrollable::State,
+    ) {
+        if self.active == Some(index) || index >= self.tabs.len() {
+            return;
+        }
+
+        self.park(viewer, scrollable);
+        self.active = Some(index);
+
+        let (document, parked_scrollable) = match self.tabs[index].parked.take() {
+            Some((document, scrollable)) => (Some(document), scrollable),
+            None => (None, scrollable::State::new()),
+        };
+
+        *viewer = document;
+        *scrollable = parked_scrollable;
+    }
+
+    pub fn close(
+        &mut self,
+        index: usize,
+        viewer: &mut Option<Document>,
+        scrollable: &mut scrollable::State,
+    ) {
+        if index >= self.tabs.len() {
+            return;
+        }
+
+        self.tabs.remove(index);
+
+        match self.active {
+            Some(active) if active == index => {
+                self.active = None;
+                *viewer = None;
+
+                if !self.tabs.is_empty() {
+                    self.select(index.min(self.tabs.len() - 1), viewer, scrollable);
+                }
+            }
+            Some(active) if active > index => {
+                self.active = Some(active - 1);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn is_dirty(&self, index: usize, viewer: &Option<Document>) -> bool {
+        let document = if self.active == Some(index) {
+            viewer.as_ref()
+        } else {
+            self.tabs
+                .get(index)
+                .and_then(|tab| tab.parked.as_ref())
+                .map(|(document, _)| document)
+        };
+
+        document.is_some_and(Document::is_dirty)
+    }
+
+    pub fn path(&self, index: usize) -> Option<&Path> {
+        self.tabs.get(index).map(|tab| tab.path.as_path())
+    }
+
+    pub fn position(&self, path: &Path) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.path == path)
+    }
+
+    fn park(&mut self, viewer: &mut Option<Document>, scrollable: &mut scrollable::State) {
+        if let (Some(active), Some(document)) = (self.active, viewer.take()) {
+            self.tabs[active].parked = Some((document, mem::take(scrollable)));
+        }
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let active = state.active;
+
+    state
+        .tabs
+        .iter_mut()
+        .enumerate()
+        .fold(Row::new().spacing(5), |row, (index, tab)| {
+            let label = tab
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| tab.path.to_string_lossy().to_string());
+            let label = bidi::display(&label, i18n::is_right_to_left());
+
+            let mut select = Button::new(&mut tab.button, Text::new(label).size(14))
+                .padding(3)
+                .on_press(Message::Select(index));
+
+            select = if active == Some(index) {
+                select.style(style::Selected)
+            } else {
+                select.style(style::GroupHeader)
+            };
+
+            let close = Button::new(&mut tab.close_button, Text::new("×").size(14))
+                .padding(3)
+                .style(style::GroupHeader)
+                .on_press(Message::Close(index));
+
+            let tab = Row::new()
+                .align_items(Align::Center)
+                .push(select)
+                .push(style::labelled(close, i18n::tr("Close tab")));
+
+            row.push(MiddleClick {
+                content: tab.into(),
+                on_press: Message::Close(index),
+            })
+        })
+        .into()
+}
+
+struct MiddleClick<'a, Message, Renderer> {
+    content: iced_native::Element<'a, Message, Renderer>,
+    on_press: Message,
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for MiddleClick<'a, Message, Renderer>
+where
+    Message: Clone,
+    Renderer: iced_native::Renderer,
+{
+    fn width(&self) -> Length {
+        self.content.width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.layout(renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        defaults: &Renderer::Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) -> Renderer::Output {
+        self.content
+            .draw(renderer, defaults, layout, cursor_position, viewport)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        self.content.hash_layout(state);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) = event {
+            if layout.bounds().contains(cursor_position) {
+                messages.push(self.on_press.clone());
+
+                return event::Status::Captured;
+            }
+        }
+
+        self.content.on_event(
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            messages,
+        )
+    }
+
+    fn overlay(&mut self, layout: Layout<'_>) -> Option<overlay::Element<'_, Message, Renderer>> {
+        self.content.overlay(layout)
+    }
+}
+
+impl<'a, Message, Renderer> From<MiddleClick<'a, Message, Renderer>>
+    for iced_native::Element<'a, Message, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(middle_click: MiddleClick<'a, Message, Renderer>) -> Self {
+        iced_native::Element::new(middle_click)
+    }
+}
@@ -0,0 +1,101 @@
+use iced::Color;
+
+use crate::i18n;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+pub const TAGS: &str = "tags";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tag {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 6] = [
+        Tag::Red,
+        Tag::Orange,
+        Tag::Yellow,
+        Tag::Green,
+        Tag::Blue,
+        Tag::Purple,
+    ];
+
+    pub fn color(self) -> Color {
+        match self {
+            Tag::Red => Color::from_rgb(0.85, 0.2, 0.2),
+            Tag::Orange => Color::from_rgb(0.95, 0.55, 0.1),
+            Tag::Yellow => Color::from_rgb(0.9, 0.8, 0.1),
+            Tag::Green => Color::from_rgb(0.2, 0.7, 0.3),
+            Tag::Blue => Color::from_rgb(0.2, 0.45, 0.9),
+            Tag::Purple => Color::from_rgb(0.6, 0.3, 0.8),
+        }
+    }
+
+    pub fn after(tag: Option<Tag>) -> Option<Tag> {
+        match tag {
+            None => Some(Tag::ALL[0]),
+            Some(tag) => {
+                let index = Tag::ALL.iter().position(|candidate| *candidate == tag)?;
+
+                Tag::ALL.get(index + 1).copied()
+            }
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Tag::Red => "red",
+            Tag::Orange => "orange",
+            Tag::Yellow => "yellow",
+            Tag::Green => "green",
+            Tag::Blue => "blue",
+            Tag::Purple => "purple",
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Tag::Red => "Red",
+            Tag::Orange => "Orange",
+            Tag::Yellow => "Yellow",
+            Tag::Green => "Green",
+            Tag::Blue => "Blue",
+            Tag::Purple => "Purple",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+pub fn parse(lines: &[String]) -> HashMap<PathBuf, Tag> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let (key, path) = line.split_once('\t')?;
+            let tag = Tag::ALL.iter().find(|tag| tag.key() == key)?;
+
+            Some((PathBuf::from(path), *tag))
+        })
+        .collect()
+}
+
+pub fn lines(tags: &HashMap<PathBuf, Tag>) -> Vec<String> {
+    let mut lines: Vec<_> = tags
+        .iter()
+        .map(|(path, tag)| format!("{}\t{}", tag.key(), path.to_string_lossy()))
+        .collect();
+
+    lines.sort();
+
+    lines
+}
@@ -0,0 +1,201 @@
+use iced::button::{self, Button};
+use iced::scrollable::{self, Scrollable};
+use iced::{Column, Element, Length, ProgressBar, Row, Space, Text};
+
+use crate::i18n;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub type Id = u64;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Cancel(Id),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Tasks {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    next_id: Id,
+    tasks: Vec<Task>,
+}
+
+#[derive(Debug, Clone)]
+struct Task {
+    id: Id,
+    label: String,
+    progress: Option<(u64, u64)>,
+    detail: Option<String>,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Handle {
+    inner: Arc<Registration>,
+}
+
+#[derive(Debug)]
+struct Registration {
+    id: Id,
+    tasks: Tasks,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Tasks {
+    pub fn start(&self, label: impl Into<String>) -> Handle {
+        let mut inner = self.lock();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+
+        inner.tasks.push(Task {
+            id,
+            label: label.into(),
+            progress: None,
+            detail: None,
+            cancelled: cancelled.clone(),
+        });
+
+        Handle {
+            inner: Arc::new(Registration {
+                id,
+                tasks: self.clone(),
+                cancelled,
+            }),
+        }
+    }
+
+    pub fn cancel(&self, id: Id) {
+        if let Some(task) = self.lock().tasks.iter().find(|task| task.id == id) {
+            task.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> Vec<Task> {
+        self.lock().tasks.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Handle {
+    pub fn id(&self) -> Id {
+        self.inner.id
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_progress(&self, done: u64, total: u64, detail: impl Into<String>) {
+        let mut inner = self.inner.tasks.lock();
+
+        if let Some(task) = inner.tasks.iter_mut().find(|task| task.id == self.inner.id) {
+            task.progress = Some((done, total));
+            task.detail = Some(detail.into());
+        }
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.tasks.lock().tasks.retain(|task| task.id != self.id);
+    }
+}
+
+pub struct State {
+    tasks: Tasks,
+    cancel_buttons: Vec<button::State>,
+    scrollable: scrollable::State,
+}
+
+impl State {
+    pub fn new(tasks: Tasks) -> Self {
+        Self {
+            tasks,
+            cancel_buttons: vec![],
+            scrollable: scrollable::State::new(),
+        }
+    }
+
+    pub fn tasks(&self) -> &Tasks {
+        &self.tasks
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Cancel(id) => self.tasks.cancel(id),
+        }
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let tasks = state.tasks.snapshot();
+
+    state
+        .cancel_buttons
+        .resize_with(tasks.len(), button::State::new);
+
+    let mut scrollable = Scrollable::new(&mut state.scrollable).spacing(10);
+
+    if tasks.is_empty() {
+        scrollable = scrollable.push(Text::new(i18n::tr("No background tasks running")));
+    }
+
+    for (task, button) in tasks.iter().zip(state.cancel_buttons.iter_mut()) {
+        let cancelled = task.cancelled.load(Ordering::Relaxed);
+
+        let label = if cancelled {
+            format!("#{} {} (cancelling)", task.id, task.label)
+        } else {
+            format!("#{} {}", task.id, task.label)
+        };
+
+        let mut cancel = Button::new(button, Text::new(i18n::tr("Cancel")));
+
+        if !cancelled {
+            cancel = cancel.on_press(Message::Cancel(task.id));
+        }
+
+        let mut column = Column::new().spacing(5).push(
+            Row::new()
+                .spacing(10)
+                .push(Text::new(label))
+                .push(Space::with_width(Length::Fill))
+                .push(cancel),
+        );
+
+        if let Some((done, total)) = task.progress {
+            column = column.push(
+                ProgressBar::new(0.0..=total.max(1) as f32, done as f32).height(Length::Units(10)),
+            );
+        }
+
+        if let Some(detail) = &task.detail {
+            column = column.push(Text::new(detail).size(14));
+        }
+
+        scrollable = scrollable.push(column);
+    }
+
+    Column::new()
+        .spacing(10)
+        .width(Length::Fill)
+        .push(Text::new(i18n::tr("Tasks")).size(30))
+        .push(scrollable)
+        .into()
+}
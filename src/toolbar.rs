@@ -0,0 +1,44 @@
+use iced::button::{self, Button};
+use iced::{Element, Row, Text};
+
+use crate::i18n;
+use crate::keymap::Action;
+use crate::style;
+
+const ACTIONS: [(Action, &str); 14] = [
+    (Action::NewFolder, "New folder"),
+    (Action::NewFile, "New file"),
+    (Action::Rename, "Rename"),
+    (Action::Copy, "Copy"),
+    (Action::Move, "Move"),
+    (Action::Trash, "Trash"),
+    (Action::ShowProperties, "Properties"),
+    (Action::Run, "Run"),
+    (Action::ViewDiff, "Diff"),
+    (Action::Undo, "Undo"),
+    (Action::Redo, "Redo"),
+    (Action::ToggleHistory, "History"),
+    (Action::ToggleTasks, "Tasks"),
+    (Action::OpenTerminal, "Terminal"),
+];
+
+#[derive(Default)]
+pub struct State {
+    buttons: [button::State; ACTIONS.len()],
+}
+
+pub fn view(state: &mut State, is_enabled: impl Fn(Action) -> bool) -> Element<'_, Action> {
+    let mut row = Row::new().spacing(5);
+
+    for ((action, label), button) in ACTIONS.iter().zip(state.buttons.iter_mut()) {
+        let mut button = Button::new(button, Text::new(i18n::tr(label)));
+
+        if is_enabled(*action) {
+            button = button.on_press(*action);
+        }
+
+        row = row.push(style::labelled(button, action.description()));
+    }
+
+    row.into()
+}
@@ -0,0 +1,584 @@
+use iced::button::{self, Button};
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{BoxStream, StreamExt};
+use iced::pick_list::{self, PickList};
+use iced::{Checkbox, Column, Container, Element, Length, ProgressBar, Row, Subscription, Text};
+use iced_native::subscription::Recipe;
+
+use crate::file_ops::{self, Operation};
+use crate::format;
+use crate::i18n;
+use crate::tasks::{self, Tasks};
+use crate::trash;
+
+use std::fmt;
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Copy,
+    Move,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Policy {
+    #[default]
+    Ask,
+    Skip,
+    Overwrite,
+    KeepBoth,
+}
+
+impl Policy {
+    pub const ALL: [Policy; 4] = [
+        Policy::Ask,
+        Policy::Skip,
+        Policy::Overwrite,
+        Policy::KeepBoth,
+    ];
+
+    pub fn resolution(self) -> Option<Resolution> {
+        match self {
+            Policy::Ask => None,
+            Policy::Skip => Some(Resolution::Skip),
+            Policy::Overwrite => Some(Resolution::Overwrite),
+            Policy::KeepBoth => Some(Resolution::Rename),
+        }
+    }
+
+    fn of(resolution: Option<Resolution>) -> Self {
+        match resolution {
+            None => Policy::Ask,
+            Some(Resolution::Skip) => Policy::Skip,
+            Some(Resolution::Overwrite) => Policy::Overwrite,
+            Some(Resolution::Rename) => Policy::KeepBoth,
+        }
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Policy::Ask => "Ask",
+            Policy::Skip => "Skip",
+            Policy::Overwrite => "Overwrite",
+            Policy::KeepBoth => "Keep both",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Progressed(Progress),
+    Conflict(PathBuf),
+    Resolve(Resolution),
+    ApplyToAll(bool),
+    PolicySelected(Policy),
+    Finished(Result<Operation, String>),
+    TogglePause,
+    Hide,
+    Cancel,
+}
+
+#[derive(Debug, Clone)]
+pub struct Progress {
+    bytes_done: u64,
+    bytes_total: u64,
+    current_file: PathBuf,
+}
+
+pub struct State {
+    task: tasks::Handle,
+    kind: Kind,
+    from: PathBuf,
+    to: PathBuf,
+    paused: Arc<AtomicBool>,
+    resolution: Arc<Mutex<Option<Resolution>>>,
+    conflict: Option<PathBuf>,
+    apply_to_all: bool,
+    policy: Policy,
+    hidden: bool,
+    progress: Option<Progress>,
+    started: Instant,
+    paused_at: Option<Instant>,
+    paused_for: Duration,
+    pause_button: button::State,
+    hide_button: button::State,
+    cancel_button: button::State,
+    skip_button: button::State,
+    overwrite_button: button::State,
+    rename_button: button::State,
+    policy_list: pick_list::State<Policy>,
+}
+
+impl State {
+    pub fn new(
+        kind: Kind,
+        from: PathBuf,
+        to: PathBuf,
+        policy: Option<Resolution>,
+        tasks: &Tasks,
+    ) -> Self {
+        let verb = match kind {
+            Kind::Copy => i18n::tr("Copying"),
+            Kind::Move => i18n::tr("Moving"),
+        };
+
+        Self {
+            task: tasks.start(format!("{} {:?} to {:?}", verb, from, to)),
+            kind,
+            from,
+            to,
+            paused: Arc::new(AtomicBool::new(false)),
+            resolution: Arc::new(Mutex::new(policy)),
+            conflict: None,
+            apply_to_all: false,
+            policy: Policy::of(policy),
+            hidden: false,
+            progress: None,
+            started: Instant::now(),
+            paused_at: None,
+            paused_for: Duration::default(),
+            pause_button: button::State::new(),
+            hide_button: button::State::new(),
+            cancel_button: button::State::new(),
+            skip_button: button::State::new(),
+            overwrite_button: button::State::new(),
+            rename_button: button::State::new(),
+            policy_list: pick_list::State::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Result<Operation, String>> {
+        match message {
+            Message::Progressed(progress) => {
+                self.progress = Some(progress);
+            }
+            Message::Conflict(path) => {
+                self.conflict = Some(path);
+            }
+            Message::Resolve(resolution) => self.resolve(resolution),
+            Message::ApplyToAll(apply_to_all) => {
+                self.apply_to_all = apply_to_all;
+            }
+            Message::PolicySelected(policy) => {
+                self.policy = policy;
+
+                if let Ok(mut current) = self.resolution.lock() {
+                    *current = policy.resolution();
+                }
+            }
+            Message::Finished(result) => return Some(result),
+            Message::TogglePause => match self.paused_at.take() {
+                Some(paused_at) => {
+                    self.paused_for += paused_at.elapsed();
+                    self.paused.store(false, Ordering::Relaxed);
+                }
+                None => {
+                    self.paused_at = Some(Instant::now());
+                    self.paused.store(true, Ordering::Relaxed);
+                }
+            },
+            Message::Hide => {
+                self.hidden = true;
+            }
+            Message::Cancel => {
+                self.task.cancel();
+            }
+        }
+
+        None
+    }
+
+    pub fn id(&self) -> tasks::Id {
+        self.task.id()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        (self.progress.is_some() && !self.hidden) || self.conflict.is_some()
+    }
+
+    pub fn applies_to_all(&self) -> bool {
+        self.apply_to_all
+    }
+
+    pub fn resolve(&mut self, resolution: Resolution) {
+        self.conflict = None;
+        self.policy = Policy::of(Some(resolution));
+
+        if let Ok(mut current) = self.resolution.lock() {
+            *current = Some(resolution);
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::from_recipe(Transfer {
+            task: self.task.clone(),
+            kind: self.kind,
+            from: self.from.clone(),
+            to: self.to.clone(),
+            paused: self.paused.clone(),
+            resolution: self.resolution.clone(),
+        })
+    }
+
+    fn elapsed(&self) -> Duration {
+        let paused = self.paused_for
+            + self
+                .paused_at
+                .map(|paused_at| paused_at.elapsed())
+                .unwrap_or_default();
+
+        self.started.elapsed().saturating_sub(paused)
+    }
+}
+
+pub fn view(state: &mut State) -> Element<'_, Message> {
+    let verb = match state.kind {
+        Kind::Copy => i18n::tr("Copying"),
+        Kind::Move => i18n::tr("Moving"),
+    };
+
+    let mut content = Column::new()
+        .spacing(10)
+        .max_width(700)
+        .push(Text::new(format!("{} {:?}", verb, state.from)).size(24))
+        .push(Text::new(i18n::tr_with(
+            "to {}",
+            &[&format!("{:?}", state.to)],
+        )));
+
+    if let Some(conflict) = &state.conflict {
+        let choice = |state, label, resolution| {
+            Button::new(state, Text::new(i18n::tr(label))).on_press(Message::Resolve(resolution))
+        };
+
+        let choices = Row::new()
+            .spacing(10)
+            .push(choice(&mut state.skip_button, "Skip", Resolution::Skip))
+            .push(choice(
+                &mut state.overwrite_button,
+                "Overwrite",
+                Resolution::Overwrite,
+            ))
+            .push(choice(
+                &mut state.rename_button,
+                "Rename",
+                Resolution::Rename,
+            ));
+
+        content = content
+            .push(Text::new(i18n::tr_with(
+                "{} already exists",
+                &[&format!("{:?}", conflict)],
+            )))
+            .push(choices)
+            .push(Checkbox::new(
+                state.apply_to_all,
+                i18n::tr("Apply to all"),
+                Message::ApplyToAll,
+            ));
+
+        let cancel = Button::new(&mut state.cancel_button, Text::new(i18n::tr("Cancel")))
+            .on_press(Message::Cancel);
+
+        return Container::new(content.push(cancel))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(40)
+            .center_x()
+            .center_y()
+            .into();
+    }
+
+    if let Some(progress) = &state.progress {
+        let seconds = state.elapsed().as_secs_f64();
+        let speed = if seconds > 0.0 {
+            progress.bytes_done as f64 / seconds
+        } else {
+            0.0
+        };
+
+        let remaining = progress.bytes_total.saturating_sub(progress.bytes_done);
+        let eta = if speed > 0.0 {
+            duration(Duration::from_secs_f64(remaining as f64 / speed))
+        } else {
+            i18n::tr("Unknown").to_string()
+        };
+
+        content = content
+            .push(ProgressBar::new(
+                0.0..=progress.bytes_total.max(1) as f32,
+                progress.bytes_done as f32,
+            ))
+            .push(Text::new(i18n::tr_with(
+                "{} of {}",
+                &[
+                    &format::size(progress.bytes_done),
+                    &format::size(progress.bytes_total),
+                ],
+            )))
+            .push(
+                Text::new(i18n::tr_with(
+                    "Current file: {}",
+                    &[&format!("{:?}", progress.current_file)],
+                ))
+                .size(14),
+            )
+            .push(Text::new(i18n::tr_with(
+                "Speed: {}/s, time remaining: {}",
+                &[&format::size(speed as u64), &eta],
+            )));
+    }
+
+    let pause_label = if state.paused_at.is_some() {
+        "Resume"
+    } else {
+        "Pause"
+    };
+
+    let pause = Button::new(&mut state.pause_button, Text::new(i18n::tr(pause_label)))
+        .on_press(Message::TogglePause);
+    let hide = Button::new(
+        &mut state.hide_button,
+        Text::new(i18n::tr("Run in background")),
+    )
+    .on_press(Message::Hide);
+    let cancel = Button::new(&mut state.cancel_button, Text::new(i18n::tr("Cancel")))
+        .on_press(Message::Cancel);
+
+    let policy = Row::new()
+        .spacing(10)
+        .push(Text::new(i18n::tr("If the target exists")))
+        .push(PickList::new(
+            &mut state.policy_list,
+            &Policy::ALL[..],
+            Some(state.policy),
+            Message::PolicySelected,
+        ));
+
+    let buttons = Row::new().spacing(10).push(pause).push(hide).push(cancel);
+
+    content = content.push(policy);
+
+    Container::new(content.push(buttons))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(40)
+        .center_x()
+        .center_y()
+        .into()
+}
+
+fn duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    if seconds >= 3600 {
+        format!("{}h {}m", seconds / 3600, seconds % 3600 / 60)
+    } else if seconds >= 60 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+struct Transfer {
+    task: tasks::Handle,
+    kind: Kind,
+    from: PathBuf,
+    to: PathBuf,
+    paused: Arc<AtomicBool>,
+    resolution: Arc<Mutex<Option<Resolution>>>,
+}
+
+impl<H, I> Recipe<H, I> for Transfer
+where
+    H: std::hash::Hasher,
+{
+    type Output = Message;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.task.id().hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Message> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        thread::spawn(move || {
+            let result = run(&self, &sender);
+
+            let _ = sender.unbounded_send(Message::Finished(result));
+        });
+
+        receiver.boxed()
+    }
+}
+
+fn run(transfer: &Transfer, sender: &mpsc::UnboundedSender<Message>) -> Result<Operation, String> {
+    let Transfer { kind, from, .. } = transfer;
+
+    let mut to = transfer.to.clone();
+    let mut replaced = None;
+
+    if fs::symlink_metadata(&to).is_ok() {
+        match resolve(transfer, sender)? {
+            Resolution::Skip => return Err(format!("Skipped {:?}, it already exists", to)),
+            Resolution::Overwrite => replaced = Some(trash::trash(&to)?),
+            Resolution::Rename => to = file_ops::keep_both(&to),
+        }
+    }
+
+    if let Err(error) = transfer_to(transfer, &to, sender) {
+        if let Some(replaced) = &replaced {
+            let _ = trash::restore(replaced);
+        }
+
+        return Err(error);
+    }
+
+    Ok(match (kind, replaced) {
+        (Kind::Copy, Some(replaced)) => Operation::Overwrite {
+            from: from.clone(),
+            to,
+            replaced,
+        },
+        (Kind::Copy, None) => Operation::Copy {
+            from: from.clone(),
+            to,
+        },
+        (Kind::Move, Some(replaced)) => Operation::MoveOver {
+            from: from.clone(),
+            to,
+            replaced,
+        },
+        (Kind::Move, None) => Operation::Move {
+            from: from.clone(),
+            to,
+        },
+    })
+}
+
+fn resolve(
+    transfer: &Transfer,
+    sender: &mpsc::UnboundedSender<Message>,
+) -> Result<Resolution, String> {
+    let current = || {
+        transfer
+            .resolution
+            .lock()
+            .map_err(|_| "Cancelled".to_string())
+            .map(|resolution| *resolution)
+    };
+
+    if let Some(resolution) = current()? {
+        return Ok(resolution);
+    }
+
+    sender
+        .unbounded_send(Message::Conflict(transfer.to.clone()))
+        .map_err(|_| "Cancelled".to_string())?;
+
+    loop {
+        if transfer.task.is_cancelled() {
+            return Err("Cancelled".into());
+        }
+
+        if let Some(resolution) = current()? {
+            return Ok(resolution);
+        }
+
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+fn transfer_to(
+    transfer: &Transfer,
+    to: &Path,
+    sender: &mpsc::UnboundedSender<Message>,
+) -> Result<(), String> {
+    let Transfer {
+        task,
+        kind,
+        from,
+        paused,
+        ..
+    } = transfer;
+
+    if *kind == Kind::Move {
+        match fs::rename(from, to) {
+            Err(error) if error.kind() == io::ErrorKind::CrossesDevices => {}
+            result => {
+                return result
+                    .map_err(|error| format!("Cannot move {:?} to {:?}: {}", from, to, error));
+            }
+        }
+    }
+
+    let bytes_total = file_ops::disk_usage(from, || task.is_cancelled())
+        .ok_or_else(|| "Cancelled".to_string())?;
+    let mut bytes_done = 0;
+    let mut reported_at: Option<Instant> = None;
+
+    file_ops::copy(from, to, |current_file: &Path, written| {
+        bytes_done += written;
+
+        while paused.load(Ordering::Relaxed) && !task.is_cancelled() {
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        if task.is_cancelled() {
+            return Err("Cancelled".into());
+        }
+
+        if reported_at.is_none_or(|reported_at| reported_at.elapsed() >= Duration::from_millis(100))
+        {
+            reported_at = Some(Instant::now());
+
+            task.set_progress(
+                bytes_done,
+                bytes_total,
+                format!(
+                    "{} of {}",
+                    format::size(bytes_done),
+                    format::size(bytes_total)
+                ),
+            );
+
+            let progress = Progress {
+                bytes_done,
+                bytes_total,
+                current_file: current_file.to_path_buf(),
+            };
+
+            sender
+                .unbounded_send(Message::Progressed(progress))
+                .map_err(|_| "Cancelled".to_string())?;
+        }
+
+        Ok(())
+    })?;
+
+    if *kind == Kind::Move {
+        file_ops::remove(from)
+            .map_err(|error| format!("Copied {:?} but cannot remove it: {}", from, error))?;
+    }
+
+    Ok(())
+}
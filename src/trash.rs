@@ -0,0 +1,295 @@
+use percent_encoding::{percent_decode_str, percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::file_ops;
+use crate::format;
+
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const PATH_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub original: PathBuf,
+    pub trashed: PathBuf,
+    pub deleted_at: String,
+    info: PathBuf,
+}
+
+pub fn trash(path: &Path) -> Result<Item, String> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("Cannot trash {:?}", path))?;
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let original = parent
+        .canonicalize()
+        .map_err(|error| format!("Cannot trash {:?}: {}", path, error))?
+        .join(name);
+
+    let directory =
+        directory_for(&original).ok_or_else(|| "No trash directory available".to_string())?;
+    let files = directory.join("files");
+    let info = directory.join("info");
+
+    fs::create_dir_all(&files).map_err(|error| error.to_string())?;
+    fs::create_dir_all(&info).map_err(|error| error.to_string())?;
+
+    let (info_path, mut info_file) =
+        create_info_file(&info, name).map_err(|error| error.to_string())?;
+    let trashed = files.join(info_path.file_stem().unwrap_or(name));
+
+    let deleted_at = format::timestamp(SystemTime::now(), 'T');
+
+    let recorded = match top_directory(&directory) {
+        Some(top) => original.strip_prefix(top).unwrap_or(&original),
+        None => &original,
+    };
+
+    let contents = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encode_path(recorded),
+        deleted_at,
+    );
+
+    let result = info_file
+        .write_all(contents.as_bytes())
+        .map_err(|error| error.to_string())
+        .and_then(|_| file_ops::relocate(&original, &trashed));
+
+    if let Err(error) = result {
+        let _ = fs::remove_file(&info_path);
+
+        return Err(format!("Cannot trash {:?}: {}", original, error));
+    }
+
+    Ok(Item {
+        original,
+        trashed,
+        deleted_at,
+        info: info_path,
+    })
+}
+
+pub async fn list() -> Result<Vec<Item>, String> {
+    let home = home_directory().ok_or_else(|| "No trash directory available".to_string())?;
+
+    let mut items = read_items(&home)?;
+
+    for directory in top_directories() {
+        items.extend(read_items(&directory).unwrap_or_default());
+    }
+
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+
+    Ok(items)
+}
+
+fn read_items(directory: &Path) -> Result<Vec<Item>, String> {
+    let files = directory.join("files");
+    let top = top_directory(directory);
+
+    let read_dir = match fs::read_dir(directory.join("info")) {
+        Ok(read_dir) => read_dir,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(error.to_string()),
+    };
+
+    let items = read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let info = entry.path();
+            let name = info.file_stem()?;
+
+            if info.extension()? != "trashinfo" {
+                return None;
+            }
+
+            let contents = fs::read_to_string(&info).ok()?;
+
+            let mut original = None;
+            let mut deleted_at = String::new();
+
+            for line in contents.lines() {
+                if let Some(path) = line.strip_prefix("Path=") {
+                    let path = decode_path(path);
+
+                    original = Some(match top {
+                        Some(top) if path.is_relative() => top.join(path),
+                        _ => path,
+                    });
+                } else if let Some(date) = line.strip_prefix("DeletionDate=") {
+                    deleted_at = date.to_string();
+                }
+            }
+
+            Some(Item {
+                original: original?,
+                trashed: files.join(name),
+                deleted_at,
+                info,
+            })
+        })
+        .collect();
+
+    Ok(items)
+}
+
+pub async fn delete(item: Item) -> Result<(), String> {
+    file_ops::remove(&item.trashed)
+        .map_err(|error| format!("Cannot delete {:?}: {}", item.trashed, error))?;
+
+    fs::remove_file(&item.info).map_err(|error| error.to_string())
+}
+
+pub async fn restore_item(item: Item) -> Result<Item, String> {
+    restore(&item).map(|_| item)
+}
+
+pub fn restore(item: &Item) -> Result<(), String> {
+    if item.original.exists() {
+        return Err(format!("{:?} already exists", item.original));
+    }
+
+    file_ops::relocate(&item.trashed, &item.original)
+        .map_err(|error| format!("Cannot restore {:?}: {}", item.original, error))?;
+
+    let _ = fs::remove_file(&item.info);
+
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn home_directory() -> Option<PathBuf> {
+    dirs_next::data_dir().map(|data| data.join("Trash"))
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn home_directory() -> Option<PathBuf> {
+    dirs_next::data_dir().map(|data| data.join("navigation_tree").join("Trash"))
+}
+
+#[cfg(target_os = "linux")]
+fn directory_for(original: &Path) -> Option<PathBuf> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    let home = home_directory()?;
+    let device = |path: &Path| {
+        path.ancestors()
+            .find_map(|ancestor| fs::metadata(ancestor).ok())
+            .map(|metadata| metadata.dev())
+    };
+
+    let parent = original.parent()?;
+    let original_device = device(parent);
+
+    if original_device == device(&home) {
+        return Some(home);
+    }
+
+    let top = parent
+        .ancestors()
+        .take_while(|ancestor| device(ancestor) == original_device)
+        .last()?;
+
+    let directory = top.join(top_directory_name());
+
+    let created = fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(&directory)
+        .is_ok()
+        && fs::symlink_metadata(&directory)
+            .is_ok_and(|metadata| metadata.is_dir() && metadata.uid() == unsafe { libc::getuid() });
+
+    Some(if created { directory } else { home })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn directory_for(_original: &Path) -> Option<PathBuf> {
+    home_directory()
+}
+
+#[cfg(target_os = "linux")]
+fn top_directory_name() -> String {
+    format!(".Trash-{}", unsafe { libc::getuid() })
+}
+
+#[cfg(target_os = "linux")]
+fn top_directories() -> Vec<PathBuf> {
+    let name = top_directory_name();
+
+    let mut directories: Vec<_> = crate::volumes::mount_points()
+        .into_iter()
+        .map(|root| root.join(&name))
+        .filter(|directory| directory.is_dir())
+        .collect();
+
+    directories.sort();
+    directories.dedup();
+
+    directories
+}
+
+#[cfg(not(target_os = "linux"))]
+fn top_directories() -> Vec<PathBuf> {
+    vec![]
+}
+
+fn top_directory(directory: &Path) -> Option<&Path> {
+    let name = directory.file_name()?.to_string_lossy();
+
+    if name.starts_with(".Trash-") {
+        directory.parent()
+    } else {
+        None
+    }
+}
+
+fn create_info_file(info: &Path, name: &OsStr) -> io::Result<(PathBuf, fs::File)> {
+    for attempt in 0.. {
+        let mut file_name = OsString::from(name);
+
+        if attempt > 0 {
+            file_name.push(format!(".{}", attempt));
+        }
+
+        file_name.push(".trashinfo");
+
+        let path = info.join(file_name);
+
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    unreachable!()
+}
+
+fn encode_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+
+    percent_encode(path.as_bytes(), PATH_ENCODE_SET).to_string()
+}
+
+fn decode_path(path: &str) -> PathBuf {
+    PathBuf::from(percent_decode_str(path).decode_utf8_lossy().into_owned())
+}
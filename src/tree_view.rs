@@ -0,0 +1,544 @@
+use iced_graphics::{defaults, Backend, Defaults, Primitive, Renderer};
+use iced_native::{
+    event, layout, mouse, Background, Clipboard, Color, Element, Event, Hasher, Layout, Length,
+    Point, Rectangle, Size, Vector, Widget,
+};
+
+use crate::style;
+
+use std::cell::Cell;
+use std::hash::Hash;
+use std::ops::Range;
+
+pub const ROW_HEIGHT: u16 = 30;
+const FIRST_PAGE: usize = 100;
+
+#[derive(Debug, Default)]
+pub struct State {
+    pressed: Option<usize>,
+    dragged_to: Option<usize>,
+    visible: Cell<Option<(f32, f32)>>,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn visible(&self) -> Option<(f32, f32)> {
+        self.visible.get()
+    }
+
+    pub fn window(&self, total: usize) -> Range<usize> {
+        let (top, height) = match self.visible.get() {
+            Some(visible) => visible,
+            None => return 0..total.min(FIRST_PAGE),
+        };
+
+        let row_height = f32::from(ROW_HEIGHT);
+        let page = (height / row_height).ceil() as usize + 1;
+        let top = (top.max(0.0) / row_height) as usize;
+
+        let start = top.saturating_sub(page).min(total);
+
+        start..(top + page * 2).min(total)
+    }
+
+    pub fn remap(&mut self, index: impl Fn(usize) -> Option<usize>) {
+        self.pressed = self.pressed.and_then(&index);
+        self.dragged_to = self.dragged_to.and_then(&index);
+
+        if self.pressed.is_none() {
+            self.dragged_to = None;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Guide {
+    Through,
+    Branch { arm: u16, last: bool },
+}
+
+pub struct Item<'a, Message, B: Backend> {
+    content: Element<'a, Message, Renderer<B>>,
+    on_open: Message,
+    on_select: Option<Message>,
+    on_middle_click: Option<Message>,
+    toggle: Option<(u16, u16, Message)>,
+    guides: Vec<(u16, Guide)>,
+    is_selected: bool,
+    flash: f32,
+}
+
+impl<'a, Message, B: Backend> Item<'a, Message, B> {
+    pub fn new(content: impl Into<Element<'a, Message, Renderer<B>>>, on_open: Message) -> Self {
+        Self {
+            content: content.into(),
+            on_open,
+            on_select: None,
+            on_middle_click: None,
+            toggle: None,
+            guides: vec![],
+            is_selected: false,
+            flash: 0.0,
+        }
+    }
+
+    pub fn on_select(mut self, message: Message) -> Self {
+        self.on_select = Some(message);
+        self
+    }
+
+    pub fn on_middle_click(mut self, message: Message) -> Self {
+        self.on_middle_click = Some(message);
+        self
+    }
+
+    pub fn toggle(mut self, offset: u16, width: u16, message: Message) -> Self {
+        self.toggle = Some((offset, width, message));
+        self
+    }
+
+    pub fn guide(mut self, x: u16, guide: Guide) -> Self {
+        self.guides.push((x, guide));
+        self
+    }
+
+    pub fn selected(mut self, is_selected: bool) -> Self {
+        self.is_selected = is_selected;
+        self
+    }
+
+    pub fn flash(mut self, intensity: f32) -> Self {
+        self.flash = intensity.clamp(0.0, 1.0);
+        self
+    }
+}
+
+pub struct TreeView<'a, Message, B: Backend> {
+    state: &'a mut State,
+    items: Vec<Item<'a, Message, B>>,
+    first: usize,
+    total: usize,
+    reveal: Option<(usize, usize, f32)>,
+    on_scroll: Option<Message>,
+}
+
+impl<'a, Message, B: Backend> TreeView<'a, Message, B> {
+    pub fn new(state: &'a mut State, items: Vec<Item<'a, Message, B>>) -> Self {
+        let total = items.len();
+
+        Self {
+            state,
+            items,
+            first: 0,
+            total,
+            reveal: None,
+            on_scroll: None,
+        }
+    }
+
+    pub fn window(mut self, first: usize, total: usize) -> Self {
+        self.first = first;
+        self.total = total.max(first + self.items.len());
+        self
+    }
+
+    pub fn on_scroll(mut self, message: Message) -> Self {
+        self.on_scroll = Some(message);
+        self
+    }
+
+    pub fn reveal(mut self, start: usize, count: usize, fraction: f32) -> Self {
+        self.reveal = Some((start, count, fraction.clamp(0.0, 1.0)));
+        self
+    }
+
+    fn hidden(&self) -> (usize, f32) {
+        match self.reveal {
+            Some((start, count, fraction)) => (
+                start,
+                count as f32 * f32::from(ROW_HEIGHT) * (1.0 - fraction),
+            ),
+            None => (self.total, 0.0),
+        }
+    }
+
+    fn row_top(&self, index: usize) -> f32 {
+        let (start, hidden) = self.hidden();
+        let top = index as f32 * f32::from(ROW_HEIGHT);
+
+        if index >= start {
+            top - hidden
+        } else {
+            top
+        }
+    }
+
+    fn row_at(&self, y: f32) -> usize {
+        let (start, hidden) = self.hidden();
+        let row_height = f32::from(ROW_HEIGHT);
+
+        if y < start as f32 * row_height {
+            (y / row_height) as usize
+        } else {
+            ((y + hidden) / row_height) as usize
+        }
+    }
+
+    fn is_revealing(&self, index: usize) -> bool {
+        self.reveal
+            .is_some_and(|(start, count, _)| index >= start && index < start + count)
+    }
+
+    fn index_at(&self, bounds: Rectangle, position: Point) -> Option<usize> {
+        if !bounds.contains(position) {
+            return None;
+        }
+
+        let index = self.row_at(position.y - bounds.y);
+
+        (index < self.total).then_some(index)
+    }
+
+    fn item(&self, index: usize) -> Option<&Item<'a, Message, B>> {
+        self.items.get(index.checked_sub(self.first)?)
+    }
+
+    fn needs_rows(&self) -> bool {
+        let (top, height) = match self.state.visible.get() {
+            Some(visible) => visible,
+            None => return false,
+        };
+
+        let start = self.row_at(top.max(0.0));
+        let end = (self.row_at((top + height).max(0.0)) + 1).min(self.total);
+
+        start < end && (start < self.first || end > self.first + self.items.len())
+    }
+}
+
+impl<'a, Message, B> Widget<Message, Renderer<B>> for TreeView<'a, Message, B>
+where
+    Message: Clone,
+    B: Backend,
+{
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn layout(&self, renderer: &Renderer<B>, limits: &layout::Limits) -> layout::Node {
+        let row_height = f32::from(ROW_HEIGHT);
+        let row_limits = layout::Limits::new(Size::ZERO, Size::new(f32::INFINITY, row_height));
+
+        let mut width: f32 = 0.0;
+
+        let children: Vec<_> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let mut node = item.content.layout(renderer, &row_limits);
+                let size = node.size();
+
+                width = width.max(size.width);
+                node.move_to(Point::new(
+                    0.0,
+                    self.row_top(self.first + index) + (row_height - size.height).max(0.0) / 2.0,
+                ));
+
+                node
+            })
+            .collect();
+
+        let size = limits
+            .width(Length::Fill)
+            .resolve(Size::new(width, self.row_top(self.total)));
+
+        layout::Node::with_children(size, children)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer<B>,
+        defaults: &Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) -> (Primitive, mouse::Interaction) {
+        let bounds = layout.bounds();
+        let row_height = f32::from(ROW_HEIGHT);
+
+        self.state
+            .visible
+            .set(Some((viewport.y - bounds.y, viewport.height)));
+
+        let hovered = self
+            .index_at(bounds, cursor_position)
+            .filter(|_| viewport.contains(cursor_position));
+
+        let selected_defaults = Defaults {
+            text: defaults::Text {
+                color: Color::WHITE,
+            },
+        };
+
+        let first = self
+            .row_at((viewport.y - bounds.y).max(0.0))
+            .max(self.first);
+        let last = self.row_at((viewport.y + viewport.height - bounds.y).max(0.0));
+
+        let mut mouse_interaction = if hovered.is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        };
+
+        let mut primitives = vec![];
+
+        for (index, (item, layout)) in self
+            .items
+            .iter()
+            .zip(layout.children())
+            .enumerate()
+            .map(|(index, item)| (self.first + index, item))
+            .skip(first - self.first)
+            .take((last + 1).saturating_sub(first))
+        {
+            let top = bounds.y + self.row_top(index);
+            let mut row = vec![];
+
+            let background = if item.is_selected {
+                Some(style::SELECTION)
+            } else if hovered == Some(index) {
+                Some(style::HOVER)
+            } else {
+                None
+            };
+
+            let flash = (item.flash > 0.0).then_some(Color {
+                a: item.flash * 0.8,
+                ..style::FLASH
+            });
+
+            for background in background.into_iter().chain(flash) {
+                row.push(Primitive::Quad {
+                    bounds: Rectangle {
+                        y: top,
+                        height: row_height,
+                        ..bounds
+                    },
+                    background: Background::Color(background),
+                    border_radius: 2.0,
+                    border_width: 0.0,
+                    border_color: Color::TRANSPARENT,
+                });
+            }
+
+            for (x, guide) in item.guides.iter() {
+                let x = bounds.x + f32::from(*x);
+
+                let (height, arm) = match guide {
+                    Guide::Through => (row_height, None),
+                    Guide::Branch { arm, last: true } => (row_height / 2.0, Some(*arm)),
+                    Guide::Branch { arm, last: false } => (row_height, Some(*arm)),
+                };
+
+                row.push(line(Rectangle {
+                    x,
+                    y: top,
+                    width: 1.0,
+                    height,
+                }));
+
+                if let Some(arm) = arm {
+                    row.push(line(Rectangle {
+                        x,
+                        y: top + row_height / 2.0,
+                        width: f32::from(arm),
+                        height: 1.0,
+                    }));
+                }
+            }
+
+            let defaults = if item.is_selected {
+                &selected_defaults
+            } else {
+                defaults
+            };
+
+            let (primitive, interaction) =
+                item.content
+                    .draw(renderer, defaults, layout, cursor_position, viewport);
+
+            mouse_interaction = mouse_interaction.max(interaction);
+            row.push(primitive);
+
+            let row = Primitive::Group { primitives: row };
+
+            match self.reveal.filter(|_| self.is_revealing(index)) {
+                Some((start, count, fraction)) => {
+                    let y = bounds.y + start as f32 * row_height;
+
+                    primitives.push(Primitive::Clip {
+                        bounds: Rectangle {
+                            y,
+                            height: count as f32 * row_height * fraction,
+                            ..bounds
+                        },
+                        offset: Vector::new(0, 0),
+                        content: Box::new(row),
+                    });
+                }
+                None => primitives.push(row),
+            }
+        }
+
+        (Primitive::Group { primitives }, mouse_interaction)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+
+        self.first.hash(state);
+        self.total.hash(state);
+        self.reveal
+            .map(|(start, count, fraction)| (start, count, fraction.to_bits()))
+            .hash(state);
+
+        for item in self.items.iter() {
+            item.content.hash_layout(state);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer<B>,
+        clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        let bounds = layout.bounds();
+        let index = self.index_at(bounds, cursor_position);
+
+        if let Some(message) = self.on_scroll.clone().filter(|_| self.needs_rows()) {
+            messages.push(message);
+        }
+
+        if let Some(position) = index.and_then(|index| index.checked_sub(self.first)) {
+            if let (Some(item), Some(child)) = (
+                self.items.get_mut(position),
+                layout.children().nth(position),
+            ) {
+                let status = item.content.on_event(
+                    event.clone(),
+                    child,
+                    cursor_position,
+                    renderer,
+                    clipboard,
+                    messages,
+                );
+
+                if status == event::Status::Captured {
+                    return status;
+                }
+            }
+        }
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let (index, item) = match index.and_then(|index| Some((index, self.item(index)?))) {
+                    Some(found) => found,
+                    None => return event::Status::Ignored,
+                };
+
+                let x = cursor_position.x - bounds.x;
+
+                if let Some((offset, width, message)) = &item.toggle {
+                    let start = f32::from(*offset);
+
+                    if x >= start && x < start + f32::from(*width) {
+                        messages.push(message.clone());
+
+                        return event::Status::Captured;
+                    }
+                }
+
+                messages.extend(item.on_select.clone());
+
+                self.state.pressed = Some(index);
+                self.state.dragged_to = None;
+
+                event::Status::Captured
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) => {
+                match index.and_then(|index| self.item(index)?.on_middle_click.clone()) {
+                    Some(message) => {
+                        messages.push(message);
+
+                        event::Status::Captured
+                    }
+                    None => event::Status::Ignored,
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let pressed = match self.state.pressed {
+                    Some(pressed) => pressed,
+                    None => return event::Status::Ignored,
+                };
+
+                match index {
+                    Some(index) if Some(index) != self.state.dragged_to.or(Some(pressed)) => {
+                        self.state.dragged_to = Some(index);
+
+                        messages.extend(self.item(index).and_then(|item| item.on_select.clone()));
+
+                        event::Status::Captured
+                    }
+                    _ => event::Status::Ignored,
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                let pressed = match self.state.pressed.take() {
+                    Some(pressed) => pressed,
+                    None => return event::Status::Ignored,
+                };
+
+                if self.state.dragged_to.take().is_none() && index == Some(pressed) {
+                    messages.extend(self.item(pressed).map(|item| item.on_open.clone()));
+                }
+
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+}
+
+impl<'a, Message, B> From<TreeView<'a, Message, B>> for Element<'a, Message, Renderer<B>>
+where
+    Message: 'a + Clone,
+    B: 'a + Backend,
+{
+    fn from(tree_view: TreeView<'a, Message, B>) -> Self {
+        Element::new(tree_view)
+    }
+}
+
+fn line(bounds: Rectangle) -> Primitive {
+    Primitive::Quad {
+        bounds,
+        background: Background::Color(style::INDENT_GUIDE),
+        border_radius: 0.0,
+        border_width: 0.0,
+        border_color: Color::TRANSPARENT,
+    }
+}
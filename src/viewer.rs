@@ -0,0 +1,1383 @@
+use iced::button::{self, Button};
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{BoxStream, StreamExt};
+use iced::scrollable::{self, Scrollable};
+use iced::{
+    Align, Checkbox, Color, Column, Command, Element, Font, Length, Row, Subscription, Text,
+};
+use iced_native::subscription::Recipe;
+use iced_native::{
+    event, layout, mouse, overlay, Clipboard, Event, Hasher, Layout, Point, Rectangle, Widget,
+};
+
+use crate::font;
+use crate::format;
+use crate::git::BlameLine;
+use crate::i18n;
+use crate::launch;
+use crate::plugin;
+use crate::probe::{self, Probe};
+use crate::style;
+
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::hash::Hash;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+const SNIFF_LENGTH: usize = 8192;
+const HEX_ROW_LENGTH: usize = 16;
+const MAX_HEX_BYTES: usize = 64 * 1024;
+const HEADER_HEIGHT: f32 = 40.0;
+const LINE_HEIGHT: f32 = 24.0;
+const CONTEXT_LINES: usize = 5;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ShowAsText,
+    ShowAsHex,
+    LoadAnyway,
+    OpenHex,
+    OpenExternally,
+    CopyContents,
+    Loaded(Result<Vec<u8>, String>),
+    PrefixLoaded(Result<(Vec<u8>, u64), String>),
+    Measured(u64),
+    ToggleFollow,
+    Appended(String),
+    Truncated,
+    Scrolled { up: bool },
+    LevelToggled(Level, bool),
+    ToggleEditing,
+    ToggleOverwrite,
+    Edit(Edit),
+    SaveBytes,
+    BytesSaved(Result<(), String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    Digit(u8),
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    Backspace,
+    ToggleOverwrite,
+    Stop,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Editing {
+    cursor: usize,
+    low_nibble: bool,
+    overwrite: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    const ALL: [Level; 4] = [Level::Error, Level::Warn, Level::Info, Level::Debug];
+
+    fn detect(line: &str) -> Option<Level> {
+        line.split(|c: char| !c.is_ascii_alphabetic())
+            .take(8)
+            .find_map(|word| match word.to_ascii_uppercase().as_str() {
+                "ERROR" | "ERR" | "FATAL" | "CRITICAL" => Some(Level::Error),
+                "WARN" | "WARNING" => Some(Level::Warn),
+                "INFO" | "NOTICE" => Some(Level::Info),
+                "DEBUG" | "TRACE" => Some(Level::Debug),
+                _ => None,
+            })
+    }
+
+    fn color(self) -> Option<Color> {
+        match self {
+            Level::Error => Some(style::ERROR),
+            Level::Warn => Some(style::LOG_WARN),
+            Level::Info => None,
+            Level::Debug => Some(style::GUTTER),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Level::Error => "Error",
+            Level::Warn => "Warning",
+            Level::Info => "Info",
+            Level::Debug => "Debug",
+        };
+
+        f.write_str(i18n::tr(label))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Content {
+    Text(String),
+    Hex { bytes: Vec<u8>, total: u64 },
+    Diff(String),
+    TooLarge(u64),
+    Loading,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub font: Font,
+    pub tab_width: usize,
+    pub show_invisibles: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub path: PathBuf,
+    pub content: Content,
+    pub blame: Option<Vec<BlameLine>>,
+    pub highlight: Option<usize>,
+    error: Option<String>,
+    size: u64,
+    following: bool,
+    auto_scroll: bool,
+    copied: bool,
+    hidden_levels: HashSet<Level>,
+    editing: Option<Editing>,
+    dirty: bool,
+    raw: Option<(Vec<u8>, u64)>,
+    read_only: bool,
+    mode_button: button::State,
+    follow_button: button::State,
+    copy_button: button::State,
+    load_button: button::State,
+    external_button: button::State,
+    edit_button: button::State,
+    overwrite_button: button::State,
+    save_button: button::State,
+}
+
+impl Document {
+    pub fn file(path: PathBuf, bytes: Vec<u8>) -> Self {
+        let size = bytes.len() as u64;
+
+        let (content, raw) = classify(&path, bytes);
+
+        Self {
+            size,
+            raw,
+            ..Self::new(path, content)
+        }
+    }
+
+    pub fn diff(path: PathBuf, diff: String) -> Self {
+        Self::new(path, Content::Diff(diff))
+    }
+
+    pub fn too_large(path: PathBuf, size: u64) -> Self {
+        Self::new(path, Content::TooLarge(size))
+    }
+
+    fn new(path: PathBuf, content: Content) -> Self {
+        Self {
+            path,
+            content,
+            blame: None,
+            highlight: None,
+            error: None,
+            size: 0,
+            following: false,
+            auto_scroll: false,
+            copied: false,
+            hidden_levels: HashSet::new(),
+            editing: None,
+            dirty: false,
+            raw: None,
+            read_only: false,
+            mode_button: button::State::new(),
+            follow_button: button::State::new(),
+            copy_button: button::State::new(),
+            load_button: button::State::new(),
+            external_button: button::State::new(),
+            edit_button: button::State::new(),
+            overwrite_button: button::State::new(),
+            save_button: button::State::new(),
+        }
+    }
+
+    fn estimated_height(&self) -> f32 {
+        let lines = match &self.content {
+            Content::Text(text) | Content::Diff(text) => text.lines().count(),
+            Content::Hex { bytes, .. } => bytes.len().min(MAX_HEX_BYTES) / HEX_ROW_LENGTH + 1,
+            Content::TooLarge(_) | Content::Loading => 0,
+        };
+
+        HEADER_HEIGHT + lines as f32 * LINE_HEIGHT
+    }
+
+    pub fn highlight_offset(&self) -> Option<f32> {
+        let line = self.highlight?;
+
+        Some(HEADER_HEIGHT + line.saturating_sub(1 + CONTEXT_LINES) as f32 * LINE_HEIGHT)
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        match &self.content {
+            Content::Text(text) | Content::Diff(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    pub fn is_auto_scrolling(&self) -> bool {
+        self.following && self.auto_scroll
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        if !self.following {
+            return Subscription::none();
+        }
+
+        Subscription::from_recipe(Follow {
+            path: self.path.clone(),
+            offset: self.size,
+        })
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        let content = mem::replace(&mut self.content, Content::Loading);
+
+        self.content = match (message, content) {
+            (Message::ShowAsText, Content::Hex { bytes, total }) => {
+                let (text, raw) = decoded(bytes, total);
+                self.raw = raw;
+
+                Content::Text(text)
+            }
+            (Message::ShowAsHex, Content::Text(text)) => match self.raw.take() {
+                Some((bytes, total)) => Content::Hex { bytes, total },
+                None => {
+                    let total = text.len() as u64;
+
+                    Content::Hex {
+                        bytes: text.into_bytes(),
+                        total,
+                    }
+                }
+            },
+            (Message::LoadAnyway, Content::TooLarge(_)) => {
+                let path = self.path.clone();
+
+                return Command::perform(
+                    async move { fs::read(path).map_err(|error| error.to_string()) },
+                    Message::Loaded,
+                );
+            }
+            (Message::OpenHex, Content::TooLarge(_)) => {
+                return Command::perform(read_prefix(self.path.clone()), Message::PrefixLoaded);
+            }
+            (Message::OpenExternally, content) => {
+                self.error = launch::open_external(&self.path).err();
+
+                content
+            }
+            (Message::CopyContents, content) => {
+                self.copied = true;
+
+                content
+            }
+            (Message::Loaded(Ok(bytes)), Content::Loading) => {
+                self.size = bytes.len() as u64;
+                self.read_only = false;
+
+                let (content, raw) = classify(&self.path, bytes);
+                self.raw = raw;
+
+                content
+            }
+            (Message::ToggleFollow, content @ Content::Text(_)) => {
+                self.following = !self.following;
+                self.auto_scroll = self.following;
+
+                content
+            }
+            (Message::Appended(appended), Content::Text(mut text)) if self.following => {
+                self.size += appended.len() as u64;
+                self.raw = None;
+                self.read_only = true;
+                text.push_str(&appended);
+
+                Content::Text(text)
+            }
+            (Message::Truncated, Content::Text(_)) if self.following => {
+                self.size = 0;
+                self.blame = None;
+                self.raw = None;
+                self.read_only = true;
+
+                Content::Text(String::new())
+            }
+            (Message::LevelToggled(level, shown), content) => {
+                if shown {
+                    self.hidden_levels.remove(&level);
+                } else {
+                    self.hidden_levels.insert(level);
+                }
+
+                content
+            }
+            (Message::Scrolled { up }, content) => {
+                if self.following {
+                    self.auto_scroll = !up;
+                }
+
+                content
+            }
+            (Message::PrefixLoaded(Ok((bytes, total))), Content::Loading) => {
+                Content::Hex { bytes, total }
+            }
+            (Message::ToggleEditing, Content::Hex { bytes, total }) => {
+                self.editing = match self.editing {
+                    Some(_) => None,
+                    None if bytes.is_empty() || self.read_only => None,
+                    None => Some(Editing {
+                        cursor: 0,
+                        low_nibble: false,
+                        overwrite: true,
+                    }),
+                };
+
+                Content::Hex { bytes, total }
+            }
+            (Message::ToggleOverwrite, Content::Hex { bytes, total }) => {
+                self.edit(Edit::ToggleOverwrite, bytes, total)
+            }
+            (Message::Edit(edit), Content::Hex { bytes, total }) => self.edit(edit, bytes, total),
+            (Message::SaveBytes, Content::Hex { bytes, total }) if self.dirty => {
+                let whole = bytes.len() as u64 == total;
+                let command = Command::perform(
+                    write_bytes(self.path.clone(), bytes.clone(), whole),
+                    Message::BytesSaved,
+                );
+
+                self.content = Content::Hex { bytes, total };
+
+                return command;
+            }
+            (Message::BytesSaved(result), content) => {
+                match result {
+                    Ok(()) => {
+                        self.dirty = false;
+                        self.error = None;
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+
+                content
+            }
+            (Message::Loaded(Err(error)), Content::Loading)
+            | (Message::PrefixLoaded(Err(error)), Content::Loading) => {
+                self.error = Some(error);
+
+                return Command::perform(measure(self.path.clone()), Message::Measured);
+            }
+            (Message::Measured(size), Content::Loading) => Content::TooLarge(size),
+            (_, content) => content,
+        };
+
+        Command::none()
+    }
+
+    fn edit(&mut self, edit: Edit, mut bytes: Vec<u8>, mut total: u64) -> Content {
+        let whole = bytes.len() as u64 == total;
+
+        let editing = match self.editing.as_mut() {
+            Some(editing) => editing,
+            None => return Content::Hex { bytes, total },
+        };
+
+        let last = bytes.len().min(MAX_HEX_BYTES).saturating_sub(1);
+        let moved = |cursor: usize| cursor.min(last);
+
+        match edit {
+            Edit::Digit(digit) if editing.cursor < bytes.len() => {
+                let byte = &mut bytes[editing.cursor];
+
+                if editing.low_nibble {
+                    *byte = (*byte & 0xf0) | digit;
+                    editing.cursor = moved(editing.cursor + 1);
+                    editing.low_nibble = false;
+                } else if editing.overwrite {
+                    *byte = (*byte & 0x0f) | digit << 4;
+                    editing.low_nibble = true;
+                } else {
+                    bytes.insert(editing.cursor, digit << 4);
+                    editing.low_nibble = true;
+                }
+
+                self.dirty = true;
+            }
+            Edit::Digit(_) => {}
+            Edit::Left => editing.cursor = editing.cursor.saturating_sub(1),
+            Edit::Right => editing.cursor = moved(editing.cursor + 1),
+            Edit::Up => editing.cursor = editing.cursor.saturating_sub(HEX_ROW_LENGTH),
+            Edit::Down => editing.cursor = moved(editing.cursor + HEX_ROW_LENGTH),
+            Edit::Home => editing.cursor -= editing.cursor % HEX_ROW_LENGTH,
+            Edit::End => {
+                editing.cursor =
+                    moved(editing.cursor - editing.cursor % HEX_ROW_LENGTH + HEX_ROW_LENGTH - 1)
+            }
+            Edit::Backspace if !editing.overwrite && editing.cursor > 0 => {
+                editing.cursor -= 1;
+                bytes.remove(editing.cursor);
+
+                self.dirty = true;
+            }
+            Edit::Backspace => editing.cursor = editing.cursor.saturating_sub(1),
+            Edit::ToggleOverwrite => editing.overwrite = !editing.overwrite || !whole,
+            Edit::Stop => self.editing = None,
+        }
+
+        if let Some(editing) = self.editing.as_mut() {
+            if !matches!(edit, Edit::Digit(_)) {
+                editing.low_nibble = false;
+            }
+
+            editing.cursor = editing.cursor.min(bytes.len().saturating_sub(1));
+        }
+
+        if whole {
+            total = bytes.len() as u64;
+            self.size = total;
+        }
+
+        if bytes.is_empty() {
+            self.editing = None;
+        }
+
+        Content::Hex { bytes, total }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Extent {
+    bounds: Rectangle,
+    content_bounds: Rectangle,
+}
+
+impl Extent {
+    pub fn scroll_by(&self, scrollable: &mut scrollable::State, delta_y: f32) {
+        scrollable.scroll(delta_y, self.bounds, self.content_bounds);
+    }
+
+    pub fn scroll_to_bottom(&self, scrollable: &mut scrollable::State, document: &Document) {
+        let content_bounds = Rectangle {
+            height: self.content_bounds.height.max(document.estimated_height()),
+            ..self.content_bounds
+        };
+
+        scrollable.scroll_to(1.0, self.bounds, content_bounds);
+    }
+
+    pub fn scroll_to_highlight(&self, scrollable: &mut scrollable::State, document: &Document) {
+        if let Some(offset) = document.highlight_offset() {
+            let content_bounds = Rectangle {
+                height: document.estimated_height(),
+                ..self.bounds
+            };
+
+            *scrollable = scrollable::State::new();
+            scrollable.scroll(-offset, self.bounds, content_bounds);
+        }
+    }
+}
+
+pub fn measured<'a, Message: 'a>(
+    extent: &'a Cell<Extent>,
+    scrollable: Scrollable<'a, Message>,
+) -> Element<'a, Message> {
+    Measured {
+        extent,
+        content: scrollable.into(),
+    }
+    .into()
+}
+
+pub fn view(document: &mut Document, options: Options) -> Element<'_, Message> {
+    WheelWatch {
+        content: document_view(document, options),
+    }
+    .into()
+}
+
+fn document_view(document: &mut Document, options: Options) -> Element<'_, Message> {
+    let mut column = Column::new().spacing(10).width(Length::Fill);
+
+    if let Some(error) = &document.error {
+        column = column.push(Text::new(error.as_str()).color(style::ERROR));
+    }
+
+    match &document.content {
+        Content::Text(text) => {
+            let toggle = Button::new(
+                &mut document.mode_button,
+                Text::new(i18n::tr("Show as hex")).size(14),
+            )
+            .padding(3)
+            .on_press(Message::ShowAsHex);
+
+            let follow_label = if document.following {
+                "Stop following"
+            } else {
+                "Follow"
+            };
+
+            let follow = Button::new(
+                &mut document.follow_button,
+                Text::new(i18n::tr(follow_label)).size(14),
+            )
+            .padding(3)
+            .on_press(Message::ToggleFollow);
+
+            let mut header = Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(Text::new(i18n::tr_with(
+                    "File: {}",
+                    &[&format!("{:?}", document.path)],
+                )))
+                .push(toggle)
+                .push(follow)
+                .push(copy_button(&mut document.copy_button, document.copied));
+
+            if document.following && !document.auto_scroll {
+                header = header.push(
+                    Text::new(i18n::tr("Auto-scroll paused, scroll down to resume"))
+                        .size(14)
+                        .color(style::GUTTER),
+                );
+            }
+
+            let column = column.push(header);
+
+            match &document.blame {
+                Some(blame) => column.push(blamed_lines(text, blame, options)).into(),
+                None if is_log(&document.path) => {
+                    let hidden_levels = &document.hidden_levels;
+
+                    let filters = Level::ALL
+                        .iter()
+                        .fold(Row::new().spacing(15), |row, level| {
+                            let level = *level;
+
+                            row.push(
+                                Checkbox::new(
+                                    !hidden_levels.contains(&level),
+                                    level.to_string(),
+                                    move |shown| Message::LevelToggled(level, shown),
+                                )
+                                .size(16)
+                                .text_size(16),
+                            )
+                        });
+
+                    column
+                        .push(filters)
+                        .push(log_lines(text, hidden_levels, options))
+                        .into()
+                }
+                None => {
+                    let text: Vec<_> = text.lines().map(|line| render(line, options)).collect();
+
+                    match document
+                        .highlight
+                        .filter(|line| (1..=text.len()).contains(line))
+                    {
+                        Some(line) => {
+                            let before = &text[..line - 1];
+                            let after = &text[line..];
+
+                            let mut lines = Column::new();
+
+                            if !before.is_empty() {
+                                lines = lines.push(Text::new(before.join("\n")).font(options.font));
+                            }
+
+                            lines = lines.push(
+                                Text::new(text[line - 1].as_str())
+                                    .font(options.font)
+                                    .color(style::SEARCH_MATCH),
+                            );
+
+                            if !after.is_empty() {
+                                lines = lines.push(Text::new(after.join("\n")).font(options.font));
+                            }
+
+                            column.push(lines).into()
+                        }
+                        None => column
+                            .push(Text::new(text.join("\n")).font(options.font))
+                            .into(),
+                    }
+                }
+            }
+        }
+        Content::Hex { bytes, total } => {
+            let mut toggle = Button::new(
+                &mut document.mode_button,
+                Text::new(i18n::tr("Show as text")).size(14),
+            )
+            .padding(3);
+
+            if !document.dirty {
+                toggle = toggle.on_press(Message::ShowAsText);
+            }
+
+            let edit_label = if document.editing.is_some() {
+                "Stop editing"
+            } else {
+                "Edit"
+            };
+
+            let mut edit = Button::new(
+                &mut document.edit_button,
+                Text::new(i18n::tr(edit_label)).size(14),
+            )
+            .padding(3);
+
+            if !document.read_only {
+                edit = edit.on_press(Message::ToggleEditing);
+            }
+
+            let mut header = Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(Text::new(i18n::tr_with(
+                    "File: {} (binary, {})",
+                    &[&format!("{:?}", document.path), &format::size(*total)],
+                )))
+                .push(toggle)
+                .push(edit);
+
+            if let Some(editing) = &document.editing {
+                let mode = if editing.overwrite {
+                    "Overwrite"
+                } else {
+                    "Insert"
+                };
+
+                header = header.push(
+                    Button::new(
+                        &mut document.overwrite_button,
+                        Text::new(i18n::tr(mode)).size(14),
+                    )
+                    .padding(3)
+                    .on_press(Message::ToggleOverwrite),
+                );
+            }
+
+            if document.dirty {
+                header = header
+                    .push(
+                        Button::new(
+                            &mut document.save_button,
+                            Text::new(i18n::tr("Save")).size(14),
+                        )
+                        .padding(3)
+                        .on_press(Message::SaveBytes),
+                    )
+                    .push(
+                        Text::new(i18n::tr("Unsaved changes"))
+                            .size(14)
+                            .color(style::LOG_WARN),
+                    );
+            }
+
+            let mut column = column.push(header);
+
+            if bytes.len() > MAX_HEX_BYTES || (bytes.len() as u64) < *total {
+                column = column.push(
+                    Text::new(i18n::tr_with(
+                        "Showing the first {}",
+                        &[&format::size(bytes.len().min(MAX_HEX_BYTES) as u64)],
+                    ))
+                    .size(14),
+                );
+            }
+
+            let shown = &bytes[..bytes.len().min(MAX_HEX_BYTES)];
+
+            match &document.editing {
+                Some(editing) => column.push(edited_dump(shown, editing.cursor)).into(),
+                None => column
+                    .push(Text::new(hex_dump(shown)).font(font::MONOSPACE))
+                    .into(),
+            }
+        }
+        Content::TooLarge(size) => {
+            let load = Button::new(
+                &mut document.load_button,
+                Text::new(i18n::tr("Load anyway")),
+            )
+            .on_press(Message::LoadAnyway);
+            let hex = Button::new(
+                &mut document.mode_button,
+                Text::new(i18n::tr("Open hex view")),
+            )
+            .on_press(Message::OpenHex);
+            let external = Button::new(
+                &mut document.external_button,
+                Text::new(i18n::tr("Open externally")),
+            )
+            .on_press(Message::OpenExternally);
+
+            column
+                .push(Text::new(i18n::tr_with(
+                    "File: {}",
+                    &[&format!("{:?}", document.path)],
+                )))
+                .push(Text::new(i18n::tr_with(
+                    "This file is {}, loading it may freeze the viewer",
+                    &[&format::size(*size)],
+                )))
+                .push(Row::new().spacing(10).push(load).push(hex).push(external))
+                .into()
+        }
+        Content::Loading => column
+            .push(Text::new(i18n::tr_with(
+                "Loading {}...",
+                &[&format!("{:?}", document.path)],
+            )))
+            .into(),
+        Content::Diff(diff) => {
+            let lines = diff.lines().fold(Column::new(), |lines, line| {
+                let text = Text::new(render(line, options)).font(font::MONOSPACE);
+
+                match diff_color(line) {
+                    Some(color) => lines.push(text.color(color)),
+                    None => lines.push(text),
+                }
+            });
+
+            column
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(Align::Center)
+                        .push(Text::new(i18n::tr_with(
+                            "Diff against HEAD: {}",
+                            &[&format!("{:?}", document.path)],
+                        )))
+                        .push(copy_button(&mut document.copy_button, document.copied)),
+                )
+                .push(lines)
+                .into()
+        }
+    }
+}
+
+fn copy_button(state: &mut button::State, copied: bool) -> Button<'_, Message> {
+    let label = if copied { "Copied" } else { "Copy" };
+
+    Button::new(state, Text::new(i18n::tr(label)).size(14))
+        .padding(3)
+        .on_press(Message::CopyContents)
+}
+
+fn classify(path: &Path, bytes: Vec<u8>) -> (Content, Option<(Vec<u8>, u64)>) {
+    let total = bytes.len() as u64;
+
+    if let Some(text) = plugin::preview(path, &bytes) {
+        (Content::Text(text), Some((bytes, total)))
+    } else if looks_binary(&bytes) {
+        (Content::Hex { bytes, total }, None)
+    } else {
+        let (text, raw) = decoded(bytes, total);
+
+        (Content::Text(text), raw)
+    }
+}
+
+fn decoded(bytes: Vec<u8>, total: u64) -> (String, Option<(Vec<u8>, u64)>) {
+    match String::from_utf8(bytes) {
+        Ok(text) if text.len() as u64 == total => (text, None),
+        Ok(text) => (text.clone(), Some((text.into_bytes(), total))),
+        Err(error) => {
+            let bytes = error.into_bytes();
+            let text = String::from_utf8_lossy(&bytes).into_owned();
+
+            (text, Some((bytes, total)))
+        }
+    }
+}
+
+async fn measure(path: PathBuf) -> u64 {
+    probe::with_timeout(Probe::Size(path.clone()), move || {
+        fs::metadata(&path).map_or(0, |metadata| metadata.len())
+    })
+    .await
+    .unwrap_or_default()
+}
+
+async fn read_prefix(path: PathBuf) -> Result<(Vec<u8>, u64), String> {
+    let file = File::open(&path).map_err(|error| error.to_string())?;
+    let total = file.metadata().map_err(|error| error.to_string())?.len();
+
+    let mut bytes = vec![];
+
+    file.take(MAX_HEX_BYTES as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|error| error.to_string())?;
+
+    Ok((bytes, total))
+}
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(SNIFF_LENGTH)];
+
+    if sample.contains(&0) {
+        return true;
+    }
+
+    let control = sample
+        .iter()
+        .filter(|byte| {
+            byte.is_ascii_control() && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b)
+        })
+        .count();
+
+    control * 10 > sample.len()
+}
+
+fn into_text(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes)
+        .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned())
+}
+
+fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(HEX_ROW_LENGTH)
+        .enumerate()
+        .map(|(row, chunk)| hex_row(row, chunk))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn hex_row(row: usize, chunk: &[u8]) -> String {
+    let hex: Vec<_> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let ascii: String = chunk
+        .iter()
+        .map(|byte| {
+            if byte.is_ascii_graphic() || *byte == b' ' {
+                *byte as char
+            } else {
+                '.'
+            }
+        })
+        .collect();
+
+    format!(
+        "{:08x}  {:<width$}  |{}|",
+        row * HEX_ROW_LENGTH,
+        hex.join(" "),
+        ascii,
+        width = HEX_ROW_LENGTH * 3 - 1
+    )
+}
+
+fn edited_dump<'a>(bytes: &[u8], cursor: usize) -> Column<'a, Message> {
+    let row = cursor / HEX_ROW_LENGTH;
+    let start = row * HEX_ROW_LENGTH;
+    let end = (start + HEX_ROW_LENGTH).min(bytes.len());
+
+    let line = hex_row(row, &bytes[start..end]);
+    let column = 10 + (cursor - start) * 3;
+
+    let text = |text: &str| Text::new(text).font(font::MONOSPACE);
+
+    let mut lines = Column::new();
+
+    if start > 0 {
+        lines = lines.push(text(&hex_dump(&bytes[..start])));
+    }
+
+    lines = lines.push(
+        Row::new()
+            .push(text(&line[..column]))
+            .push(text(&line[column..column + 2]).color(style::SEARCH_MATCH))
+            .push(text(&line[column + 2..])),
+    );
+
+    if end < bytes.len() {
+        let after: Vec<_> = bytes[end..]
+            .chunks(HEX_ROW_LENGTH)
+            .enumerate()
+            .map(|(offset, chunk)| hex_row(row + 1 + offset, chunk))
+            .collect();
+
+        lines = lines.push(text(&after.join("\n")));
+    }
+
+    lines
+}
+
+async fn write_bytes(path: PathBuf, bytes: Vec<u8>, whole: bool) -> Result<(), String> {
+    if whole {
+        fs::write(&path, &bytes)
+    } else {
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(&bytes))
+    }
+    .map_err(|error| error.to_string())
+}
+
+fn blamed_lines<'a, Message: 'a>(
+    text: &'a str,
+    blame: &[BlameLine],
+    options: Options,
+) -> Element<'a, Message> {
+    text.lines()
+        .enumerate()
+        .fold(Column::new(), |lines, (index, line)| {
+            let annotation = blame
+                .get(index)
+                .map(|blame| {
+                    let age = blame.time.map(format::age).unwrap_or_default();
+
+                    format!("{} {:<12.12} {:>4}", blame.commit, blame.author, age)
+                })
+                .unwrap_or_default();
+
+            lines.push(
+                Row::new()
+                    .spacing(10)
+                    .push(
+                        Text::new((index + 1).to_string())
+                            .font(font::MONOSPACE)
+                            .size(14)
+                            .color(style::GUTTER)
+                            .width(Length::Units(40)),
+                    )
+                    .push(
+                        Text::new(annotation)
+                            .font(font::MONOSPACE)
+                            .size(14)
+                            .color(style::GUTTER)
+                            .width(Length::Units(240)),
+                    )
+                    .push(Text::new(render(line, options)).font(options.font)),
+            )
+        })
+        .into()
+}
+
+fn log_lines<'a, Message: 'a>(
+    text: &str,
+    hidden: &HashSet<Level>,
+    options: Options,
+) -> Element<'a, Message> {
+    let mut level = None;
+
+    text.lines()
+        .fold(Column::new(), |lines, line| {
+            level = Level::detect(line).or(level);
+
+            if level.is_some_and(|level| hidden.contains(&level)) {
+                return lines;
+            }
+
+            let text = Text::new(render(line, options)).font(options.font);
+
+            match level.and_then(Level::color) {
+                Some(color) => lines.push(text.color(color)),
+                None => lines.push(text),
+            }
+        })
+        .into()
+}
+
+fn is_log(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("log"))
+}
+
+fn render(line: &str, options: Options) -> String {
+    let tab_width = options.tab_width.max(1);
+    let content_end = if options.show_invisibles {
+        line.trim_end_matches(' ').len()
+    } else {
+        line.len()
+    };
+
+    let mut rendered = String::with_capacity(line.len());
+    let mut column = 0;
+
+    for (index, character) in line.char_indices() {
+        match character {
+            '\t' => {
+                let width = tab_width - column % tab_width;
+
+                if options.show_invisibles {
+                    rendered.push('→');
+                    rendered.extend(std::iter::repeat_n(' ', width - 1));
+                } else {
+                    rendered.extend(std::iter::repeat_n(' ', width));
+                }
+
+                column += width;
+            }
+            ' ' if index >= content_end => {
+                rendered.push('·');
+                column += 1;
+            }
+            character => {
+                rendered.push(character);
+                column += 1;
+            }
+        }
+    }
+
+    rendered
+}
+
+fn diff_color(line: &str) -> Option<Color> {
+    if line.starts_with("+++") || line.starts_with("---") {
+        None
+    } else if line.starts_with('+') {
+        Some(style::DIFF_ADDED)
+    } else if line.starts_with('-') {
+        Some(style::DIFF_REMOVED)
+    } else if line.starts_with("@@") {
+        Some(style::DIFF_HUNK)
+    } else {
+        None
+    }
+}
+
+struct Follow {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl<H, I> Recipe<H, I> for Follow
+where
+    H: std::hash::Hasher,
+{
+    type Output = Message;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.path.hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Message> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        thread::spawn(move || {
+            let Follow { path, mut offset } = *self;
+            let mut pending = vec![];
+
+            while !sender.is_closed() {
+                thread::sleep(Duration::from_millis(500));
+
+                let size = match fs::metadata(&path) {
+                    Ok(metadata) => metadata.len(),
+                    Err(_) => continue,
+                };
+
+                if size < offset {
+                    offset = 0;
+                    pending.clear();
+
+                    let _ = sender.unbounded_send(Message::Truncated);
+                }
+
+                if size == offset {
+                    continue;
+                }
+
+                let read = File::open(&path).and_then(|mut file| {
+                    file.seek(SeekFrom::Start(offset))?;
+                    file.take(size - offset).read_to_end(&mut pending)
+                });
+
+                let read = match read {
+                    Ok(read) => read,
+                    Err(_) => continue,
+                };
+
+                offset += read as u64;
+
+                if let Some(end) = pending.iter().rposition(|byte| *byte == b'\n') {
+                    let complete: Vec<_> = pending.drain(..=end).collect();
+
+                    let _ = sender.unbounded_send(Message::Appended(into_text(complete)));
+                }
+            }
+        });
+
+        receiver.boxed()
+    }
+}
+
+struct WheelWatch<'a, Renderer> {
+    content: iced_native::Element<'a, Message, Renderer>,
+}
+
+impl<'a, Renderer> Widget<Message, Renderer> for WheelWatch<'a, Renderer>
+where
+    Renderer: iced_native::Renderer,
+{
+    fn width(&self) -> Length {
+        self.content.width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.layout(renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        defaults: &Renderer::Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) -> Renderer::Output {
+        self.content
+            .draw(renderer, defaults, layout, cursor_position, viewport)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        self.content.hash_layout(state);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        if let Event::Mouse(mouse::Event::WheelScrolled { delta }) = &event {
+            if layout.bounds().contains(cursor_position) {
+                let y = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => {
+                        *y
+                    }
+                };
+
+                messages.push(Message::Scrolled { up: y > 0.0 });
+            }
+        }
+
+        self.content.on_event(
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            messages,
+        )
+    }
+
+    fn overlay(&mut self, layout: Layout<'_>) -> Option<overlay::Element<'_, Message, Renderer>> {
+        self.content.overlay(layout)
+    }
+}
+
+impl<'a, Renderer> From<WheelWatch<'a, Renderer>> for iced_native::Element<'a, Message, Renderer>
+where
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(wheel_watch: WheelWatch<'a, Renderer>) -> Self {
+        iced_native::Element::new(wheel_watch)
+    }
+}
+
+struct Measured<'a, Message, Renderer> {
+    extent: &'a Cell<Extent>,
+    content: iced_native::Element<'a, Message, Renderer>,
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Measured<'a, Message, Renderer>
+where
+    Renderer: iced_native::Renderer,
+{
+    fn width(&self) -> Length {
+        self.content.width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.layout(renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        defaults: &Renderer::Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) -> Renderer::Output {
+        let bounds = layout.bounds();
+
+        self.extent.set(Extent {
+            bounds,
+            content_bounds: layout
+                .children()
+                .next()
+                .map_or(bounds, |content| content.bounds()),
+        });
+
+        self.content
+            .draw(renderer, defaults, layout, cursor_position, viewport)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        self.content.hash_layout(state);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        self.content.on_event(
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            messages,
+        )
+    }
+
+    fn overlay(&mut self, layout: Layout<'_>) -> Option<overlay::Element<'_, Message, Renderer>> {
+        self.content.overlay(layout)
+    }
+}
+
+impl<'a, Message, Renderer> From<Measured<'a, Message, Renderer>>
+    for iced_native::Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(measured: Measured<'a, Message, Renderer>) -> Self {
+        iced_native::Element::new(measured)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_bytes(document: &Document) -> &[u8] {
+        match &document.content {
+            Content::Hex { bytes, .. } => bytes,
+            _ => panic!("expected the hex view"),
+        }
+    }
+
+    #[test]
+    fn non_utf8_files_round_trip_through_the_hex_editor() {
+        let original = b"caf\xe9 au lait\n\xff\xfe end\n".to_vec();
+        let path = std::env::temp_dir().join(format!("viewer-round-trip-{}", std::process::id()));
+        fs::write(&path, &original).unwrap();
+
+        let mut document = Document::file(path.clone(), fs::read(&path).unwrap());
+        assert!(matches!(document.content, Content::Text(_)));
+
+        let _ = document.update(Message::ShowAsHex);
+        assert_eq!(hex_bytes(&document), &original[..]);
+
+        let _ = document.update(Message::ShowAsText);
+        let _ = document.update(Message::ShowAsHex);
+        assert_eq!(hex_bytes(&document), &original[..]);
+
+        let _ = document.update(Message::ToggleEditing);
+        let _ = document.update(Message::Edit(Edit::Digit(0x4)));
+        let _ = document.update(Message::Edit(Edit::Digit(0x3)));
+        assert!(document.is_dirty());
+
+        let mut edited = original.clone();
+        edited[0] = b'C';
+        assert_eq!(hex_bytes(&document), &edited[..]);
+
+        let bytes = hex_bytes(&document).to_vec();
+        async_std::task::block_on(write_bytes(path.clone(), bytes, true)).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), edited);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn prefixes_keep_their_total_size_when_shown_as_text() {
+        let mut document = Document::new(
+            PathBuf::from("large.bin"),
+            Content::Hex {
+                bytes: b"abc".to_vec(),
+                total: 1000,
+            },
+        );
+
+        let _ = document.update(Message::ShowAsText);
+        let _ = document.update(Message::ShowAsHex);
+
+        match document.content {
+            Content::Hex { bytes, total } => {
+                assert_eq!(bytes, b"abc");
+                assert_eq!(total, 1000);
+            }
+            _ => panic!("expected the hex view"),
+        }
+    }
+
+    #[test]
+    fn followed_text_cannot_be_hex_edited() {
+        let mut document = Document::new(PathBuf::from("log"), Content::Text("a\n".into()));
+        document.following = true;
+
+        let _ = document.update(Message::Appended("b\u{fffd}\n".into()));
+        let _ = document.update(Message::ShowAsHex);
+        let _ = document.update(Message::ToggleEditing);
+
+        assert!(!document.is_editing());
+    }
+}
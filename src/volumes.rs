@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Volume {
+    pub root: PathBuf,
+    pub space: Option<Space>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Space {
+    pub total: u64,
+    pub free: u64,
+}
+
+impl Space {
+    pub fn used(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.total.saturating_sub(self.free) as f32 / self.total as f32
+    }
+}
+
+pub fn list() -> Vec<Volume> {
+    roots()
+        .into_iter()
+        .map(|root| Volume {
+            space: space(&root),
+            root,
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn roots() -> Vec<PathBuf> {
+    const PSEUDO_FILESYSTEMS: &[&str] = &[
+        "autofs",
+        "binfmt_misc",
+        "bpf",
+        "cgroup",
+        "cgroup2",
+        "configfs",
+        "debugfs",
+        "devpts",
+        "devtmpfs",
+        "efivarfs",
+        "fusectl",
+        "hugetlbfs",
+        "mqueue",
+        "nsfs",
+        "overlay",
+        "proc",
+        "pstore",
+        "securityfs",
+        "squashfs",
+        "sysfs",
+        "tmpfs",
+        "tracefs",
+    ];
+
+    let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+
+    let mut roots: Vec<_> = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let target = fields.nth(1)?;
+            let kind = fields.next()?;
+
+            if PSEUDO_FILESYSTEMS.contains(&kind) {
+                return None;
+            }
+
+            Some(PathBuf::from(unescape(target)))
+        })
+        .filter(|root| root.is_dir())
+        .collect();
+
+    roots.push(PathBuf::from("/"));
+    roots.sort();
+    roots.dedup();
+
+    roots
+}
+
+#[cfg(target_os = "linux")]
+pub fn mount_points() -> Vec<PathBuf> {
+    std::fs::read_to_string("/proc/mounts")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(|target| PathBuf::from(unescape(target)))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut rest = field;
+
+    while let Some(index) = rest.find('\\') {
+        unescaped.push_str(&rest[..index]);
+
+        let code = rest
+            .get(index + 1..index + 4)
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+
+        match code {
+            Some(code) => {
+                unescaped.push(code as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+
+    unescaped
+}
+
+#[cfg(target_os = "linux")]
+pub fn space(root: &Path) -> Option<Space> {
+    use std::ffi::CString;
+    use std::mem;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(root.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { mem::zeroed() };
+
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let block = stat.f_frsize as u64;
+
+    Some(Space {
+        total: stat.f_blocks as u64 * block,
+        free: stat.f_bavail as u64 * block,
+    })
+}
+
+#[cfg(windows)]
+fn roots() -> Vec<PathBuf> {
+    (b'A'..=b'Z')
+        .map(|letter| PathBuf::from(format!("{}:\\", letter as char)))
+        .filter(|root| root.exists())
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn roots() -> Vec<PathBuf> {
+    let mounted = std::fs::read_dir("/Volumes")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|root| root.is_dir());
+
+    std::iter::once(PathBuf::from("/")).chain(mounted).collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn space(_root: &Path) -> Option<Space> {
+    None
+}
@@ -0,0 +1,198 @@
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{BoxStream, StreamExt};
+use iced::Subscription;
+use iced_native::subscription::Recipe;
+
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+#[derive(Debug, Clone)]
+pub enum Change {
+    Created(PathBuf),
+    Removed(PathBuf),
+    Renamed { from: PathBuf, to: PathBuf },
+    Modified(PathBuf),
+    Rescan(PathBuf),
+    Unavailable(PathBuf),
+}
+
+impl Change {
+    pub fn directory(&self) -> Option<&Path> {
+        match self {
+            Change::Created(path) | Change::Removed(path) | Change::Modified(path) => path.parent(),
+            Change::Renamed { to, .. } => to.parent(),
+            Change::Rescan(directory) | Change::Unavailable(directory) => Some(directory),
+        }
+    }
+}
+
+pub fn is_supported() -> bool {
+    cfg!(target_os = "linux")
+}
+
+pub fn watch(directory: PathBuf) -> Subscription<Change> {
+    Subscription::from_recipe(Watch { directory })
+}
+
+struct Watch {
+    directory: PathBuf,
+}
+
+impl<H, I> Recipe<H, I> for Watch
+where
+    H: std::hash::Hasher,
+{
+    type Output = Change;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.directory.hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Change> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        thread::spawn(move || {
+            if run(&self.directory, &sender).is_err() {
+                let _ = sender.unbounded_send(Change::Unavailable(self.directory.clone()));
+            }
+        });
+
+        receiver.boxed()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn run(directory: &std::path::Path, sender: &mpsc::UnboundedSender<Change>) -> std::io::Result<()> {
+    use std::collections::HashMap;
+    use std::ffi::{CString, OsStr};
+    use std::io;
+    use std::mem;
+    use std::os::unix::ffi::OsStrExt;
+    use std::time::{Duration, Instant};
+
+    const PAIRING_WINDOW: Duration = Duration::from_millis(100);
+
+    let path = CString::new(directory.as_os_str().as_bytes())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+    let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mask = libc::IN_CREATE
+        | libc::IN_DELETE
+        | libc::IN_MOVED_FROM
+        | libc::IN_MOVED_TO
+        | libc::IN_CLOSE_WRITE
+        | libc::IN_DELETE_SELF
+        | libc::IN_MOVE_SELF;
+
+    if unsafe { libc::inotify_add_watch(fd, path.as_ptr(), mask) } < 0 {
+        let error = io::Error::last_os_error();
+
+        unsafe { libc::close(fd) };
+
+        return Err(error);
+    }
+
+    let mut buffer = [0u8; 4096];
+    let mut moved_from: HashMap<u32, (PathBuf, Instant)> = HashMap::new();
+    let header = mem::size_of::<libc::inotify_event>();
+
+    while !sender.is_closed() {
+        let mut poll_fd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        let timeout = if moved_from.is_empty() {
+            500
+        } else {
+            PAIRING_WINDOW.as_millis() as libc::c_int
+        };
+
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, timeout) };
+
+        moved_from.retain(|_, (from, since)| {
+            if since.elapsed() < PAIRING_WINDOW {
+                return true;
+            }
+
+            let _ = sender.unbounded_send(Change::Removed(from.clone()));
+
+            false
+        });
+
+        if ready <= 0 {
+            continue;
+        }
+
+        let read =
+            unsafe { libc::read(fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len()) };
+
+        if read <= 0 {
+            break;
+        }
+
+        let mut offset = 0;
+
+        while offset + header <= read as usize {
+            let event: libc::inotify_event =
+                unsafe { std::ptr::read_unaligned(buffer.as_ptr().add(offset) as *const _) };
+
+            let name_start = offset + header;
+            let name_end = name_start + event.len as usize;
+            offset = name_end;
+
+            let name = buffer[name_start..name_end.min(read as usize)]
+                .split(|byte| *byte == 0)
+                .next()
+                .unwrap_or_default();
+            let path = directory.join(OsStr::from_bytes(name));
+
+            let change = if event.mask & libc::IN_Q_OVERFLOW != 0
+                || event.mask & (libc::IN_DELETE_SELF | libc::IN_MOVE_SELF) != 0
+            {
+                Some(Change::Rescan(directory.to_path_buf()))
+            } else if event.mask & libc::IN_MOVED_FROM != 0 {
+                moved_from.insert(event.cookie, (path, Instant::now()));
+
+                None
+            } else if event.mask & libc::IN_MOVED_TO != 0 {
+                match moved_from.remove(&event.cookie) {
+                    Some((from, _)) => Some(Change::Renamed { from, to: path }),
+                    None => Some(Change::Created(path)),
+                }
+            } else if event.mask & libc::IN_CREATE != 0 {
+                Some(Change::Created(path))
+            } else if event.mask & libc::IN_DELETE != 0 {
+                Some(Change::Removed(path))
+            } else if event.mask & libc::IN_CLOSE_WRITE != 0 {
+                Some(Change::Modified(path))
+            } else {
+                None
+            };
+
+            if let Some(change) = change {
+                let _ = sender.unbounded_send(change);
+            }
+        }
+    }
+
+    unsafe { libc::close(fd) };
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn run(
+    _directory: &std::path::Path,
+    _sender: &mpsc::UnboundedSender<Change>,
+) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
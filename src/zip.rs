@@ -0,0 +1,355 @@
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+const DIRECTORY_ENTRY: u32 = 0x0201_4b50;
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const MAX_COMMENT: usize = 0xffff;
+const MAX_UNCOMPRESSED: usize = 16 * 1024 * 1024;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+pub fn read(archive: &[u8], name: &str) -> Option<Vec<u8>> {
+    let end = (archive.len().saturating_sub(22 + MAX_COMMENT)..=archive.len().checked_sub(22)?)
+        .rev()
+        .find(|offset| u32_at(archive, *offset) == Some(END_OF_DIRECTORY))?;
+
+    let count = u16_at(archive, end + 10)?;
+    let mut offset = u32_at(archive, end + 16)? as usize;
+
+    for _ in 0..count {
+        if u32_at(archive, offset)? != DIRECTORY_ENTRY {
+            return None;
+        }
+
+        let method = u16_at(archive, offset + 10)?;
+        let compressed = u32_at(archive, offset + 20)? as usize;
+        let uncompressed = u32_at(archive, offset + 24)? as usize;
+        let name_length = u16_at(archive, offset + 28)? as usize;
+        let extra_length = u16_at(archive, offset + 30)? as usize;
+        let comment_length = u16_at(archive, offset + 32)? as usize;
+        let header = u32_at(archive, offset + 42)? as usize;
+        let entry_name = archive.get(offset + 46..offset + 46 + name_length)?;
+
+        if entry_name == name.as_bytes() {
+            if u32_at(archive, header)? != LOCAL_HEADER || uncompressed > MAX_UNCOMPRESSED {
+                return None;
+            }
+
+            let start = header
+                + 30
+                + u16_at(archive, header + 26)? as usize
+                + u16_at(archive, header + 28)? as usize;
+            let data = archive.get(start..start.checked_add(compressed)?)?;
+
+            return match method {
+                STORED if compressed == uncompressed => Some(data.to_vec()),
+                DEFLATED => inflate(data, uncompressed),
+                _ => None,
+            };
+        }
+
+        offset += 46 + name_length + extra_length + comment_length;
+    }
+
+    None
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes([
+        *bytes.get(offset)?,
+        *bytes.get(offset + 1)?,
+    ]))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes([
+        *bytes.get(offset)?,
+        *bytes.get(offset + 1)?,
+        *bytes.get(offset + 2)?,
+        *bytes.get(offset + 3)?,
+    ]))
+}
+
+struct Bits<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl Bits<'_> {
+    fn bit(&mut self) -> Option<u32> {
+        let byte = *self.data.get(self.position / 8)?;
+        let bit = (byte >> (self.position % 8)) & 1;
+
+        self.position += 1;
+
+        Some(u32::from(bit))
+    }
+
+    fn bits(&mut self, count: u8) -> Option<u32> {
+        (0..count).try_fold(0, |value, shift| Some(value | self.bit()? << shift))
+    }
+
+    fn align(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+}
+
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0; 16];
+
+        for length in lengths {
+            counts[usize::from(*length)] += 1;
+        }
+
+        counts[0] = 0;
+
+        let mut symbols: Vec<u16> = (0..lengths.len() as u16)
+            .filter(|symbol| lengths[usize::from(*symbol)] != 0)
+            .collect();
+
+        symbols.sort_by_key(|symbol| lengths[usize::from(*symbol)]);
+
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, bits: &mut Bits<'_>) -> Option<u16> {
+        let (mut code, mut first, mut index) = (0, 0, 0);
+
+        for count in &self.counts[1..] {
+            let count = i32::from(*count);
+
+            code |= bits.bit()? as i32;
+
+            if code - first < count {
+                return self.symbols.get((index + code - first) as usize).copied();
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        None
+    }
+}
+
+fn inflate(data: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let mut bits = Bits { data, position: 0 };
+    let mut output = Vec::with_capacity(limit);
+
+    loop {
+        let last = bits.bit()? == 1;
+
+        match bits.bits(2)? {
+            0 => {
+                bits.align();
+
+                let start = bits.position / 8;
+                let length = usize::from(u16_at(data, start)?);
+
+                if output.len() + length > limit {
+                    return None;
+                }
+
+                output.extend_from_slice(data.get(start + 4..start + 4 + length)?);
+                bits.position = (start + 4 + length) * 8;
+            }
+            1 => {
+                let mut lengths = [0; 288];
+
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+
+                let literals = Huffman::new(&lengths);
+                let distances = Huffman::new(&[5; 30]);
+
+                codes(&mut bits, &mut output, limit, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic(&mut bits)?;
+
+                codes(&mut bits, &mut output, limit, &literals, &distances)?;
+            }
+            _ => return None,
+        }
+
+        if last {
+            return Some(output);
+        }
+    }
+}
+
+fn dynamic(bits: &mut Bits<'_>) -> Option<(Huffman, Huffman)> {
+    let literal_count = bits.bits(5)? as usize + 257;
+    let distance_count = bits.bits(5)? as usize + 1;
+    let code_length_count = bits.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0; 19];
+
+    for position in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[*position] = bits.bits(3)? as u8;
+    }
+
+    let code_lengths = Huffman::new(&code_lengths);
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+
+    while lengths.len() < literal_count + distance_count {
+        let (value, repeat) = match code_lengths.decode(bits)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last()?, 3 + bits.bits(2)?),
+            17 => (0, 3 + bits.bits(3)?),
+            18 => (0, 11 + bits.bits(7)?),
+            _ => return None,
+        };
+
+        lengths.extend(std::iter::repeat_n(value, repeat as usize));
+    }
+
+    if lengths.len() != literal_count + distance_count {
+        return None;
+    }
+
+    Some((
+        Huffman::new(&lengths[..literal_count]),
+        Huffman::new(&lengths[literal_count..]),
+    ))
+}
+
+fn codes(
+    bits: &mut Bits<'_>,
+    output: &mut Vec<u8>,
+    limit: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Option<()> {
+    loop {
+        let symbol = usize::from(literals.decode(bits)?);
+
+        match symbol {
+            0..=255 if output.len() < limit => output.push(symbol as u8),
+            0..=255 => return None,
+            256 => return Some(()),
+            _ => {
+                let code = symbol - 257;
+                let length = usize::from(*LENGTH_BASE.get(code)?)
+                    + bits.bits(*LENGTH_EXTRA.get(code)?)? as usize;
+
+                let code = usize::from(distances.decode(bits)?);
+                let distance = usize::from(*DISTANCE_BASE.get(code)?)
+                    + bits.bits(*DISTANCE_EXTRA.get(code)?)? as usize;
+
+                let start = output.len().checked_sub(distance)?;
+
+                if output.len() + length > limit {
+                    return None;
+                }
+
+                for offset in 0..length {
+                    output.push(output[start + offset]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(name: &str, method: u16, data: &[u8], uncompressed: u32) -> Vec<u8> {
+        let mut archive = vec![];
+
+        archive.extend(LOCAL_HEADER.to_le_bytes());
+        archive.extend([0; 22]);
+        archive.extend((name.len() as u16).to_le_bytes());
+        archive.extend([0; 2]);
+        archive.extend(name.as_bytes());
+        archive.extend(data);
+
+        let directory = archive.len() as u32;
+
+        archive.extend(DIRECTORY_ENTRY.to_le_bytes());
+        archive.extend([0; 6]);
+        archive.extend(method.to_le_bytes());
+        archive.extend([0; 8]);
+        archive.extend((data.len() as u32).to_le_bytes());
+        archive.extend(uncompressed.to_le_bytes());
+        archive.extend((name.len() as u16).to_le_bytes());
+        archive.extend([0; 12]);
+        archive.extend(0u32.to_le_bytes());
+        archive.extend(name.as_bytes());
+
+        let size = archive.len() as u32 - directory;
+
+        archive.extend(END_OF_DIRECTORY.to_le_bytes());
+        archive.extend([0; 6]);
+        archive.extend(1u16.to_le_bytes());
+        archive.extend(size.to_le_bytes());
+        archive.extend(directory.to_le_bytes());
+        archive.extend([0; 2]);
+
+        archive
+    }
+
+    #[test]
+    fn reads_stored_entries() {
+        let zip = archive("content.xml", STORED, b"<text:p>hi</text:p>", 19);
+
+        assert_eq!(
+            read(&zip, "content.xml").as_deref(),
+            Some(&b"<text:p>hi</text:p>"[..])
+        );
+        assert_eq!(read(&zip, "other.xml"), None);
+    }
+
+    #[test]
+    fn inflates_deflated_entries() {
+        let data = [
+            179, 41, 183, 42, 177, 203, 72, 205, 201, 201, 87, 192, 32, 109, 244, 65, 146, 0,
+        ];
+        let zip = archive("word/document.xml", DEFLATED, &data, 34);
+
+        assert_eq!(
+            read(&zip, "word/document.xml").as_deref(),
+            Some(&b"<w:t>hello hello hello hello</w:t>"[..])
+        );
+    }
+
+    #[test]
+    fn stops_inflating_past_the_recorded_size() {
+        let data = [75, 76, 28, 5, 163, 96, 20, 12, 119, 0, 0];
+
+        assert_eq!(
+            read(&archive("a", DEFLATED, &data, 1000), "a").map(|bytes| bytes.len()),
+            Some(1000)
+        );
+        assert_eq!(read(&archive("a", DEFLATED, &data, 999), "a"), None);
+        assert_eq!(read(&archive("a", DEFLATED, &data, u32::MAX), "a"), None);
+    }
+}